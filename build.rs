@@ -1,10 +1,27 @@
+#[cfg(feature = "cli")]
 use clap::CommandFactory;
+#[cfg(feature = "cli")]
 use clap_complete::{generate_to, shells::*};
-use std::{env, fs, path::PathBuf, process};
+use std::process;
+#[cfg(feature = "cli")]
+use std::{fs, path::PathBuf};
 
+#[cfg(feature = "cli")]
 include!("src/bin/envio/clap_app.rs");
 
 fn main() {
+    // The completions and manpages are only needed by the binary
+    #[cfg(feature = "cli")]
+    generate_cli_files();
+
+    let build_timestamp: String = get_buildtimestamp();
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
+    println!("cargo:rustc-env=BUILD_VERSION={}", get_version());
+}
+
+/// Generate the completions and manpages for the CLI application
+#[cfg(feature = "cli")]
+fn generate_cli_files() {
     let mut cmd = ClapApp::command();
     let app_name = cmd.get_name().to_string();
 
@@ -26,13 +43,10 @@ fn main() {
     if let Err(e) = generate_manpages(cmd, manpage_dir) {
         panic!("Error: {}", e);
     }
-
-    let build_timestamp: String = get_buildtimestamp();
-    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
-    println!("cargo:rustc-env=BUILD_VERSION={}", get_version());
 }
 
 /// Generate manpages for the CLI application
+#[cfg(feature = "cli")]
 fn generate_manpages(cmd: clap::Command, out_dir: &str) -> std::io::Result<()> {
     let man = clap_mangen::Man::new(cmd);
     let mut buffer: Vec<u8> = Default::default();
@@ -44,6 +58,7 @@ fn generate_manpages(cmd: clap::Command, out_dir: &str) -> std::io::Result<()> {
 }
 
 /// Generate completions for the CLI application
+#[cfg(feature = "cli")]
 fn generate_completions(
     cmd: &mut clap::Command,
     app_name: &str,
@@ -86,6 +101,7 @@ fn get_buildtimestamp() -> String {
 }
 
 /// Auxilliary function to create a directory
+#[cfg(feature = "cli")]
 fn create_dir(dir_name: &str) -> Result<(), std::io::Error> {
     fs::create_dir_all(dir_name)?;
 
@@ -25,14 +25,29 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Create a new profile')
             [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Add envionment variables to a profile')
-            [CompletionResult]::new('load', 'load', [CompletionResultType]::ParameterValue, 'Load all environment variables in a profile for use in your terminal sessions')
+            [CompletionResult]::new('load', 'load', [CompletionResultType]::ParameterValue, 'Load all environment variables in a profile into the current shell, requires the shell hook on unix (see `envio hook`)')
             [CompletionResult]::new('unload', 'unload', [CompletionResultType]::ParameterValue, 'Unload a profile')
+            [CompletionResult]::new('env', 'env', [CompletionResultType]::ParameterValue, 'Print the shell code that loads the environment variables of a profile into the current shell, e.g. eval "$(envio env <PROFILE_NAME>)"')
+            [CompletionResult]::new('hook', 'hook', [CompletionResultType]::ParameterValue, 'Print the shell functions that let `envio load` and `envio unload` change the current shell')
+            [CompletionResult]::new('allow', 'allow', [CompletionResultType]::ParameterValue, 'Allow the .envio file of the current directory to load its profile until the file changes')
+            [CompletionResult]::new('deny', 'deny', [CompletionResultType]::ParameterValue, 'Stop the .envio file of the current directory from loading its profile')
+            [CompletionResult]::new('hook-env', 'hook-env', [CompletionResultType]::ParameterValue, 'hook-env')
             [CompletionResult]::new('launch', 'launch', [CompletionResultType]::ParameterValue, 'Run a command with the environment variables from a profile')
             [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'Remove a environment variable from a profile')
             [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all the environment variables in a profile or all the profiles currenty stored')
             [CompletionResult]::new('update', 'update', [CompletionResultType]::ParameterValue, 'Update environment variables in a profile')
+            [CompletionResult]::new('edit', 'edit', [CompletionResultType]::ParameterValue, 'Edit the environment variables of a profile in your editor')
+            [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Generate a random secret and store it in a profile without printing it')
+            [CompletionResult]::new('rotate', 'rotate', [CompletionResultType]::ParameterValue, 'Replace the value of an environment variable with the output of a rotation command, which gets the current value on stdin')
             [CompletionResult]::new('export', 'export', [CompletionResultType]::ParameterValue, 'Export a profile to a file if no file is specified it will be exported to a file named .env')
             [CompletionResult]::new('import', 'import', [CompletionResultType]::ParameterValue, 'Download a profile over the internet and import it into the system or import a locally stored profile into your current envio installation')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Show, set or remove the validation schema of a profile')
+            [CompletionResult]::new('validate', 'validate', [CompletionResultType]::ParameterValue, 'Validate the environment variables of a profile against its schema')
+            [CompletionResult]::new('policy', 'policy', [CompletionResultType]::ParameterValue, 'Show or set what happens to the expired environment variables of a profile (warn, block, strip or default)')
+            [CompletionResult]::new('describe', 'describe', [CompletionResultType]::ParameterValue, 'Show or set the description, owner and notes of a profile')
+            [CompletionResult]::new('expiring', 'expiring', [CompletionResultType]::ParameterValue, 'List the environment variables of all profiles that have expired or expire soon')
+            [CompletionResult]::new('store', 'store', [CompletionResultType]::ParameterValue, 'Show or change where the profiles are stored')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Show or change the settings in the config file')
             [CompletionResult]::new('version', 'version', [CompletionResultType]::ParameterValue, 'Print the version')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
@@ -44,6 +59,18 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--envs', 'envs', [CompletionResultType]::ParameterName, 'envs')
             [CompletionResult]::new('-g', 'g', [CompletionResultType]::ParameterName, 'g')
             [CompletionResult]::new('--gpg-key-fingerprint', 'gpg-key-fingerprint', [CompletionResultType]::ParameterName, 'gpg-key-fingerprint')
+            [CompletionResult]::new('-E', 'E ', [CompletionResultType]::ParameterName, 'E')
+            [CompletionResult]::new('--expires', 'expires', [CompletionResultType]::ParameterName, 'expires')
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--schema', 'schema', [CompletionResultType]::ParameterName, 'schema')
+            [CompletionResult]::new('-F', 'F ', [CompletionResultType]::ParameterName, 'F')
+            [CompletionResult]::new('--from-file', 'from-file', [CompletionResultType]::ParameterName, 'from-file')
+            [CompletionResult]::new('-d', 'd', [CompletionResultType]::ParameterName, 'd')
+            [CompletionResult]::new('--description', 'description', [CompletionResultType]::ParameterName, 'description')
+            [CompletionResult]::new('-o', 'o', [CompletionResultType]::ParameterName, 'o')
+            [CompletionResult]::new('--owner', 'owner', [CompletionResultType]::ParameterName, 'owner')
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--notes', 'notes', [CompletionResultType]::ParameterName, 'notes')
             [CompletionResult]::new('-c', 'c', [CompletionResultType]::ParameterName, 'c')
             [CompletionResult]::new('--add-comments', 'add-comments', [CompletionResultType]::ParameterName, 'add-comments')
             [CompletionResult]::new('-x', 'x', [CompletionResultType]::ParameterName, 'x')
@@ -55,6 +82,14 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
         'envio;add' {
             [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
             [CompletionResult]::new('--envs', 'envs', [CompletionResultType]::ParameterName, 'envs')
+            [CompletionResult]::new('-E', 'E ', [CompletionResultType]::ParameterName, 'E')
+            [CompletionResult]::new('--expires', 'expires', [CompletionResultType]::ParameterName, 'expires')
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--sensitive', 'sensitive', [CompletionResultType]::ParameterName, 'sensitive')
+            [CompletionResult]::new('-F', 'F ', [CompletionResultType]::ParameterName, 'F')
+            [CompletionResult]::new('--from-file', 'from-file', [CompletionResultType]::ParameterName, 'from-file')
+            [CompletionResult]::new('-t', 't', [CompletionResultType]::ParameterName, 't')
+            [CompletionResult]::new('--tags', 'tags', [CompletionResultType]::ParameterName, 'tags')
             [CompletionResult]::new('-c', 'c', [CompletionResultType]::ParameterName, 'c')
             [CompletionResult]::new('--add-comments', 'add-comments', [CompletionResultType]::ParameterName, 'add-comments')
             [CompletionResult]::new('-x', 'x', [CompletionResultType]::ParameterName, 'x')
@@ -73,6 +108,37 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
+        'envio;env' {
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 'bash, zsh, fish, nu, pwsh, elvish, xonsh or tcsh [default: the current shell]')
+            [CompletionResult]::new('--shell', 'shell', [CompletionResultType]::ParameterName, 'bash, zsh, fish, nu, pwsh, elvish, xonsh or tcsh [default: the current shell]')
+            [CompletionResult]::new('-u', 'u', [CompletionResultType]::ParameterName, 'Print the code that removes the environment variables loaded before')
+            [CompletionResult]::new('--unset', 'unset', [CompletionResultType]::ParameterName, 'Print the code that removes the environment variables loaded before')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;hook' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'envio;allow' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;deny' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;hook-env' {
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--shell', 'shell', [CompletionResultType]::ParameterName, 'shell')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
         'envio;launch' {
             [CompletionResult]::new('-c', 'c', [CompletionResultType]::ParameterName, 'c')
             [CompletionResult]::new('--command', 'command', [CompletionResultType]::ParameterName, 'command')
@@ -98,6 +164,8 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--display-comments', 'display-comments', [CompletionResultType]::ParameterName, 'display-comments')
             [CompletionResult]::new('-x', 'x', [CompletionResultType]::ParameterName, 'x')
             [CompletionResult]::new('--display-expiration-date', 'display-expiration-date', [CompletionResultType]::ParameterName, 'display-expiration-date')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'r')
+            [CompletionResult]::new('--reveal', 'reveal', [CompletionResultType]::ParameterName, 'reveal')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
@@ -105,6 +173,12 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
         'envio;update' {
             [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
             [CompletionResult]::new('--envs', 'envs', [CompletionResultType]::ParameterName, 'envs')
+            [CompletionResult]::new('-E', 'E ', [CompletionResultType]::ParameterName, 'E')
+            [CompletionResult]::new('--expires', 'expires', [CompletionResultType]::ParameterName, 'expires')
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--sensitive', 'sensitive', [CompletionResultType]::ParameterName, 'sensitive')
+            [CompletionResult]::new('-t', 't', [CompletionResultType]::ParameterName, 't')
+            [CompletionResult]::new('--tags', 'tags', [CompletionResultType]::ParameterName, 'tags')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'v')
             [CompletionResult]::new('--update-values', 'update-values', [CompletionResultType]::ParameterName, 'update-values')
             [CompletionResult]::new('-c', 'c', [CompletionResultType]::ParameterName, 'c')
@@ -115,11 +189,44 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
+        'envio;edit' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;generate' {
+            [CompletionResult]::new('-T', 'T ', [CompletionResultType]::ParameterName, 'chars, hex, base64, uuid or words [default: chars]')
+            [CompletionResult]::new('--type', 'type', [CompletionResultType]::ParameterName, 'chars, hex, base64, uuid or words [default: chars]')
+            [CompletionResult]::new('-l', 'l', [CompletionResultType]::ParameterName, 'Number of characters, random bytes (hex and base64) or words [default: 32, words: 8, each word adds about 10.5 bits of entropy]')
+            [CompletionResult]::new('--length', 'length', [CompletionResultType]::ParameterName, 'Number of characters, random bytes (hex and base64) or words [default: 32, words: 8, each word adds about 10.5 bits of entropy]')
+            [CompletionResult]::new('-c', 'c', [CompletionResultType]::ParameterName, 'alphanumeric, letters, digits or symbols [default: alphanumeric]')
+            [CompletionResult]::new('--charset', 'charset', [CompletionResultType]::ParameterName, 'alphanumeric, letters, digits or symbols [default: alphanumeric]')
+            [CompletionResult]::new('--separator', 'separator', [CompletionResultType]::ParameterName, 'Separator between words [default: -]')
+            [CompletionResult]::new('-E', 'E ', [CompletionResultType]::ParameterName, 'E')
+            [CompletionResult]::new('--expires', 'expires', [CompletionResultType]::ParameterName, 'expires')
+            [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'p')
+            [CompletionResult]::new('--print', 'print', [CompletionResultType]::ParameterName, 'print')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;rotate' {
+            [CompletionResult]::new('-w', 'w', [CompletionResultType]::ParameterName, 'w')
+            [CompletionResult]::new('--with', 'with', [CompletionResultType]::ParameterName, 'with')
+            [CompletionResult]::new('-P', 'P ', [CompletionResultType]::ParameterName, 'Push the expiration date to this far from today, e.g. 90d or 6m [default: the rotation.period setting]')
+            [CompletionResult]::new('--period', 'period', [CompletionResultType]::ParameterName, 'Push the expiration date to this far from today, e.g. 90d or 6m [default: the rotation.period setting]')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
         'envio;export' {
             [CompletionResult]::new('-f', 'f', [CompletionResultType]::ParameterName, 'f')
             [CompletionResult]::new('--file-to-export-to', 'file-to-export-to', [CompletionResultType]::ParameterName, 'file-to-export-to')
             [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'e')
             [CompletionResult]::new('--envs', 'envs', [CompletionResultType]::ParameterName, 'envs')
+            [CompletionResult]::new('--format', 'format', [CompletionResultType]::ParameterName, 'format')
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 's')
+            [CompletionResult]::new('--include-sensitive', 'include-sensitive', [CompletionResultType]::ParameterName, 'include-sensitive')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
@@ -133,6 +240,122 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
+        'envio;schema' {
+            [CompletionResult]::new('-f', 'f', [CompletionResultType]::ParameterName, 'f')
+            [CompletionResult]::new('--file', 'file', [CompletionResultType]::ParameterName, 'file')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'r')
+            [CompletionResult]::new('--remove', 'remove', [CompletionResultType]::ParameterName, 'remove')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;validate' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;policy' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;describe' {
+            [CompletionResult]::new('-d', 'd', [CompletionResultType]::ParameterName, 'd')
+            [CompletionResult]::new('--description', 'description', [CompletionResultType]::ParameterName, 'description')
+            [CompletionResult]::new('-o', 'o', [CompletionResultType]::ParameterName, 'o')
+            [CompletionResult]::new('--owner', 'owner', [CompletionResultType]::ParameterName, 'owner')
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'n')
+            [CompletionResult]::new('--notes', 'notes', [CompletionResultType]::ParameterName, 'notes')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;expiring' {
+            [CompletionResult]::new('-w', 'w', [CompletionResultType]::ParameterName, 'w')
+            [CompletionResult]::new('--within', 'within', [CompletionResultType]::ParameterName, 'within')
+            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'Exit with status 1 if environment variables expire within the period, or with status 2 if a profile could not be checked')
+            [CompletionResult]::new('--exit-code', 'exit-code', [CompletionResultType]::ParameterName, 'Exit with status 1 if environment variables expire within the period, or with status 2 if a profile could not be checked')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('migrate', 'migrate', [CompletionResultType]::ParameterValue, 'Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'envio;store;migrate' {
+            [CompletionResult]::new('--to', 'to', [CompletionResultType]::ParameterName, 'to')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;store;help' {
+            [CompletionResult]::new('migrate', 'migrate', [CompletionResultType]::ParameterValue, 'Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'envio;store;help;migrate' {
+            break
+        }
+        'envio;store;help;help' {
+            break
+        }
+        'envio;config' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all settings and their values')
+            [CompletionResult]::new('get', 'get', [CompletionResultType]::ParameterValue, 'Print the value of a setting')
+            [CompletionResult]::new('set', 'set', [CompletionResultType]::ParameterValue, 'Change the value of a setting')
+            [CompletionResult]::new('unset', 'unset', [CompletionResultType]::ParameterValue, 'Reset a setting to its default value')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'envio;config;list' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;config;get' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;config;set' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;config;unset' {
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'envio;config;help' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all settings and their values')
+            [CompletionResult]::new('get', 'get', [CompletionResultType]::ParameterValue, 'Print the value of a setting')
+            [CompletionResult]::new('set', 'set', [CompletionResultType]::ParameterValue, 'Change the value of a setting')
+            [CompletionResult]::new('unset', 'unset', [CompletionResultType]::ParameterValue, 'Reset a setting to its default value')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'envio;config;help;list' {
+            break
+        }
+        'envio;config;help;get' {
+            break
+        }
+        'envio;config;help;set' {
+            break
+        }
+        'envio;config;help;unset' {
+            break
+        }
+        'envio;config;help;help' {
+            break
+        }
         'envio;version' {
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'v')
             [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'verbose')
@@ -143,14 +366,29 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
         'envio;help' {
             [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Create a new profile')
             [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Add envionment variables to a profile')
-            [CompletionResult]::new('load', 'load', [CompletionResultType]::ParameterValue, 'Load all environment variables in a profile for use in your terminal sessions')
+            [CompletionResult]::new('load', 'load', [CompletionResultType]::ParameterValue, 'Load all environment variables in a profile into the current shell, requires the shell hook on unix (see `envio hook`)')
             [CompletionResult]::new('unload', 'unload', [CompletionResultType]::ParameterValue, 'Unload a profile')
+            [CompletionResult]::new('env', 'env', [CompletionResultType]::ParameterValue, 'Print the shell code that loads the environment variables of a profile into the current shell, e.g. eval "$(envio env <PROFILE_NAME>)"')
+            [CompletionResult]::new('hook', 'hook', [CompletionResultType]::ParameterValue, 'Print the shell functions that let `envio load` and `envio unload` change the current shell')
+            [CompletionResult]::new('allow', 'allow', [CompletionResultType]::ParameterValue, 'Allow the .envio file of the current directory to load its profile until the file changes')
+            [CompletionResult]::new('deny', 'deny', [CompletionResultType]::ParameterValue, 'Stop the .envio file of the current directory from loading its profile')
+            [CompletionResult]::new('hook-env', 'hook-env', [CompletionResultType]::ParameterValue, 'hook-env')
             [CompletionResult]::new('launch', 'launch', [CompletionResultType]::ParameterValue, 'Run a command with the environment variables from a profile')
             [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'Remove a environment variable from a profile')
             [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all the environment variables in a profile or all the profiles currenty stored')
             [CompletionResult]::new('update', 'update', [CompletionResultType]::ParameterValue, 'Update environment variables in a profile')
+            [CompletionResult]::new('edit', 'edit', [CompletionResultType]::ParameterValue, 'Edit the environment variables of a profile in your editor')
+            [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Generate a random secret and store it in a profile without printing it')
+            [CompletionResult]::new('rotate', 'rotate', [CompletionResultType]::ParameterValue, 'Replace the value of an environment variable with the output of a rotation command, which gets the current value on stdin')
             [CompletionResult]::new('export', 'export', [CompletionResultType]::ParameterValue, 'Export a profile to a file if no file is specified it will be exported to a file named .env')
             [CompletionResult]::new('import', 'import', [CompletionResultType]::ParameterValue, 'Download a profile over the internet and import it into the system or import a locally stored profile into your current envio installation')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Show, set or remove the validation schema of a profile')
+            [CompletionResult]::new('validate', 'validate', [CompletionResultType]::ParameterValue, 'Validate the environment variables of a profile against its schema')
+            [CompletionResult]::new('policy', 'policy', [CompletionResultType]::ParameterValue, 'Show or set what happens to the expired environment variables of a profile (warn, block, strip or default)')
+            [CompletionResult]::new('describe', 'describe', [CompletionResultType]::ParameterValue, 'Show or set the description, owner and notes of a profile')
+            [CompletionResult]::new('expiring', 'expiring', [CompletionResultType]::ParameterValue, 'List the environment variables of all profiles that have expired or expire soon')
+            [CompletionResult]::new('store', 'store', [CompletionResultType]::ParameterValue, 'Show or change where the profiles are stored')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Show or change the settings in the config file')
             [CompletionResult]::new('version', 'version', [CompletionResultType]::ParameterValue, 'Print the version')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
@@ -167,6 +405,21 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
         'envio;help;unload' {
             break
         }
+        'envio;help;env' {
+            break
+        }
+        'envio;help;hook' {
+            break
+        }
+        'envio;help;allow' {
+            break
+        }
+        'envio;help;deny' {
+            break
+        }
+        'envio;help;hook-env' {
+            break
+        }
         'envio;help;launch' {
             break
         }
@@ -179,12 +432,62 @@ Register-ArgumentCompleter -Native -CommandName 'envio' -ScriptBlock {
         'envio;help;update' {
             break
         }
+        'envio;help;edit' {
+            break
+        }
+        'envio;help;generate' {
+            break
+        }
+        'envio;help;rotate' {
+            break
+        }
         'envio;help;export' {
             break
         }
         'envio;help;import' {
             break
         }
+        'envio;help;schema' {
+            break
+        }
+        'envio;help;validate' {
+            break
+        }
+        'envio;help;policy' {
+            break
+        }
+        'envio;help;describe' {
+            break
+        }
+        'envio;help;expiring' {
+            break
+        }
+        'envio;help;store' {
+            [CompletionResult]::new('migrate', 'migrate', [CompletionResultType]::ParameterValue, 'Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)')
+            break
+        }
+        'envio;help;store;migrate' {
+            break
+        }
+        'envio;help;config' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List all settings and their values')
+            [CompletionResult]::new('get', 'get', [CompletionResultType]::ParameterValue, 'Print the value of a setting')
+            [CompletionResult]::new('set', 'set', [CompletionResultType]::ParameterValue, 'Change the value of a setting')
+            [CompletionResult]::new('unset', 'unset', [CompletionResultType]::ParameterValue, 'Reset a setting to its default value')
+            break
+        }
+        'envio;help;config;list' {
+            break
+        }
+        'envio;help;config;get' {
+            break
+        }
+        'envio;help;config;set' {
+            break
+        }
+        'envio;help;config;unset' {
+            break
+        }
         'envio;help;version' {
             break
         }
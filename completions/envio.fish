@@ -1,28 +1,61 @@
 complete -c envio -n "__fish_use_subcommand" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_use_subcommand" -f -a "create" -d 'Create a new profile'
 complete -c envio -n "__fish_use_subcommand" -f -a "add" -d 'Add envionment variables to a profile'
-complete -c envio -n "__fish_use_subcommand" -f -a "load" -d 'Load all environment variables in a profile for use in your terminal sessions'
+complete -c envio -n "__fish_use_subcommand" -f -a "load" -d 'Load all environment variables in a profile into the current shell, requires the shell hook on unix (see `envio hook`)'
 complete -c envio -n "__fish_use_subcommand" -f -a "unload" -d 'Unload a profile'
+complete -c envio -n "__fish_use_subcommand" -f -a "env" -d 'Print the shell code that loads the environment variables of a profile into the current shell, e.g. eval "$(envio env <PROFILE_NAME>)"'
+complete -c envio -n "__fish_use_subcommand" -f -a "hook" -d 'Print the shell functions that let `envio load` and `envio unload` change the current shell'
+complete -c envio -n "__fish_use_subcommand" -f -a "allow" -d 'Allow the .envio file of the current directory to load its profile until the file changes'
+complete -c envio -n "__fish_use_subcommand" -f -a "deny" -d 'Stop the .envio file of the current directory from loading its profile'
+complete -c envio -n "__fish_use_subcommand" -f -a "hook-env"
 complete -c envio -n "__fish_use_subcommand" -f -a "launch" -d 'Run a command with the environment variables from a profile'
 complete -c envio -n "__fish_use_subcommand" -f -a "remove" -d 'Remove a environment variable from a profile'
 complete -c envio -n "__fish_use_subcommand" -f -a "list" -d 'List all the environment variables in a profile or all the profiles currenty stored'
 complete -c envio -n "__fish_use_subcommand" -f -a "update" -d 'Update environment variables in a profile'
+complete -c envio -n "__fish_use_subcommand" -f -a "edit" -d 'Edit the environment variables of a profile in your editor'
+complete -c envio -n "__fish_use_subcommand" -f -a "generate" -d 'Generate a random secret and store it in a profile without printing it'
+complete -c envio -n "__fish_use_subcommand" -f -a "rotate" -d 'Replace the value of an environment variable with the output of a rotation command, which gets the current value on stdin'
 complete -c envio -n "__fish_use_subcommand" -f -a "export" -d 'Export a profile to a file if no file is specified it will be exported to a file named .env'
 complete -c envio -n "__fish_use_subcommand" -f -a "import" -d 'Download a profile over the internet and import it into the system or import a locally stored profile into your current envio installation'
+complete -c envio -n "__fish_use_subcommand" -f -a "schema" -d 'Show, set or remove the validation schema of a profile'
+complete -c envio -n "__fish_use_subcommand" -f -a "validate" -d 'Validate the environment variables of a profile against its schema'
+complete -c envio -n "__fish_use_subcommand" -f -a "policy" -d 'Show or set what happens to the expired environment variables of a profile (warn, block, strip or default)'
+complete -c envio -n "__fish_use_subcommand" -f -a "describe" -d 'Show or set the description, owner and notes of a profile'
+complete -c envio -n "__fish_use_subcommand" -f -a "expiring" -d 'List the environment variables of all profiles that have expired or expire soon'
+complete -c envio -n "__fish_use_subcommand" -f -a "store" -d 'Show or change where the profiles are stored'
+complete -c envio -n "__fish_use_subcommand" -f -a "config" -d 'Show or change the settings in the config file'
 complete -c envio -n "__fish_use_subcommand" -f -a "version" -d 'Print the version'
 complete -c envio -n "__fish_use_subcommand" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c envio -n "__fish_seen_subcommand_from create" -s f -l file-to-import-envs-from -r
 complete -c envio -n "__fish_seen_subcommand_from create" -s e -l envs -r
 complete -c envio -n "__fish_seen_subcommand_from create" -s g -l gpg-key-fingerprint -r
+complete -c envio -n "__fish_seen_subcommand_from create" -s E -l expires -r
+complete -c envio -n "__fish_seen_subcommand_from create" -s s -l schema -r
+complete -c envio -n "__fish_seen_subcommand_from create" -s F -l from-file -r
+complete -c envio -n "__fish_seen_subcommand_from create" -s d -l description -r
+complete -c envio -n "__fish_seen_subcommand_from create" -s o -l owner -r
+complete -c envio -n "__fish_seen_subcommand_from create" -s n -l notes -r
 complete -c envio -n "__fish_seen_subcommand_from create" -s c -l add-comments
 complete -c envio -n "__fish_seen_subcommand_from create" -s x -l add-expiration-date
 complete -c envio -n "__fish_seen_subcommand_from create" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from add" -s e -l envs -r
+complete -c envio -n "__fish_seen_subcommand_from add" -s E -l expires -r
+complete -c envio -n "__fish_seen_subcommand_from add" -s s -l sensitive -r -f -a "{true	'',false	''}"
+complete -c envio -n "__fish_seen_subcommand_from add" -s F -l from-file -r
+complete -c envio -n "__fish_seen_subcommand_from add" -s t -l tags -r
 complete -c envio -n "__fish_seen_subcommand_from add" -s c -l add-comments
 complete -c envio -n "__fish_seen_subcommand_from add" -s x -l add-expiration-date
 complete -c envio -n "__fish_seen_subcommand_from add" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from load" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from unload" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from env" -s s -l shell -d 'bash, zsh, fish, nu, pwsh, elvish, xonsh or tcsh [default: the current shell]' -r
+complete -c envio -n "__fish_seen_subcommand_from env" -s u -l unset -d 'Print the code that removes the environment variables loaded before'
+complete -c envio -n "__fish_seen_subcommand_from env" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from hook" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c envio -n "__fish_seen_subcommand_from allow" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from deny" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from hook-env" -s s -l shell -r
+complete -c envio -n "__fish_seen_subcommand_from hook-env" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from launch" -s c -l command -r
 complete -c envio -n "__fish_seen_subcommand_from launch" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from remove" -s e -l envs-to-remove -r
@@ -32,29 +65,100 @@ complete -c envio -n "__fish_seen_subcommand_from list" -s p -l profiles
 complete -c envio -n "__fish_seen_subcommand_from list" -s v -l no-pretty-print
 complete -c envio -n "__fish_seen_subcommand_from list" -s c -l display-comments
 complete -c envio -n "__fish_seen_subcommand_from list" -s x -l display-expiration-date
+complete -c envio -n "__fish_seen_subcommand_from list" -s r -l reveal
 complete -c envio -n "__fish_seen_subcommand_from list" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from update" -s e -l envs -r
+complete -c envio -n "__fish_seen_subcommand_from update" -s E -l expires -r
+complete -c envio -n "__fish_seen_subcommand_from update" -s s -l sensitive -r -f -a "{true	'',false	''}"
+complete -c envio -n "__fish_seen_subcommand_from update" -s t -l tags -r
 complete -c envio -n "__fish_seen_subcommand_from update" -s v -l update-values
 complete -c envio -n "__fish_seen_subcommand_from update" -s c -l update-comments
 complete -c envio -n "__fish_seen_subcommand_from update" -s x -l update-expiration-date
 complete -c envio -n "__fish_seen_subcommand_from update" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from edit" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from generate" -s T -l type -d 'chars, hex, base64, uuid or words [default: chars]' -r
+complete -c envio -n "__fish_seen_subcommand_from generate" -s l -l length -d 'Number of characters, random bytes (hex and base64) or words [default: 32, words: 8, each word adds about 10.5 bits of entropy]' -r
+complete -c envio -n "__fish_seen_subcommand_from generate" -s c -l charset -d 'alphanumeric, letters, digits or symbols [default: alphanumeric]' -r
+complete -c envio -n "__fish_seen_subcommand_from generate" -l separator -d 'Separator between words [default: -]' -r
+complete -c envio -n "__fish_seen_subcommand_from generate" -s E -l expires -r
+complete -c envio -n "__fish_seen_subcommand_from generate" -s p -l print
+complete -c envio -n "__fish_seen_subcommand_from generate" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from rotate" -s w -l with -r
+complete -c envio -n "__fish_seen_subcommand_from rotate" -s P -l period -d 'Push the expiration date to this far from today, e.g. 90d or 6m [default: the rotation.period setting]' -r
+complete -c envio -n "__fish_seen_subcommand_from rotate" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from export" -s f -l file-to-export-to -r
 complete -c envio -n "__fish_seen_subcommand_from export" -s e -l envs -r
+complete -c envio -n "__fish_seen_subcommand_from export" -l format -r
+complete -c envio -n "__fish_seen_subcommand_from export" -s s -l include-sensitive
 complete -c envio -n "__fish_seen_subcommand_from export" -s h -l help -d 'Print help'
 complete -c envio -n "__fish_seen_subcommand_from import" -s f -l file-to-import-from -r
 complete -c envio -n "__fish_seen_subcommand_from import" -s u -l url -r
 complete -c envio -n "__fish_seen_subcommand_from import" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from schema" -s f -l file -r
+complete -c envio -n "__fish_seen_subcommand_from schema" -s r -l remove
+complete -c envio -n "__fish_seen_subcommand_from schema" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from validate" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from policy" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from describe" -s d -l description -r
+complete -c envio -n "__fish_seen_subcommand_from describe" -s o -l owner -r
+complete -c envio -n "__fish_seen_subcommand_from describe" -s n -l notes -r
+complete -c envio -n "__fish_seen_subcommand_from describe" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from expiring" -s w -l within -r
+complete -c envio -n "__fish_seen_subcommand_from expiring" -s e -l exit-code -d 'Exit with status 1 if environment variables expire within the period, or with status 2 if a profile could not be checked'
+complete -c envio -n "__fish_seen_subcommand_from expiring" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from store; and not __fish_seen_subcommand_from migrate; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from store; and not __fish_seen_subcommand_from migrate; and not __fish_seen_subcommand_from help" -f -a "migrate" -d 'Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)'
+complete -c envio -n "__fish_seen_subcommand_from store; and not __fish_seen_subcommand_from migrate; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c envio -n "__fish_seen_subcommand_from store; and __fish_seen_subcommand_from migrate" -l to -r
+complete -c envio -n "__fish_seen_subcommand_from store; and __fish_seen_subcommand_from migrate" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from store; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from migrate; and not __fish_seen_subcommand_from help" -f -a "migrate" -d 'Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)'
+complete -c envio -n "__fish_seen_subcommand_from store; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from migrate; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c envio -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "list" -d 'List all settings and their values'
+complete -c envio -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "get" -d 'Print the value of a setting'
+complete -c envio -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "set" -d 'Change the value of a setting'
+complete -c envio -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "unset" -d 'Reset a setting to its default value'
+complete -c envio -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from get" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from unset" -s h -l help -d 'Print help'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "list" -d 'List all settings and their values'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "get" -d 'Print the value of a setting'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "set" -d 'Change the value of a setting'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "unset" -d 'Reset a setting to its default value'
+complete -c envio -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c envio -n "__fish_seen_subcommand_from version" -s v -l verbose
 complete -c envio -n "__fish_seen_subcommand_from version" -s h -l help -d 'Print help'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "create" -d 'Create a new profile'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "add" -d 'Add envionment variables to a profile'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "load" -d 'Load all environment variables in a profile for use in your terminal sessions'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "unload" -d 'Unload a profile'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "launch" -d 'Run a command with the environment variables from a profile'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "remove" -d 'Remove a environment variable from a profile'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "list" -d 'List all the environment variables in a profile or all the profiles currenty stored'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "update" -d 'Update environment variables in a profile'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "export" -d 'Export a profile to a file if no file is specified it will be exported to a file named .env'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "import" -d 'Download a profile over the internet and import it into the system or import a locally stored profile into your current envio installation'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "version" -d 'Print the version'
-complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "create" -d 'Create a new profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "add" -d 'Add envionment variables to a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "load" -d 'Load all environment variables in a profile into the current shell, requires the shell hook on unix (see `envio hook`)'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "unload" -d 'Unload a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "env" -d 'Print the shell code that loads the environment variables of a profile into the current shell, e.g. eval "$(envio env <PROFILE_NAME>)"'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "hook" -d 'Print the shell functions that let `envio load` and `envio unload` change the current shell'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "allow" -d 'Allow the .envio file of the current directory to load its profile until the file changes'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "deny" -d 'Stop the .envio file of the current directory from loading its profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "hook-env"
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "launch" -d 'Run a command with the environment variables from a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "remove" -d 'Remove a environment variable from a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "list" -d 'List all the environment variables in a profile or all the profiles currenty stored'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "update" -d 'Update environment variables in a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "edit" -d 'Edit the environment variables of a profile in your editor'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "generate" -d 'Generate a random secret and store it in a profile without printing it'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "rotate" -d 'Replace the value of an environment variable with the output of a rotation command, which gets the current value on stdin'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "export" -d 'Export a profile to a file if no file is specified it will be exported to a file named .env'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "import" -d 'Download a profile over the internet and import it into the system or import a locally stored profile into your current envio installation'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "schema" -d 'Show, set or remove the validation schema of a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "validate" -d 'Validate the environment variables of a profile against its schema'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "policy" -d 'Show or set what happens to the expired environment variables of a profile (warn, block, strip or default)'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "describe" -d 'Show or set the description, owner and notes of a profile'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "expiring" -d 'List the environment variables of all profiles that have expired or expire soon'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "store" -d 'Show or change where the profiles are stored'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "config" -d 'Show or change the settings in the config file'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "version" -d 'Print the version'
+complete -c envio -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from create; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from load; and not __fish_seen_subcommand_from unload; and not __fish_seen_subcommand_from env; and not __fish_seen_subcommand_from hook; and not __fish_seen_subcommand_from allow; and not __fish_seen_subcommand_from deny; and not __fish_seen_subcommand_from hook-env; and not __fish_seen_subcommand_from launch; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from update; and not __fish_seen_subcommand_from edit; and not __fish_seen_subcommand_from generate; and not __fish_seen_subcommand_from rotate; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from import; and not __fish_seen_subcommand_from schema; and not __fish_seen_subcommand_from validate; and not __fish_seen_subcommand_from policy; and not __fish_seen_subcommand_from describe; and not __fish_seen_subcommand_from expiring; and not __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from version; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c envio -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from store; and not __fish_seen_subcommand_from migrate" -f -a "migrate" -d 'Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)'
+complete -c envio -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset" -f -a "list" -d 'List all settings and their values'
+complete -c envio -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset" -f -a "get" -d 'Print the value of a setting'
+complete -c envio -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset" -f -a "set" -d 'Change the value of a setting'
+complete -c envio -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from set; and not __fish_seen_subcommand_from unset" -f -a "unset" -d 'Reset a setting to its default value'
@@ -41,6 +41,18 @@ pub enum Command {
         add_comments: bool,
         #[arg(required = false, long = "add-expiration-date", short = 'x')]
         add_expiration_date: bool,
+        #[arg(required = false, long = "expires", short = 'E')]
+        expires: Option<String>,
+        #[arg(required = false, long = "schema", short = 's')]
+        schema: Option<String>,
+        #[arg(required = false, long = "from-file", short = 'F', value_delimiter = ' ', num_args = 1..)]
+        from_file: Option<Vec<String>>,
+        #[arg(required = false, long = "description", short = 'd')]
+        description: Option<String>,
+        #[arg(required = false, long = "owner", short = 'o')]
+        owner: Option<String>,
+        #[arg(required = false, long = "notes", short = 'n')]
+        notes: Option<String>,
     },
     #[command(
         name = "add",
@@ -51,7 +63,7 @@ pub enum Command {
         #[arg(required = true)]
         profile_name: String,
         #[arg(
-            required = true,
+            required_unless_present = "from_file",
             long = "envs",
             short = 'e',
             value_delimiter = ' ',
@@ -62,10 +74,18 @@ pub enum Command {
         add_comments: bool,
         #[arg(required = false, long = "add-expiration-date", short = 'x')]
         add_expiration_date: bool,
+        #[arg(required = false, long = "expires", short = 'E')]
+        expires: Option<String>,
+        #[arg(required = false, long = "sensitive", short = 's')]
+        sensitive: Option<bool>,
+        #[arg(required = false, long = "from-file", short = 'F', value_delimiter = ' ', num_args = 1..)]
+        from_file: Option<Vec<String>>,
+        #[arg(required = false, long = "tags", short = 't', value_delimiter = ',')]
+        tags: Option<Vec<String>>,
     },
     #[command(
         name = "load",
-        about = "Load all environment variables in a profile for use in your terminal sessions"
+        about = "Load all environment variables in a profile into the current shell, requires the shell hook on unix (see `envio hook`)"
     )]
     Load {
         #[arg(required = true)]
@@ -80,6 +100,74 @@ pub enum Command {
         #[arg(required = true)]
         profile_name: String,
     },
+    #[command(
+        name = "env",
+        about = "Print the shell code that loads the environment variables of a profile into the current shell, e.g. eval \"$(envio env <PROFILE_NAME>)\"",
+        override_usage = "envio env <PROFILE_NAME> [OPTIONS]\n       envio env --unset [OPTIONS]"
+    )]
+    Env {
+        #[arg(required_unless_present = "unset")]
+        profile_name: Option<String>,
+        #[arg(
+            required = false,
+            long = "shell",
+            short = 's',
+            help = "bash, zsh, fish, nu, pwsh, elvish, xonsh or tcsh [default: the current shell]"
+        )]
+        shell: Option<String>,
+        #[arg(
+            required = false,
+            long = "unset",
+            short = 'u',
+            help = "Print the code that removes the environment variables loaded before"
+        )]
+        unset: bool,
+    },
+    #[command(
+        name = "hook",
+        about = "Print the shell functions that let `envio load` and `envio unload` change the current shell",
+        long_about = "Print the shell functions that let `envio load` and `envio unload` change the current shell\n\n\
+                      To set up the hook add the following line to your shell config:\n\n  \
+                      bash (~/.bashrc):                       eval \"$(envio hook bash)\"\n  \
+                      zsh (~/.zshrc):                         eval \"$(envio hook zsh)\"\n  \
+                      fish (~/.config/fish/config.fish):      envio hook fish | source\n  \
+                      pwsh ($PROFILE):                        Invoke-Expression (& envio hook pwsh | Out-String)\n  \
+                      elvish (~/.config/elvish/rc.elv):       eval (envio hook elvish | slurp)\n  \
+                      xonsh (~/.xonshrc):                     execx($(envio hook xonsh))\n\n\
+                      nu and tcsh can not evaluate code from their config, so the hook is saved to a file which is sourced:\n\n  \
+                      nu (~/.config/nushell/config.nu):       source ~/.config/nushell/envio.nu\n  \
+                      \x20                                       after running: envio hook nu | save --force ~/.config/nushell/envio.nu\n  \
+                      tcsh (~/.tcshrc):                       source ~/.envio.tcsh\n  \
+                      \x20                                       after running: envio hook tcsh > ~/.envio.tcsh",
+        override_usage = "envio hook <SHELL>"
+    )]
+    Hook {
+        #[arg(required = true)]
+        shell: String,
+    },
+    #[command(
+        name = "allow",
+        about = "Allow the .envio file of the current directory to load its profile until the file changes",
+        override_usage = "envio allow [PATH]"
+    )]
+    Allow {
+        #[arg(required = false)]
+        path: Option<String>,
+    },
+    #[command(
+        name = "deny",
+        about = "Stop the .envio file of the current directory from loading its profile",
+        override_usage = "envio deny [PATH]"
+    )]
+    Deny {
+        #[arg(required = false)]
+        path: Option<String>,
+    },
+    #[command(name = "hook-env", hide = true)]
+    HookEnv {
+        #[arg(required = false, long = "shell", short = 's')]
+        shell: Option<String>,
+    },
     #[command(
         name = "launch",
         about = "Run a command with the environment variables from a profile",
@@ -117,6 +205,8 @@ pub enum Command {
         display_comments: bool,
         #[arg(required = false, long = "display-expiration-date", short = 'x')]
         display_expiration_date: bool,
+        #[arg(required = false, long = "reveal", short = 'r')]
+        reveal: bool,
     },
     #[command(
         name = "update",
@@ -140,6 +230,83 @@ pub enum Command {
         update_comments: bool,
         #[arg(required = false, long = "update-expiration-date", short = 'x')]
         update_expiration_date: bool,
+        #[arg(required = false, long = "expires", short = 'E')]
+        expires: Option<String>,
+        #[arg(required = false, long = "sensitive", short = 's')]
+        sensitive: Option<bool>,
+        #[arg(required = false, long = "tags", short = 't', value_delimiter = ',')]
+        tags: Option<Vec<String>>,
+    },
+    #[command(
+        name = "edit",
+        about = "Edit the environment variables of a profile in your editor",
+        override_usage = "envio edit <PROFILE_NAME>"
+    )]
+    Edit {
+        #[arg(required = true)]
+        profile_name: String,
+    },
+    #[command(
+        name = "generate",
+        about = "Generate a random secret and store it in a profile without printing it",
+        override_usage = "envio generate <PROFILE_NAME> <KEY> [OPTIONS]"
+    )]
+    Generate {
+        #[arg(required = true)]
+        profile_name: String,
+        #[arg(required = true)]
+        key: String,
+        #[arg(
+            required = false,
+            long = "type",
+            short = 'T',
+            help = "chars, hex, base64, uuid or words [default: chars]"
+        )]
+        secret_type: Option<String>,
+        #[arg(
+            required = false,
+            long = "length",
+            short = 'l',
+            help = "Number of characters, random bytes (hex and base64) or words [default: 32, words: 8, each word adds about 10.5 bits of entropy]"
+        )]
+        length: Option<usize>,
+        #[arg(
+            required = false,
+            long = "charset",
+            short = 'c',
+            help = "alphanumeric, letters, digits or symbols [default: alphanumeric]"
+        )]
+        charset: Option<String>,
+        #[arg(
+            required = false,
+            long = "separator",
+            help = "Separator between words [default: -]"
+        )]
+        separator: Option<String>,
+        #[arg(required = false, long = "print", short = 'p')]
+        print: bool,
+        #[arg(required = false, long = "expires", short = 'E')]
+        expires: Option<String>,
+    },
+    #[command(
+        name = "rotate",
+        about = "Replace the value of an environment variable with the output of a rotation command, which gets the current value on stdin",
+        override_usage = "envio rotate <PROFILE_NAME> <KEY> --with <COMMAND> [OPTIONS]"
+    )]
+    Rotate {
+        #[arg(required = true)]
+        profile_name: String,
+        #[arg(required = true)]
+        key: String,
+        #[arg(required = true, long = "with", short = 'w')]
+        command: String,
+        #[arg(
+            required = false,
+            long = "period",
+            short = 'P',
+            help = "Push the expiration date to this far from today, e.g. 90d or 6m [default: the rotation.period setting]"
+        )]
+        period: Option<String>,
     },
     #[command(
         name = "export",
@@ -159,6 +326,10 @@ pub enum Command {
             num_args = 1..,
         )]
         envs: Option<Vec<String>>,
+        #[arg(required = false, long = "include-sensitive", short = 's')]
+        include_sensitive: bool,
+        #[arg(required = false, long = "format")]
+        format: Option<String>,
     },
     #[command(
         name = "import",
@@ -173,6 +344,86 @@ pub enum Command {
         #[arg(required = false, long = "url", short = 'u')]
         url: Option<String>,
     },
+    #[command(
+        name = "schema",
+        about = "Show, set or remove the validation schema of a profile",
+        override_usage = "envio schema <PROFILE_NAME> [OPTIONS]"
+    )]
+    Schema {
+        #[arg(required = true)]
+        profile_name: String,
+        #[arg(required = false, long = "file", short = 'f')]
+        file: Option<String>,
+        #[arg(required = false, long = "remove", short = 'r')]
+        remove: bool,
+    },
+    #[command(
+        name = "validate",
+        about = "Validate the environment variables of a profile against its schema"
+    )]
+    Validate {
+        #[arg(required = true)]
+        profile_name: String,
+    },
+    #[command(
+        name = "policy",
+        about = "Show or set what happens to the expired environment variables of a profile (warn, block, strip or default)",
+        override_usage = "envio policy <PROFILE_NAME> [POLICY]"
+    )]
+    Policy {
+        #[arg(required = true)]
+        profile_name: String,
+        #[arg(required = false)]
+        policy: Option<String>,
+    },
+    #[command(
+        name = "describe",
+        about = "Show or set the description, owner and notes of a profile",
+        override_usage = "envio describe <PROFILE_NAME> [OPTIONS]"
+    )]
+    Describe {
+        #[arg(required = true)]
+        profile_name: String,
+        #[arg(required = false, long = "description", short = 'd')]
+        description: Option<String>,
+        #[arg(required = false, long = "owner", short = 'o')]
+        owner: Option<String>,
+        #[arg(required = false, long = "notes", short = 'n')]
+        notes: Option<String>,
+    },
+    #[command(
+        name = "expiring",
+        about = "List the environment variables of all profiles that have expired or expire soon"
+    )]
+    Expiring {
+        #[arg(required = false, long = "within", short = 'w', default_value = "14d")]
+        within: String,
+        #[arg(
+            required = false,
+            long = "exit-code",
+            short = 'e',
+            help = "Exit with status 1 if environment variables expire within the period, or with status 2 if a profile could not be checked"
+        )]
+        exit_code: bool,
+    },
+    #[command(
+        name = "store",
+        about = "Show or change where the profiles are stored",
+        override_usage = "envio store [migrate --to <fs|sqlite>]"
+    )]
+    Store {
+        #[command(subcommand)]
+        command: Option<StoreCommand>,
+    },
+    #[command(
+        name = "config",
+        about = "Show or change the settings in the config file",
+        override_usage = "envio config <list | get <KEY> | set <KEY> <VALUE> | unset <KEY>>"
+    )]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
     #[command(name = "version", about = "Print the version")]
     Version {
         #[arg(required = false, long = "verbose", short = 'v')]
@@ -180,6 +431,41 @@ pub enum Command {
     },
 }
 
+#[derive(clap::Subcommand, Debug)]
+pub enum StoreCommand {
+    #[command(
+        name = "migrate",
+        about = "Move all profiles to another store, either one file per profile (fs) or a single SQLite database (sqlite)"
+    )]
+    Migrate {
+        #[arg(required = true, long = "to")]
+        to: String,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum ConfigCommand {
+    #[command(name = "list", about = "List all settings and their values")]
+    List,
+    #[command(name = "get", about = "Print the value of a setting")]
+    Get {
+        #[arg(required = true)]
+        key: String,
+    },
+    #[command(name = "set", about = "Change the value of a setting")]
+    Set {
+        #[arg(required = true)]
+        key: String,
+        #[arg(required = true)]
+        value: String,
+    },
+    #[command(name = "unset", about = "Reset a setting to its default value")]
+    Unset {
+        #[arg(required = true)]
+        key: String,
+    },
+}
+
 #[derive(clap::Args, Debug)]
 #[group(required = true, multiple = false)]
 pub struct LaunchCommandArg {
@@ -1,42 +1,58 @@
 /// Utility/helper functions specific to the CLI version of envio.
 /// These functions are designed for CLI usage and may not be something used by users interacting with the API directly
 use std::{
-    io::{Read, Write},
+    collections::BTreeMap,
+    io::{Seek, Write},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 #[cfg(target_family = "windows")]
 use std::process::Command;
 
-use chrono::{Local, NaiveDate};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use colored::Colorize;
 use comfy_table::{Attribute, Cell, Table};
 use envio::{
     crypto::EncryptionType,
     error::{Error, Result},
-    EnvVec, Profile,
+    escape,
+    schema::Schema,
+    store::{ProfileStore, StoreKind, StoredProfile},
+    Env, EnvVec, ExpirationPolicy, Profile, ProfileMetadata,
 };
 
-use crate::utils::{contains_path_separator, download_file, get_configdir, get_cwd};
+use crate::config::{Config, ExportFormat};
+use crate::utils::{contains_path_separator, download_file, get_cwd};
 
-#[cfg(target_family = "unix")]
-use crate::utils::get_shell_config;
+use crate::project::Project;
+use crate::shell::{
+    is_valid_name, EnvChange, Shell, LOADED_KEYS_ENV, LOADED_PROFILE_ENV, PROJECT_HASH_ENV,
+    PROJECT_SAVED_ENV, PROJECT_STATE_ENV,
+};
 
 /// Create a new profile which is stored in the profiles directory
 ///
 /// # Parameters
+/// - `store` - the store to create the profile in
 /// - `name` - the name of the profile
 /// - `envs` - the environment variables of the profile
+/// - `schema` - the schema to validate the environment variables against
+/// - `metadata` - the description, owner and notes of the profile
 /// - `encryption_type` - the encryption type of the profile
 ///
 /// # Returns
 /// - `Result<()>`: whether the operation was successful
 pub fn create_profile(
+    store: &Arc<dyn ProfileStore>,
     name: String,
     envs: Option<EnvVec>,
+    schema: Option<Schema>,
+    metadata: ProfileMetadata,
     encryption_type: Box<dyn EncryptionType>,
 ) -> Result<()> {
-    if Profile::does_exist(&name) {
+    if store.exists(&name) {
         return Err(Error::ProfileAlreadyExists(name));
     }
 
@@ -45,30 +61,140 @@ pub fn create_profile(
         None => EnvVec::new(),
     };
 
-    let config_dir = get_configdir()?;
-    let profile_dir = config_dir.join("profiles");
+    let profile_file_path = store.path(&name).unwrap_or_default();
 
-    if !profile_dir.exists() {
-        println!(
-            "{}",
-            "Profiles directory does not exist creating it now..".bold()
-        );
-        std::fs::create_dir_all(&profile_dir).unwrap();
-    }
+    let mut profile = Profile::new(name, envs, profile_file_path, encryption_type);
+    profile.metadata = metadata;
+    profile.set_store(store.clone());
 
-    let profile_file_path = profile_dir.join(name.clone() + ".env");
+    if let Some(schema) = schema {
+        for key in schema.apply_defaults(&mut profile.envs) {
+            println!("Using the default value of '{}' from the schema", key);
+        }
+
+        profile.schema = Some(schema);
+        check_schema(&profile)?;
+    }
 
-    Profile::new(name, envs, profile_file_path, encryption_type).push_changes()?;
+    profile.push_changes()?;
 
     println!("{}: Profile created", "Success".green());
     Ok(())
 }
 
-pub fn check_expired_envs(profile: &Profile) {
-    for env in &profile.envs {
-        if let Some(date) = env.expiration_date {
-            if date <= Local::now().date_naive() {
-                println!(
+/// Read and parse a schema file
+///
+/// # Parameters
+/// - `file_path` - the path to the schema file
+///
+/// # Returns
+/// - `Result<Schema>`: the parsed schema
+pub fn read_schema(file_path: &str) -> Result<Schema> {
+    if !Path::new(file_path).exists() {
+        return Err(Error::Msg(format!("File `{}` does not exist", file_path)));
+    }
+
+    Schema::from_toml(&std::fs::read_to_string(file_path)?)
+}
+
+/// Validate a profile against its schema and print all the violations found
+///
+/// # Parameters
+/// - `profile` - the profile to validate ([Profile] object)
+///
+/// # Returns
+/// - `Result<()>`: an error if the profile does not match its schema
+pub fn check_schema(profile: &Profile) -> Result<()> {
+    let violations = profile.validate();
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    for violation in &violations {
+        println!("{}: {}", "Invalid".red(), violation);
+    }
+
+    Err(Error::SchemaViolations(violations))
+}
+
+/// How the environment variables of a profile are going to be used
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProfileUsage {
+    /// The profile is only looked at or modified
+    Inspect,
+    /// The values are handed over to something else, e.g. a launched command,
+    /// the shell or an exported file
+    Consume,
+}
+
+/// Get the expiration policy that applies to a profile
+///
+/// The policy of the profile takes precedence over the global policy, see
+/// [Config::expiration_policy]
+///
+/// # Parameters
+/// - `profile` - the profile ([Profile] object)
+/// - `config` - the global configuration
+///
+/// # Returns
+/// - `Result<ExpirationPolicy>`: the policy or an error if the global policy
+///   is invalid
+pub fn get_expiration_policy(profile: &Profile, config: &Config) -> Result<ExpirationPolicy> {
+    match profile.expiration_policy {
+        Some(policy) => Ok(policy),
+        None => config.expiration_policy(),
+    }
+}
+
+/// Apply the expiration policy of a profile
+///
+/// Expired environment variables are always reported. If the values are about
+/// to be consumed, the `block` policy refuses to continue and the `strip`
+/// policy removes the expired environment variables from the profile object.
+///
+/// # Parameters
+/// - `profile` - the profile ([Profile] object)
+/// - `usage` - how the profile is going to be used
+/// - `config` - the global configuration
+///
+/// # Returns
+/// - `Result<()>`: an error if the profile is blocked
+pub fn enforce_expiration_policy(
+    profile: &mut Profile,
+    usage: ProfileUsage,
+    config: &Config,
+) -> Result<()> {
+    if profile.expired_envs().is_empty() {
+        return Ok(());
+    }
+
+    let policy = get_expiration_policy(profile, config)?;
+
+    match (policy, usage) {
+        (ExpirationPolicy::Block, ProfileUsage::Consume) => {
+            for env in profile.expired_envs() {
+                eprintln!(
+                    "{}: Environment variable '{}' has expired",
+                    "Error".red(),
+                    env.name
+                );
+            }
+
+            return Err(Error::ExpiredEnvs(profile.name.to_string()));
+        }
+        (ExpirationPolicy::Strip, ProfileUsage::Consume) => {
+            for env in profile.strip_expired() {
+                eprintln!(
+                    "{}: Environment variable '{}' has expired and was left out",
+                    "Warning".yellow(),
+                    env.name
+                );
+            }
+        }
+        _ => {
+            for env in profile.expired_envs() {
+                eprintln!(
                     "{}: Environment variable '{}' has expired",
                     "Warning".yellow(),
                     env.name
@@ -76,21 +202,168 @@ pub fn check_expired_envs(profile: &Profile) {
             }
         }
     }
+
+    Ok(())
+}
+
+/// List the environment variables of the given profiles which expire within
+/// the given number of days, including the ones that have already expired
+///
+/// # Parameters
+/// - `expirations` - the names of the profiles along with the expiration
+///   dates of their environment variables, see
+///   [ProfileMetadata::expiration_dates](envio::ProfileMetadata::expiration_dates)
+/// - `within_days` - the number of days to look ahead
+///
+/// # Returns
+/// - `usize`: the number of environment variables listed
+pub fn list_expiring_envs(
+    expirations: &[(String, BTreeMap<String, NaiveDate>)],
+    within_days: i64,
+) -> usize {
+    let mut table = Table::new();
+
+    table.set_header(vec![
+        Cell::new("Profile").add_attribute(Attribute::Bold),
+        Cell::new("Environment Variable").add_attribute(Attribute::Bold),
+        Cell::new("Expiration Date").add_attribute(Attribute::Bold),
+        Cell::new("Status").add_attribute(Attribute::Bold),
+    ]);
+
+    let today = Local::now().date_naive();
+    let mut count = 0;
+
+    for (profile_name, dates) in expirations {
+        for (name, date) in dates {
+            let days = (*date - today).num_days();
+
+            if days > within_days {
+                continue;
+            }
+
+            let status = match days {
+                d if d < 0 => format!("expired {} day(s) ago", -d),
+                0 => "expires today".to_string(),
+                d => format!("expires in {} day(s)", d),
+            };
+
+            table.add_row(vec![
+                profile_name.clone(),
+                name.clone(),
+                date.to_string(),
+                status,
+            ]);
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        println!(
+            "{}",
+            format!(
+                "No environment variables expire within {} day(s)",
+                within_days
+            )
+            .bold()
+        );
+    } else {
+        println!("{table}");
+    }
+
+    count
+}
+
+/// Placeholder displayed instead of the value of a sensitive environment
+/// variable
+const MASKED_VALUE: &str = "********";
+
+/// Number of characters of a value that are displayed before it is truncated
+const MAX_DISPLAYED_VALUE_LEN: usize = 60;
+
+/// Get the value of an environment variable as it should be displayed
+///
+/// # Parameters
+/// - `env` - the environment variable
+/// - `reveal` - whether to show the value of sensitive environment variables
+///
+/// # Returns
+/// - `&str`: the value or a mask if the environment variable is sensitive
+pub fn display_value(env: &Env, reveal: bool) -> &str {
+    if env.sensitive && !reveal {
+        MASKED_VALUE
+    } else {
+        &env.value
+    }
+}
+
+/// Get a short summary of the value of an environment variable for display in
+/// a table
+///
+/// Sensitive values are masked, binary values are replaced by their size and
+/// long or multi-line values are truncated to their first line
+///
+/// # Parameters
+/// - `env` - the environment variable
+/// - `reveal` - whether to show the value of sensitive environment variables
+///
+/// # Returns
+/// - `String`: the summary of the value
+pub fn summarize_value(env: &Env, reveal: bool) -> String {
+    let value = display_value(env, reveal);
+
+    if value == MASKED_VALUE {
+        return value.to_string();
+    }
+
+    if env.is_binary() {
+        let size = env
+            .decoded_value()
+            .map(|v| v.len())
+            .unwrap_or(env.value.len());
+
+        return format!("<binary, {} bytes>", size);
+    }
+
+    let first_line = value.lines().next().unwrap_or_default();
+    let mut summary: String = first_line.chars().take(MAX_DISPLAYED_VALUE_LEN).collect();
+
+    if first_line.chars().count() > MAX_DISPLAYED_VALUE_LEN {
+        summary.push_str("...");
+    }
+
+    if env.is_multiline() {
+        summary.push_str(&format!(
+            " ({} lines, {} bytes)",
+            value.lines().count(),
+            value.len()
+        ));
+    }
+
+    summary
 }
 
 /// Export all the environment variables of the profile to a file in plain text
 ///
+/// Sensitive environment variables are only exported if they are part of
+/// `envs_selected` or if `include_sensitive` is set
+///
 /// # Parameters
 /// - `profile` - the profile to export ([Profile] object)
 /// - `file_name` - the name of the file to export to
 /// - `envs_selected` - the environment variables to export
+/// - `include_sensitive` - whether to export sensitive environment variables
+///   that were not explicitly selected
+/// - `format` - the format of the file
 ///
 /// # Returns
-/// - `Result<()>`: whether the operation was successful
+/// - `Result<()>`: whether the operation was successful, an error if a key is
+///   not a valid name for an environment variable
 pub fn export_envs(
     profile: &Profile,
     file_name: &str,
     envs_selected: &Option<Vec<String>>,
+    include_sensitive: bool,
+    format: ExportFormat,
 ) -> Result<()> {
     let path = if contains_path_separator(file_name) {
         PathBuf::from(file_name)
@@ -98,13 +371,6 @@ pub fn export_envs(
         get_cwd().join(file_name)
     };
 
-    let mut file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(path)
-        .unwrap();
-
     let mut buffer = String::from("");
 
     if profile.envs.is_empty() {
@@ -112,27 +378,77 @@ pub fn export_envs(
     }
 
     let mut keys: Vec<_> = profile.envs.keys();
+    let mut skipped_sensitive = 0;
 
-    if let Some(envs_selected) = envs_selected {
-        if !envs_selected.is_empty() {
+    match envs_selected {
+        Some(envs_selected) if !envs_selected.is_empty() => {
             keys = keys
                 .into_iter()
                 .filter(|item| envs_selected.contains(item))
                 .collect::<Vec<String>>();
         }
-
-        if keys.is_empty() {
-            return Err(Error::Msg("No envs to export".to_string()));
+        _ if !include_sensitive => {
+            let total = keys.len();
+            keys = profile
+                .envs
+                .iter()
+                .filter(|e| !e.sensitive)
+                .map(|e| e.name.clone())
+                .collect();
+            skipped_sensitive = total - keys.len();
         }
+        _ => {}
+    }
+
+    if keys.is_empty() {
+        return Err(Error::Msg("No envs to export".to_string()));
     }
 
     for key in keys {
-        buffer = buffer + key.as_str() + "=" + profile.envs.get(key.as_str()).unwrap() + "\n";
+        // The file is meant to be sourced or passed to other programs, a key
+        // that is not a valid name could inject code or corrupt the file
+        if !is_valid_name(&key) {
+            return Err(Error::Msg(format!(
+                "Failed to export '{}': it is not a valid name for an environment variable",
+                key
+            )));
+        }
+
+        let value = profile.envs.get(key.as_str()).unwrap(); // unwrap is safe here because the keys come from the profile
+
+        buffer = match format {
+            ExportFormat::Dotenv => buffer + key.as_str() + "=" + &escape::dotenv(value) + "\n",
+            ExportFormat::Shell => {
+                buffer + "export " + key.as_str() + "=" + &escape::sh(value) + "\n"
+            }
+            ExportFormat::Docker => {
+                let value = escape::docker_env_file(value)
+                    .map_err(|e| Error::Msg(format!("Failed to export '{}': {}", key, e)))?;
+
+                buffer + key.as_str() + "=" + &value + "\n"
+            }
+        };
     }
 
+    // Opened last so that a failed export does not truncate the file
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)?;
+
     write!(file, "{}", buffer)?;
 
     println!("{}", "Exported envs".bold());
+
+    if skipped_sensitive > 0 {
+        println!(
+            "{}: Skipped {} sensitive environment variable(s), pass `--include-sensitive` to export them",
+            "Note".yellow(),
+            skipped_sensitive
+        );
+    }
+
     Ok(())
 }
 
@@ -140,7 +456,10 @@ pub fn export_envs(
 ///
 /// # Parameters
 /// - `profile` - the profile to list the environment variables of ([Profile] object)
-pub fn list_envs(profile: &Profile, display_comments: bool, display_expired: bool) {
+/// - `display_comments` - whether to display the comments
+/// - `display_expired` - whether to display the expiration dates
+/// - `reveal` - whether to display the values of sensitive environment variables
+pub fn list_envs(profile: &Profile, display_comments: bool, display_expired: bool, reveal: bool) {
     let mut table = Table::new();
 
     let mut header = vec![
@@ -156,11 +475,18 @@ pub fn list_envs(profile: &Profile, display_comments: bool, display_expired: boo
         header.push(Cell::new("Expiration Date").add_attribute(Attribute::Bold));
     }
 
+    // Most profiles do not use tags, only show them when there are some
+    let display_tags = profile.envs.iter().any(|e| !e.tags.is_empty());
+
+    if display_tags {
+        header.push(Cell::new("Tags").add_attribute(Attribute::Bold));
+    }
+
     table.set_header(header);
 
     let mut row;
     for env in &profile.envs {
-        row = vec![env.name.clone(), env.value.clone()];
+        row = vec![env.name.clone(), summarize_value(env, reveal)];
 
         if display_comments {
             if let Some(comment) = &env.comment {
@@ -178,69 +504,315 @@ pub fn list_envs(profile: &Profile, display_comments: bool, display_expired: boo
             }
         }
 
+        if display_tags {
+            row.push(env.tags.join(", "));
+        }
+
         table.add_row(row);
     }
 
     println!("{table}");
 }
 
-/// Delete a profile from the profiles directory
+/// Clean up the tags passed on the command line
+///
+/// Surrounding whitespace and empty tags are removed, so that `--tags ""`
+/// clears the tags of an environment variable.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+
+    normalized
+}
+
+/// Write the environment variables of a profile to a buffer the user can edit
+///
+/// The buffer is in the dotenv format, the comment, expiration date, tags and
+/// sensitivity of every environment variable are written as annotations above
+/// it. It is read back by
+/// [parse_annotated_envs_from_string](crate::utils::parse_annotated_envs_from_string).
 ///
 /// # Parameters
-/// - `name` - the name of the profile to delete
+/// - `profile` - the profile to edit ([Profile] object)
 ///
 /// # Returns
-/// - `Result<()>`: whether the operation was successful
-pub fn delete_profile(name: &str) -> Result<()> {
-    if Profile::does_exist(name) {
-        let configdir = get_configdir()?;
-        let profile_path = configdir.join("profiles").join(format!("{}.env", name));
+/// - `String`: the buffer
+pub fn edit_buffer(profile: &Profile) -> String {
+    let mut buffer = format!(
+        "# Editing profile '{}'
+#
+# Lines starting with `#` are ignored. The annotations right above a variable
+# set its comment, expiration date, sensitivity and tags, e.g.
+#
+#   # @comment Used by the billing service
+#   # @expires 2030-01-31
+#   # @sensitive true
+#   # @tags backend,billing
+#   API_KEY=value
+#
+# Save and close the editor to apply the changes, leave the file as it is to
+# discard them.
+",
+        profile.name
+    );
+
+    for env in &profile.envs {
+        buffer.push('\n');
+
+        if let Some(comment) = env.comment.as_deref().filter(|c| !c.is_empty()) {
+            buffer += &format!("# @comment {}\n", comment.replace('\n', " "));
+        }
+
+        if let Some(date) = env.expiration_date {
+            buffer += &format!("# @expires {}\n", date.format("%Y-%m-%d"));
+        }
+
+        // Only written if it differs from what the name suggests, which is
+        // what variables without the annotation get
+        if env.sensitive != Env::is_sensitive_name(&env.name) {
+            buffer += &format!("# @sensitive {}\n", env.sensitive);
+        }
 
-        match std::fs::remove_file(profile_path) {
-            Ok(_) => println!("{}: Deleted profile", "Success".green()),
-            Err(e) => return Err(Error::Io(e)),
+        if !env.tags.is_empty() {
+            buffer += &format!("# @tags {}\n", env.tags.join(","));
         }
+
+        buffer += &format!("{}={}\n", env.name, escape::dotenv(&env.value));
+    }
+
+    buffer
+}
+
+/// Print which environment variables were added, removed or changed, without
+/// printing any values
+///
+/// # Parameters
+/// - `old` - the environment variables before the change
+/// - `new` - the environment variables after the change
+///
+/// # Returns
+/// - `usize`: the number of environment variables that were added, removed or
+///   changed
+pub fn print_envs_diff(old: &EnvVec, new: &EnvVec) -> usize {
+    let mut changes = 0;
+
+    for env in new {
+        let marker = match old.get_env(&env.name) {
+            None => "+".green(),
+            Some(old_env) if !same_env(old_env, env) => "~".yellow(),
+            Some(_) => continue,
+        };
+
+        println!("{} {}", marker, env.name);
+        changes += 1;
+    }
+
+    for env in old {
+        if new.get_env(&env.name).is_none() {
+            println!("{} {}", "-".red(), env.name);
+            changes += 1;
+        }
+    }
+
+    changes
+}
+
+fn same_env(a: &Env, b: &Env) -> bool {
+    let comment = |env: &Env| env.comment.clone().filter(|c| !c.is_empty());
+
+    a.value == b.value
+        && comment(a) == comment(b)
+        && a.expiration_date == b.expiration_date
+        && a.sensitive == b.sensitive
+        && a.tags == b.tags
+}
+
+/// Let the user edit `content` in their editor
+///
+/// The content is written to a temporary file only the user can access, on a
+/// tmpfs if one is available so that it never reaches the disk. The file is
+/// overwritten with zeros before it is removed, also if the editor fails.
+///
+/// # Parameters
+/// - `editor` - the editor command, which may contain arguments
+/// - `content` - the content to edit
+///
+/// # Returns
+/// - `Result<String>`: the edited content
+pub fn edit_in_editor(editor: &str, content: &str) -> Result<String> {
+    let shm = Path::new("/dev/shm");
+    let dir = if shm.is_dir() {
+        shm.to_path_buf()
     } else {
-        return Err(Error::ProfileDoesNotExist(name.to_string()));
+        std::env::temp_dir()
+    };
+
+    // Created with permissions that only allow the user to access it
+    let mut file = WipedTempFile(
+        tempfile::Builder::new()
+            .prefix("envio-")
+            .suffix(".env")
+            .tempfile_in(dir)?,
+    );
+
+    file.0.write_all(content.as_bytes())?;
+    file.0.as_file().sync_all()?;
+
+    let mut args = editor.split_whitespace();
+    let program = args
+        .next()
+        .ok_or_else(|| Error::Msg("The editor command is empty".to_string()))?;
+
+    let status = std::process::Command::new(program)
+        .args(args)
+        .arg(file.0.path())
+        .status()
+        .map_err(|e| Error::Msg(format!("Failed to run editor `{}`: {}", program, e)))?;
+
+    if !status.success() {
+        return Err(Error::Msg(format!(
+            "Editor `{}` exited with {}",
+            program, status
+        )));
+    }
+
+    Ok(std::fs::read_to_string(file.0.path())?)
+}
+
+/// Run the command that rotates a secret
+///
+/// The command is run by the shell, it receives the current value on stdin
+/// and prints the new value to stdout. A single trailing newline is removed
+/// from the new value.
+///
+/// # Parameters
+/// - `command` - the command to run
+/// - `current` - the current value of the secret
+///
+/// # Returns
+/// - `Result<Vec<u8>>`: the new value, an error if the command failed or did
+///   not print anything
+pub fn run_rotation_command(command: &str, current: &[u8]) -> Result<Vec<u8>> {
+    #[cfg(target_family = "unix")]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+
+    #[cfg(target_family = "windows")]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    };
+
+    let mut child = cmd
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::inherit())
+        .spawn()
+        .map_err(|e| Error::Msg(format!("Failed to run `{}`: {}", command, e)))?;
+
+    let mut stdin = child.stdin.take().unwrap(); // unwrap is safe here because stdin is piped
+    let current = current.to_vec();
+
+    // Written from another thread so that a command which prints a lot before
+    // reading its input can not block forever
+    let writer = std::thread::spawn(move || match stdin.write_all(&current) {
+        // Commands that do not need the current value may exit without
+        // reading it
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    });
+
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(Error::Msg(format!(
+            "The rotation command `{}` exited with {}",
+            command, output.status
+        )));
+    }
+
+    writer
+        .join()
+        .map_err(|_| Error::Msg("Failed to pass the current value to the command".to_string()))??;
+
+    let mut value = output.stdout;
+
+    if value.ends_with(b"\n") {
+        value.pop();
+
+        if value.ends_with(b"\r") {
+            value.pop();
+        }
     }
 
+    if value.is_empty() {
+        return Err(Error::Msg(format!(
+            "The rotation command `{}` did not print a new value",
+            command
+        )));
+    }
+
+    Ok(value)
+}
+
+/// Temporary file which is overwritten with zeros before it is removed
+struct WipedTempFile(tempfile::NamedTempFile);
+
+impl Drop for WipedTempFile {
+    fn drop(&mut self) {
+        let wipe = |mut file: &std::fs::File| -> std::io::Result<()> {
+            let len = file.metadata()?.len();
+
+            file.rewind()?;
+            file.write_all(&vec![0; len as usize])?;
+            file.sync_all()
+        };
+
+        // Editors that save by renaming a new file over the old one leave
+        // the original content in the file that is still open
+        let _ = wipe(self.0.as_file());
+
+        if let Ok(file) = std::fs::OpenOptions::new().write(true).open(self.0.path()) {
+            let _ = wipe(&file);
+        }
+    }
+}
+
+/// Delete a profile from the store
+///
+/// # Parameters
+/// - `store` - the store the profile is kept in
+/// - `name` - the name of the profile to delete
+///
+/// # Returns
+/// - `Result<()>`: whether the operation was successful
+pub fn delete_profile(store: &Arc<dyn ProfileStore>, name: &str) -> Result<()> {
+    store.delete(name)?;
+
+    println!("{}: Deleted profile", "Success".green());
     Ok(())
 }
 
-/// List all the stored profiles in the profiles directory
+/// List all the stored profiles
 ///
 /// # Parameters
+/// - `store` - the store to list the profiles of
 /// - `raw` - whether to list the profiles in raw format. If true, the profiles
 ///   will be listed without any decorations
 ///
 /// # Returns
 /// - `Result<()>`: whether the operation was successful
-pub fn list_profiles(raw: bool) -> Result<()> {
-    let configdir = get_configdir()?;
-    let profile_dir = configdir.join("profiles");
-
-    if !profile_dir.exists() {
-        return Err(Error::Msg("profiles directory does not exist".to_string()));
-    }
-
-    let mut profiles = Vec::new();
-    for entry in std::fs::read_dir(profile_dir).unwrap() {
-        let entry = entry.unwrap();
-        let path = entry.path();
-        match path.extension() {
-            None => continue,
-            Some(ext) => {
-                if ext != "env" {
-                    continue;
-                }
-            }
-        }
-        let profile_name = path.file_stem().unwrap().to_str().unwrap().to_owned();
-        if profile_name.starts_with('.') {
-            continue;
-        }
-        profiles.push(profile_name);
-    }
+pub fn list_profiles(store: &Arc<dyn ProfileStore>, raw: bool) -> Result<()> {
+    let profiles = store.list()?;
 
     if raw {
         if profiles.is_empty() {
@@ -254,38 +826,126 @@ pub fn list_profiles(raw: bool) -> Result<()> {
     }
 
     let mut table = Table::new();
-    table.set_header(vec![Cell::new("Profiles").add_attribute(Attribute::Bold)]);
+    table.set_header(vec![
+        Cell::new("Profiles").add_attribute(Attribute::Bold),
+        Cell::new("Description").add_attribute(Attribute::Bold),
+        Cell::new("Owner").add_attribute(Attribute::Bold),
+        Cell::new("Created").add_attribute(Attribute::Bold),
+        Cell::new("Modified").add_attribute(Attribute::Bold),
+        Cell::new("Notes").add_attribute(Attribute::Bold),
+    ]);
 
     for profile in profiles {
-        table.add_row(vec![profile]);
+        let metadata = store.read_metadata(&profile)?;
+
+        // Only the beginning of the notes is shown to keep the table readable
+        let notes = metadata.notes.as_deref().unwrap_or_default();
+        let first_line = notes.lines().next().unwrap_or_default();
+        let mut notes: String = first_line.chars().take(MAX_DISPLAYED_VALUE_LEN).collect();
+
+        if notes.len() < metadata.notes.as_deref().unwrap_or_default().len() {
+            notes.push_str("...");
+        }
+
+        table.add_row(vec![
+            profile,
+            metadata.description.unwrap_or_default(),
+            metadata.owner.unwrap_or_default(),
+            format_timestamp(metadata.created_at).unwrap_or_default(),
+            format_timestamp(metadata.modified_at).unwrap_or_default(),
+            notes,
+        ]);
     }
 
     println!("{table}");
     Ok(())
 }
 
-/// Download a profile from a URL and store it in the profiles directory
+/// Print the metadata of a profile
+///
+/// # Parameters
+/// - `profile_name` - the name of the profile
+/// - `metadata` - the metadata of the profile
+pub fn display_metadata(profile_name: &str, metadata: &ProfileMetadata) {
+    let or_unset = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+    println!("{} {}", "Profile:".bold(), profile_name);
+    println!(
+        "{} {}",
+        "Description:".bold(),
+        or_unset(metadata.description.clone())
+    );
+    println!("{} {}", "Owner:".bold(), or_unset(metadata.owner.clone()));
+    println!(
+        "{} {}",
+        "Encryption:".bold(),
+        or_unset(metadata.encryption_type.clone())
+    );
+    println!(
+        "{} {}",
+        "Variables:".bold(),
+        or_unset(metadata.env_count.map(|count| count.to_string()))
+    );
+    println!(
+        "{} {}",
+        "Created:".bold(),
+        or_unset(format_timestamp(metadata.created_at))
+    );
+    println!(
+        "{} {}",
+        "Modified:".bold(),
+        or_unset(format_timestamp(metadata.modified_at))
+    );
+    println!("{} {}", "Notes:".bold(), or_unset(metadata.notes.clone()));
+}
+
+/// Format a timestamp of the metadata of a profile in the local timezone
+fn format_timestamp(timestamp: Option<DateTime<Utc>>) -> Option<String> {
+    timestamp.map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+}
+
+/// Check that the content of a profile file can be loaded and matches its
+/// schema before it is added to the store
+///
+/// # Parameters
+/// - `content` - the encrypted content of the profile file
+/// - `load` - decrypts and reads the content
+///
+/// # Returns
+/// - `Result<()>`: an error if the profile can not be added to the store
+fn check_imported_profile<F>(content: &[u8], load: F) -> Result<()>
+where
+    F: FnOnce(&[u8]) -> Result<Profile>,
+{
+    let profile =
+        load(content).map_err(|e| Error::Msg(format!("Unable to read the profile: {}", e)))?;
+
+    check_schema(&profile)
+}
+
+/// Download a profile from a URL and add it to the store
+///
+/// The profile is only added once it has been decrypted and validated against
+/// its schema.
 ///
 /// # Parameters
+/// - `store` - the store to add the profile to
 /// - `url` - the URL to download the profile from
 /// - `profile_name` - the name of the profile to store the downloaded profile as
+/// - `load` - decrypts and reads the downloaded content
 ///
 /// # Returns
 /// - `Result<()>`: whether the operation was successful
-pub fn download_profile(url: String, profile_name: String) -> Result<()> {
+pub fn download_profile<F>(
+    store: &Arc<dyn ProfileStore>,
+    url: String,
+    profile_name: String,
+    load: F,
+) -> Result<()>
+where
+    F: FnOnce(&[u8]) -> Result<Profile>,
+{
     println!("Downloading profile from {}", url);
-    let configdir = get_configdir()?;
-
-    let location = match configdir
-        .join("profiles")
-        .join(profile_name.clone() + ".env")
-        .to_str()
-    {
-        Some(location) => location.to_owned(),
-        None => {
-            return Err(Error::Msg("Could not convert path to string".to_string()));
-        }
-    };
 
     let runtime = match tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -297,150 +957,438 @@ pub fn download_profile(url: String, profile_name: String) -> Result<()> {
         }
     };
 
-    runtime.block_on(download_file(url.as_str(), location.as_str()))?;
+    let content = runtime.block_on(download_file(url.as_str()))?;
+
+    check_imported_profile(&content, load)?;
+    store.write(&profile_name, &content)?;
 
     println!("Downloaded profile: {}", profile_name);
     Ok(())
 }
 
-/// Import a profile stored somewhere on the system but not in the profiles directory
+/// Import a profile stored somewhere on the system into the store
+///
+/// The profile is only added once it has been decrypted and validated against
+/// its schema.
 ///
 /// # Parameters
+/// - `store` - the store to add the profile to
 /// - `file_path` - the path to the profile file
 /// - `profile_name` - the name of the profile to store the imported profile as
+/// - `load` - decrypts and reads the content of the profile file
 ///
 /// # Returns
 /// - `Result<()>`: whether the operation was successful
-pub fn import_profile(file_path: String, profile_name: String) -> Result<()> {
+pub fn import_profile<F>(
+    store: &Arc<dyn ProfileStore>,
+    file_path: String,
+    profile_name: String,
+    load: F,
+) -> Result<()>
+where
+    F: FnOnce(&[u8]) -> Result<Profile>,
+{
     if !Path::new(&file_path).exists() {
         return Err(Error::Msg(format!("File `{}` does not exist", file_path)));
     }
 
-    let configdir = get_configdir()?;
+    let content = std::fs::read(&file_path)?;
 
-    let mut file = std::fs::OpenOptions::new()
-        .read(true)
-        .open(&file_path)
-        .unwrap();
+    check_imported_profile(&content, load)?;
+    store.write(&profile_name, &content)?;
 
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+    Ok(())
+}
 
-    let location = match configdir
-        .join("profiles")
-        .join(profile_name.clone() + ".env")
-        .to_str()
-    {
-        Some(location) => location.to_owned(),
+/// Move all the profiles to another kind of store and keep them there from now
+/// on
+///
+/// The profiles are copied as they are, still encrypted, along with their
+/// metadata. They are only removed from the current store once all of them
+/// have been added to the other store, which adds either all of them or none,
+/// see [ProfileStore::add_all].
+///
+/// # Parameters
+/// - `to` - the kind of store to move the profiles to
+///
+/// # Returns
+/// - `Result<()>`: whether the operation was successful
+pub fn migrate_store(to: StoreKind) -> Result<()> {
+    let from = StoreKind::current()?;
+
+    if from == to {
+        println!(
+            "{}",
+            format!("Profiles are already kept in the {} store", to).bold()
+        );
+        return Ok(());
+    }
+
+    let source = from.open()?;
+    let destination = to.open()?;
+
+    let names = source.list()?;
+
+    if let Some(name) = names.iter().find(|name| destination.exists(name)) {
+        return Err(Error::Msg(format!(
+            "Profile `{}` already exists in the {} store",
+            name, to
+        )));
+    }
+
+    // Keep other envio processes from changing the profiles while they move
+    let mut _locks = Vec::with_capacity(names.len() * 2);
+
+    for name in &names {
+        _locks.push(source.lock(name)?);
+        _locks.push(destination.lock(name)?);
+    }
+
+    let profiles = names
+        .iter()
+        .map(|name| {
+            Ok(StoredProfile {
+                name: name.clone(),
+                content: source.read(name)?,
+                metadata: source.read_metadata(name)?,
+            })
+        })
+        .collect::<Result<Vec<StoredProfile>>>()?;
+
+    destination.add_all(&profiles)?;
+
+    to.make_current()?;
+
+    for name in &names {
+        if let Err(e) = source.delete(name) {
+            println!(
+                "{}: Failed to remove `{}` from the {} store: {}",
+                "Warning".yellow(),
+                name,
+                from,
+                e
+            );
+        }
+    }
+
+    println!(
+        "{}: Moved {} profile(s) from the {} store to the {} store",
+        "Success".green(),
+        names.len(),
+        from,
+        to
+    );
+
+    Ok(())
+}
+
+/// Generate the shell code that loads the environment variables of a profile
+/// into the current shell
+///
+/// The environment variables loaded before, as listed in `ENVIO_LOADED_KEYS`,
+/// are removed unless the profile sets them again. Without a profile the code
+/// only removes them.
+///
+/// # Parameters
+/// - `profile` - the profile to load ([Profile] object), `None` to unload
+/// - `shell` - the shell to generate code for
+///
+/// # Returns
+/// - `String`: the shell code
+pub fn shell_env(profile: Option<&Profile>, shell: Shell) -> String {
+    let envs = profile.into_iter().flat_map(|profile| &profile.envs);
+    let loaded = std::env::var(LOADED_KEYS_ENV).unwrap_or_default();
+    let (mut changes, keys) = replace_envs_changes(envs, &loaded, shell);
+    let keys = keys.join(":");
+
+    match profile {
+        Some(profile) => {
+            changes.push(EnvChange::Set(LOADED_KEYS_ENV, &keys));
+            changes.push(EnvChange::Set(LOADED_PROFILE_ENV, &profile.name));
+        }
         None => {
-            return Err(Error::Msg("Could not convert path to string".to_string()));
+            changes.push(EnvChange::Unset(LOADED_KEYS_ENV));
+            changes.push(EnvChange::Unset(LOADED_PROFILE_ENV));
         }
-    };
+    }
 
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .open(location)
-        .unwrap();
+    shell.script(&changes)
+}
+
+/// Get the changes that set `envs` and remove the environment variables in
+/// `loaded`, the value of `ENVIO_LOADED_KEYS`, which are not set again
+///
+/// # Returns
+/// - `(Vec<EnvChange>, Vec<&str>)`: the changes and the keys that are set
+fn replace_envs_changes<'a>(
+    envs: impl IntoIterator<Item = &'a Env>,
+    loaded: &'a str,
+    shell: Shell,
+) -> (Vec<EnvChange<'a>>, Vec<&'a str>) {
+    let mut changes = Vec::new();
+    let mut keys = Vec::new();
+
+    for env in envs {
+        if !is_valid_name(&env.name) {
+            eprintln!(
+                "{}: '{}' is not a valid name for a shell variable and was left out",
+                "Warning".yellow(),
+                env.name
+            );
+            continue;
+        }
 
-    file.write_all(contents.as_bytes())?;
+        if !shell.supports_value(&env.value) {
+            eprintln!(
+                "{}: The value of '{}' can not be passed to {} and was left out",
+                "Warning".yellow(),
+                env.name,
+                shell
+            );
+            continue;
+        }
 
-    Ok(())
+        changes.push(EnvChange::Set(&env.name, &env.value));
+        keys.push(env.name.as_str());
+    }
+
+    for key in loaded.split(':') {
+        if is_valid_name(key) && !keys.contains(&key) {
+            changes.push(EnvChange::Unset(key));
+        }
+    }
+
+    (changes, keys)
 }
 
-// Unix specific code
-// Creates a shell script that can be sourced to set the environment variables
-#[cfg(target_family = "unix")]
-pub fn create_shellscript(profile: &str) -> Result<()> {
-    let configdir = get_configdir()?;
-    let shellscript_path = configdir.join("setenv.sh");
+/// Get the changes that give the environment variables saved in
+/// `ENVIO_PROJECT_SAVED` their previous values back
+///
+/// # Parameters
+/// - `saved` - the saved keys and values
+/// - `keep` - keys that are set again and are left alone
+/// - `loaded_keys` - the keys loaded by `envio load`, which are left alone
+///
+/// # Returns
+/// - `Vec<EnvChange>`: the changes
+fn restore_saved_envs_changes<'a>(
+    saved: &'a [(String, Option<String>)],
+    keep: &[&str],
+    loaded_keys: &[&str],
+) -> Vec<EnvChange<'a>> {
+    saved
+        .iter()
+        .filter(|(key, _)| {
+            is_valid_name(key)
+                && !keep.contains(&key.as_str())
+                && !loaded_keys.contains(&key.as_str())
+        })
+        .map(|(key, value)| match value {
+            Some(value) => EnvChange::Set(key, value),
+            None => EnvChange::Unset(key),
+        })
+        .collect()
+}
 
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .append(false)
-        .open(shellscript_path)?;
-
-    let shellscript = format!(
-        r#"#!/bin/bash
-# This script was generated by envio and should not be modified!
-
-has_error_occurred=false
-handle_error() {{
-    has_error_occurred=true
-}}
-
-raw_output=$(envio list -n {} -v)
-
-if ! echo "$raw_output" | grep -q "="; then
-    echo -e "\e[31mError: \e[0mFailed to load environment variables from profile '{}'" >&2
-    handle_error
-fi
-
-if [ "$has_error_occurred" = false ]; then
-    ENV_VARS=$(echo "$raw_output" | awk -F "=" '/^[^=]+=.+/{{print}}')
-    SHELL_NAME=$(basename "$SHELL")
-
-    case "$SHELL_NAME" in
-        bash | zsh)
-            source <(echo '
-            #!/bin/bash
-            echo "$ENV_VARS" | while IFS= read -r line; do
-                export $line
-            done 
-        ')
-            ;;
-        fish)
-            source <(echo '
-            #!/bin/fish
-            echo "$ENV_VARS" | while IFS= read -r line; do
-                set -gx $line
-            done 
-        ')
-            ;;
-        *)
-            echo -e "\e[31mError: \e[0mUnsupported shell ($SHELL_NAME)" >&2
-            handle_error
-            ;;
-    esac
-fi
-"#,
-        profile, profile
-    );
+/// Encode keys and their previous values for `ENVIO_PROJECT_SAVED`
+fn encode_saved_envs(saved: &[(String, Option<String>)]) -> String {
+    saved
+        .iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{}={}", key, BASE64.encode(value)),
+            None => key.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join(":")
+}
 
-    file.write_all(shellscript.as_bytes())?;
-    file.flush()?;
-    file.sync_all()?;
-    Ok(())
+/// Decode the value of `ENVIO_PROJECT_SAVED`, entries that can not be decoded
+/// are skipped
+fn decode_saved_envs(saved: &str) -> Vec<(String, Option<String>)> {
+    saved
+        .split(':')
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| match entry.split_once('=') {
+            Some((key, value)) => {
+                let value = String::from_utf8(BASE64.decode(value).ok()?).ok()?;
+                Some((key.to_string(), Some(value)))
+            }
+            None => Some((entry.to_string(), None)),
+        })
+        .collect()
 }
 
-/// Load the environment variables of the profile into the current session
+/// Generate the shell code that loads the profile of the project the current
+/// directory belongs to, run by the prompt hook before every prompt
+///
+/// Nothing is generated as long as the project file the hook handled last
+/// stays the same. Otherwise the environment variables of the previous
+/// project get back the values they had before it was entered and the ones of
+/// the new project are loaded, if the user allowed its project file. Variables
+/// loaded by `envio load` are neither overwritten nor restored.
 ///
 /// # Parameters
-/// - `profile_name` - the name of the profile to load
+/// - `shell` - the shell to generate code for
+/// - `load` - loads a profile by name
 ///
 /// # Returns
-/// - `Result<()>`: whether the operation was successful
-#[cfg(target_family = "unix")]
-pub fn load_profile(profile_name: &str) -> Result<()> {
-    if !Profile::does_exist(profile_name) {
-        return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+/// - `Result<String>`: the shell code
+pub fn project_env<F>(shell: Shell, load: F) -> Result<String>
+where
+    F: FnOnce(&str) -> Result<Profile>,
+{
+    let hash = std::env::var(PROJECT_HASH_ENV).ok();
+    let state = std::env::var(PROJECT_STATE_ENV).unwrap_or_default();
+    let saved = decode_saved_envs(&std::env::var(PROJECT_SAVED_ENV).unwrap_or_default());
+    let loaded_keys = std::env::var(LOADED_KEYS_ENV).unwrap_or_default();
+    let loaded_keys: Vec<&str> = loaded_keys.split(':').collect();
+
+    let project = Project::find(&get_cwd())
+        .map(|path| Project::open(&path))
+        .transpose()?;
+
+    let unchanged = match &project {
+        Some(project) if hash.as_deref() == Some(project.hash()) => match state.as_str() {
+            "loaded" => project.is_allowed()?,
+            "blocked" => !project.is_allowed()?,
+            // Only retried once the file changes or the shell comes back to it
+            _ => true,
+        },
+        Some(_) => false,
+        None => hash.is_none(),
+    };
+
+    if unchanged {
+        return Ok(String::new());
     }
 
-    let shell_config = get_shell_config()?;
+    let project = match project {
+        Some(project) => project,
+        None => {
+            let mut changes = restore_saved_envs_changes(&saved, &[], &loaded_keys);
+            changes.push(EnvChange::Unset(PROJECT_SAVED_ENV));
+            changes.push(EnvChange::Unset(PROJECT_HASH_ENV));
+            changes.push(EnvChange::Unset(PROJECT_STATE_ENV));
 
-    create_shellscript(profile_name)?;
+            return Ok(shell.script(&changes));
+        }
+    };
 
-    if !shell_config.is_empty() {
-        println!(
-            "Reload your shell to apply changes or run `source {}`",
-            format_args!("~/{}", shell_config)
-        );
+    let allowed = project.is_allowed()?;
+
+    let loaded = if allowed {
+        project
+            .settings()
+            .and_then(|settings| Ok((load(&settings.profile)?, settings)))
+            .map(Some)
+            .unwrap_or_else(|e| {
+                eprintln!(
+                    "{}: Failed to load {}: {}",
+                    "Error".red(),
+                    project.path.display(),
+                    e
+                );
+                None
+            })
     } else {
-        println!("Reload your shell to apply changes");
-    }
+        eprintln!(
+            "{}: {} is not allowed, run `envio allow` to load it",
+            "Warning".yellow(),
+            project.path.display()
+        );
+        None
+    };
 
-    Ok(())
+    let envs: Vec<&Env> = match &loaded {
+        Some((profile, settings)) => match &settings.keys {
+            Some(keys) => keys
+                .iter()
+                .filter_map(|key| {
+                    let env = profile.envs.get_env(key);
+
+                    if env.is_none() {
+                        eprintln!(
+                            "{}: '{}' does not exist in profile '{}'",
+                            "Warning".yellow(),
+                            key,
+                            profile.name
+                        );
+                    }
+
+                    env
+                })
+                .collect(),
+            None => profile.envs.iter().collect(),
+        },
+        None => Vec::new(),
+    };
+
+    // Variables loaded by `envio load` take precedence over the project
+    let envs = envs.into_iter().filter(|env| {
+        let owned = loaded_keys.contains(&env.name.as_str());
+
+        if owned {
+            eprintln!(
+                "{}: '{}' was loaded by `envio load` and is not overwritten by the project",
+                "Warning".yellow(),
+                env.name
+            );
+        }
+
+        !owned
+    });
+
+    let (mut changes, keys) = replace_envs_changes(envs, "", shell);
+    changes.extend(restore_saved_envs_changes(&saved, &keys, &loaded_keys));
+
+    // Keys the previous project had set already keep their original value
+    let new_saved: Vec<(String, Option<String>)> = keys
+        .iter()
+        .map(|key| match saved.iter().find(|(k, _)| k == key) {
+            Some(entry) => entry.clone(),
+            None => (key.to_string(), std::env::var(key).ok()),
+        })
+        .collect();
+    let new_saved = encode_saved_envs(&new_saved);
+
+    changes.push(EnvChange::Set(PROJECT_SAVED_ENV, &new_saved));
+    changes.push(EnvChange::Set(PROJECT_HASH_ENV, project.hash()));
+
+    let state = match &loaded {
+        Some((profile, _)) => {
+            eprintln!(
+                "{}: Loaded profile '{}' from {}",
+                "Success".green(),
+                profile.name,
+                project.path.display()
+            );
+            "loaded"
+        }
+        None if allowed => "failed",
+        None => "blocked",
+    };
+    changes.push(EnvChange::Set(PROJECT_STATE_ENV, state));
+
+    Ok(shell.script(&changes))
+}
+
+/// Get the error for `envio load` and `envio unload` reaching the binary,
+/// which only happens if the shell hook is not set up
+///
+/// # Parameters
+/// - `shell` - the shell from the config, detected if it is `None`
+#[cfg(target_family = "unix")]
+pub fn hook_missing_error(shell: Option<&str>) -> Error {
+    match Shell::detect(shell) {
+        Ok(shell) => Error::Msg(format!(
+            "The shell hook is not set up, {} and start a new shell",
+            shell.setup()
+        )),
+        Err(_) => Error::Msg(
+            "The shell hook is not set up, see `envio hook --help` for how to set it up"
+                .to_string(),
+        ),
+    }
 }
 
 /// Windows implementation of the load_profile function
@@ -468,24 +1416,6 @@ pub fn load_profile(profile: Profile) -> envio::error::Result<()> {
     Ok(())
 }
 
-/// Unload the environment variables of the profile from the current session
-///
-/// # Returns
-/// - `Result<()>`: whether the operation was successful
-#[cfg(target_family = "unix")]
-pub fn unload_profile() -> Result<()> {
-    let file = std::fs::OpenOptions::new()
-        .write(true)
-        .append(false)
-        .open(get_configdir()?.join("setenv.sh"))
-        .unwrap();
-
-    file.set_len(0)?;
-
-    println!("Reload your shell to apply changes");
-    Ok(())
-}
-
 /// Windows implementation of the unload_profile function
 #[cfg(target_family = "windows")]
 pub fn unload_profile(profile: Profile) -> Result<()> {
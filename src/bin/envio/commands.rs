@@ -1,46 +1,134 @@
 /// Implementation of all the subcommands that can be run by the CLI
-use chrono::Local;
+use chrono::{Local, NaiveDate, Utc};
 use colored::Colorize;
 use inquire::{
     min_length, Confirm, DateSelect, MultiSelect, Password, PasswordDisplayMode, Select, Text,
 };
 use regex::Regex;
-use std::collections::HashMap;
-use std::env;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Read;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use url::Url;
 
-use envio::crypto::create_encryption_type;
 use envio::crypto::gpg::get_gpg_keys;
+use envio::crypto::{create_encryption_type, get_encryption_type_from_bytes, EncryptionType};
 use envio::error::{Error, Result};
-use envio::{load_profile, Env, EnvVec, Profile};
-
-use crate::clap_app::Command;
-use crate::cli::{self, check_expired_envs};
-use crate::utils::parse_envs_from_string;
+use envio::escape;
+use envio::format::{LoadOptions, LoadWarning};
+use envio::store::{ProfileStore, StoreKind, StoreLock, DATABASE_FILE_NAME};
+use envio::utils::{get_datadir, parse_duration};
+use envio::{Env, EnvVec, ExpirationPolicy, Profile, ProfileMetadata, ValueEncoding};
+
+use crate::clap_app::{Command, ConfigCommand, StoreCommand};
+use crate::cli::{self, ProfileUsage};
+use crate::config::{self, Config};
+use crate::generate::{generate_secret, SecretOptions};
+use crate::project::Project;
+use crate::shell::{is_valid_name, Shell};
+use crate::utils::{
+    date_after_period, env_from_file_arg, env_from_key_value, open_store,
+    parse_annotated_envs_from_string, parse_envs_from_string, parse_expiration_date,
+};
 
 /// Get the user's encryption key
-fn get_userkey() -> String {
-    println!("{}", "Loading Profile".green());
-    let prompt = Password::new("Enter your encryption key:")
+fn get_userkey() -> Result<String> {
+    eprintln!("{}", "Loading Profile".green());
+
+    Password::new("Enter your encryption key:")
         .with_display_toggle_enabled()
         .with_display_mode(PasswordDisplayMode::Masked)
         .with_help_message("OH NO! you forgot your key! just kidding... or did you?")
         .without_confirmation()
-        .prompt();
+        .prompt()
+        .map_err(|e| Error::Msg(e.to_string()))
+}
 
-    if let Err(e) = prompt {
-        println!("{}: {}", "Error".red(), e);
-        std::process::exit(1);
-    } else {
-        prompt.unwrap()
+/// Get the encryption type of the content of a profile file, asking the user
+/// for the key if it is encrypted using `age`
+fn get_encryption_type_with_key(encrypted_content: &[u8]) -> Result<Box<dyn EncryptionType>> {
+    let mut encryption_type = get_encryption_type_from_bytes(encrypted_content)?;
+
+    if encryption_type.as_string() == "age" {
+        encryption_type.set_key(get_userkey()?);
+    }
+
+    Ok(encryption_type)
+}
+
+/// Decrypt and read the content of a profile file which is not in the store
+/// yet
+fn profile_from_bytes(profile_name: &str, encrypted_content: &[u8]) -> Result<Profile> {
+    let encryption_type = get_encryption_type_with_key(encrypted_content)?;
+
+    Profile::from_bytes(profile_name, encrypted_content, encryption_type)
+}
+
+/// Load a profile, asking the user before a profile which can only be read in
+/// the plain text format of the first versions of envio is migrated
+///
+/// Profiles stored in an older format are written back in the current format
+/// while holding the lock of the profile.
+fn load_profile(store: &Arc<dyn ProfileStore>, profile_name: &str) -> Result<Profile> {
+    let encrypted_content = store.read(profile_name)?;
+    let encryption_type = get_encryption_type_with_key(&encrypted_content)?;
+
+    let confirm_text_format = || {
+        eprintln!(
+            "{}: Unable to deserialize the profile content\n\n\
+             This may indicate:\n \
+             - The file has been tampered with\n \
+             - It was created with an older version of the tool\n",
+            "Warning".yellow().bold()
+        );
+
+        Confirm::new("Do you want to fallback to the old way of reading the profile?")
+            .with_default(false)
+            .with_help_message("If the file has been tampered with, then falling back to the old way of reading the profile will not work")
+            .prompt()
+            .unwrap_or(false)
+    };
+
+    let options = LoadOptions {
+        confirm_text_format: Some(&confirm_text_format),
+        ..Default::default()
+    };
+
+    let (mut profile, report) =
+        Profile::load_from(store.clone(), profile_name, encryption_type, &options)?;
+
+    let written_back =
+        report.migrated && write_back(store, profile_name, &mut profile, &encrypted_content)?;
+
+    for warning in &report.warnings {
+        match warning {
+            LoadWarning::Migrated { .. } if written_back => {
+                eprintln!("{}: {}", "Success".green(), warning)
+            }
+            // The user has already been asked about it
+            LoadWarning::TextFormat => {}
+            // Handled according to the expiration policy of the profile, see
+            // `cli::enforce_expiration_policy`
+            LoadWarning::ExpiredEnvs(_) => {}
+            _ => eprintln!("{}: {}", "Warning".yellow(), warning),
+        }
     }
+
+    Ok(profile)
+}
+
+/// Get the names of the environment variables passed as `KEY` or `KEY=VALUE`
+/// arguments
+fn env_names(envs: &[String]) -> HashSet<String> {
+    envs.iter()
+        .map(|env| env.split_once('=').map_or(env.as_str(), |(key, _)| key))
+        .map(str::to_string)
+        .collect()
 }
 
 /// Check to see if the user is using a vi based editor so that we can use the vim mode in the inquire crate
-fn get_vim_mode() -> Result<bool> {
-    let env = env::var("VISUAL").unwrap_or_else(|_| env::var("EDITOR").unwrap_or_default());
+fn get_vim_mode(config: &Config) -> Result<bool> {
+    let env = config.editor().unwrap_or_default();
 
     let program = env.split_whitespace().next().ok_or("")?; // Throw an error if the program is empty, we don't really care about the error message
 
@@ -52,10 +140,137 @@ fn get_vim_mode() -> Result<bool> {
     Ok(Regex::new(r"n?vim?").unwrap().is_match(program_stem)) // unwrap is safe here because we know that the regex will always compile
 }
 
+/// Write a migrated profile back in the current format
+///
+/// The profile is locked for the write unless this process already holds its
+/// lock, and it is only written when nobody changed it since it was read.
+///
+/// # Parameters
+/// - `store` - the store the profile was loaded from
+/// - `profile_name` - the name the profile was loaded with
+/// - `profile` - the migrated profile
+/// - `encrypted_content` - the content the profile was loaded from
+///
+/// # Returns
+/// - `Result<bool>`: whether the profile was written back
+fn write_back(
+    store: &Arc<dyn ProfileStore>,
+    profile_name: &str,
+    profile: &mut Profile,
+    encrypted_content: &[u8],
+) -> Result<bool> {
+    let held = HELD_LOCKS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|name| name == profile_name);
+
+    let _lock = if held {
+        None
+    } else {
+        Some(lock_profile(store, profile_name)?)
+    };
+
+    if store.read(profile_name)? != encrypted_content {
+        return Ok(false);
+    }
+
+    profile.push_changes()?;
+    Ok(true)
+}
+
+/// Names of the profiles whose lock is held by this process, locks are not
+/// reentrant so a held lock must not be taken again
+static HELD_LOCKS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Lock on a profile taken by [lock_profile], released when dropped
+struct ProfileLock {
+    _lock: StoreLock,
+    profile_name: String,
+}
+
+impl Drop for ProfileLock {
+    fn drop(&mut self) {
+        let mut held = HELD_LOCKS.lock().unwrap();
+
+        if let Some(index) = held.iter().position(|name| *name == self.profile_name) {
+            held.remove(index);
+        }
+    }
+}
+
+/// Exit status of `envio expiring --exit-code` if nothing expires but some
+/// profiles could not be checked, to tell it apart from expiring environment
+/// variables which exit with 1
+const EXPIRING_UNCHECKED_EXIT_CODE: i32 = 2;
+
+/// Get the expiration dates of the environment variables of a profile
+///
+/// They are read from the metadata of the profile, so that `envio expiring`
+/// does not have to decrypt it. Profiles written before the metadata recorded
+/// them are decrypted once and their expiration dates are recorded.
+fn expiration_dates(
+    store: &Arc<dyn ProfileStore>,
+    profile_name: &str,
+) -> Result<BTreeMap<String, NaiveDate>> {
+    if let Some(dates) = store.read_metadata(profile_name)?.expiration_dates {
+        return Ok(dates);
+    }
+
+    let _lock = lock_profile(store, profile_name)?;
+    let mut profile = load_profile(store, profile_name)?;
+    let dates = profile.expiration_dates();
+
+    profile.metadata.expiration_dates = Some(dates.clone());
+    store.write_metadata(profile_name, &profile.metadata)?;
+
+    Ok(dates)
+}
+
+/// Lock a profile for the duration of a read-modify-write so that concurrent
+/// envio processes do not overwrite each other's changes
+fn lock_profile(store: &Arc<dyn ProfileStore>, profile_name: &str) -> Result<ProfileLock> {
+    let lock = match store.try_lock(profile_name)? {
+        Some(lock) => lock,
+        None => {
+            eprintln!(
+                "{}",
+                "Waiting for another envio process to finish with the profile..".bold()
+            );
+
+            store.lock(profile_name)?
+        }
+    };
+
+    HELD_LOCKS.lock().unwrap().push(profile_name.to_string());
+
+    Ok(ProfileLock {
+        _lock: lock,
+        profile_name: profile_name.to_string(),
+    })
+}
+
 impl Command {
+    /// Whether to tell the user about new versions before running the
+    /// subcommand, which is skipped for the ones whose output is evaluated by
+    /// the shell
+    pub fn checks_for_updates(&self) -> bool {
+        !matches!(
+            self,
+            Command::Env { .. } | Command::Hook { .. } | Command::HookEnv { .. }
+        )
+    }
+
+    /// Whether the subcommand can run when the config file can not be loaded,
+    /// which is the case for `envio config` as it is used to repair the file
+    pub fn runs_without_config(&self) -> bool {
+        matches!(self, Command::Config { .. })
+    }
+
     /// Run the subcommand that was passed to the CLI
-    pub fn run(&self) -> Result<()> {
-        let vim_mode = get_vim_mode().unwrap_or(false);
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let vim_mode = get_vim_mode(config).unwrap_or(false);
+        let store = open_store()?;
 
         match self {
             Command::Create {
@@ -65,20 +280,50 @@ impl Command {
                 gpg,
                 add_comments,
                 add_expiration_date,
+                expires,
+                schema,
+                from_file,
+                description,
+                owner,
+                notes,
             } => {
                 if profile_name.is_empty() {
                     return Err(Error::ProfileNameEmpty(profile_name.to_string()));
                 }
 
-                if Profile::does_exist(profile_name) {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if store.exists(profile_name) {
                     return Err(Error::ProfileExists(profile_name.to_string()));
                 }
 
+                // Parse the schema first so that an invalid schema is reported
+                // before the user is asked for anything
+                let schema = match schema {
+                    Some(file) => Some(cli::read_schema(file)?),
+                    None => None,
+                };
+
+                let expires = expires.as_deref().map(parse_expiration_date).transpose()?;
+
                 let gpg_key;
                 let encryption_type;
 
-                if gpg.is_some() {
-                    if gpg.as_ref().unwrap() == "select" {
+                // Fall back to the defaults from the config
+                let gpg = match gpg {
+                    Some(gpg) => Some(gpg.clone()),
+                    None if config.encryption_type() == "gpg" => Some(
+                        config
+                            .encryption
+                            .gpg_key
+                            .clone()
+                            .unwrap_or_else(|| "select".to_string()),
+                    ),
+                    None => None,
+                };
+
+                if let Some(gpg) = gpg {
+                    if gpg == "select" {
                         let available_keys;
 
                         #[cfg(target_family = "unix")]
@@ -122,7 +367,7 @@ impl Command {
                             })
                             .unwrap();
                     } else {
-                        gpg_key = gpg.as_ref().unwrap().to_string();
+                        gpg_key = gpg;
                     }
 
                     encryption_type = create_encryption_type(gpg_key, "gpg")?;
@@ -130,7 +375,7 @@ impl Command {
                     let prompt = Password::new("Enter your encryption key:")
                         .with_display_toggle_enabled()
                         .with_display_mode(PasswordDisplayMode::Masked)
-                        .with_validator(min_length!(8))
+                        .with_validator(min_length!(config.passphrase_min_length()))
                         .with_formatter(&|_| String::from("Input received"))
                         .with_help_message(
                             "Remeber this key, you will need it to decrypt your profile later",
@@ -233,10 +478,10 @@ impl Command {
 
                             if let Some(key) = parts.next() {
                                 if let Some(value) = parts.next() {
-                                    envs_vec.as_mut().unwrap().push(Env::from_key_value(
-                                        key.to_string(),
-                                        value.to_string(),
-                                    ));
+                                    envs_vec
+                                        .as_mut()
+                                        .unwrap()
+                                        .push(env_from_key_value(key, value)?);
                                 } else {
                                     return Err(Error::Msg(format!(
                                         "Unable to parse value for key '{}'",
@@ -271,7 +516,13 @@ impl Command {
                     envs_vec = Some(EnvVec::new()); // The user created a profile without any envs
                 }
 
-                for env in envs_vec.as_mut().unwrap() {
+                if let Some(from_file) = from_file {
+                    for arg in from_file {
+                        envs_vec.as_mut().unwrap().push(env_from_file_arg(arg)?);
+                    }
+                }
+
+                for mut env in envs_vec.as_mut().unwrap().iter_mut() {
                     if *add_comments {
                         let prompt =
                             Text::new(&format!("Enter a comment for '{}':", env.name)).prompt();
@@ -279,11 +530,13 @@ impl Command {
                         if let Err(e) = prompt {
                             return Err(Error::Msg(e.to_string()));
                         } else {
-                            env.comment = Some(prompt.unwrap());
+                            *env.comment_mut() = Some(prompt.unwrap());
                         }
                     }
 
-                    if *add_expiration_date {
+                    if expires.is_some() {
+                        *env.expiration_date_mut() = expires;
+                    } else if *add_expiration_date {
                         let prompt = DateSelect::new(&format!(
                             "Select an expiration date for '{}':",
                             env.name
@@ -294,12 +547,26 @@ impl Command {
                         if let Err(e) = prompt {
                             return Err(Error::Msg(e.to_string()));
                         } else {
-                            env.expiration_date = Some(prompt.unwrap());
+                            *env.expiration_date_mut() = Some(prompt.unwrap());
                         }
                     }
                 }
 
-                cli::create_profile(profile_name.to_string(), envs_vec, encryption_type)?;
+                let metadata = ProfileMetadata {
+                    description: description.clone(),
+                    owner: owner.clone(),
+                    notes: notes.clone(),
+                    ..Default::default()
+                };
+
+                cli::create_profile(
+                    &store,
+                    profile_name.to_string(),
+                    envs_vec,
+                    schema,
+                    metadata,
+                    encryption_type,
+                )?;
             }
 
             Command::Add {
@@ -307,13 +574,21 @@ impl Command {
                 envs,
                 add_comments,
                 add_expiration_date,
+                expires,
+                sensitive,
+                from_file,
+                tags,
             } => {
-                if !Profile::does_exist(profile_name) {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
                     return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
-                let mut profile = load_profile!(profile_name, get_userkey)?;
-                check_expired_envs(&profile);
+                let expires = expires.as_deref().map(parse_expiration_date).transpose()?;
+
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
 
                 for env in envs {
                     if (*env).contains('=') {
@@ -325,7 +600,7 @@ impl Command {
                             }
 
                             if let Some(value) = parts.next() {
-                                profile.insert_env(key.to_string(), value.to_string())
+                                profile.envs.push(env_from_key_value(key, value)?);
                             } else {
                                 return Err(Error::Msg(format!(
                                     "Unable to parse value for key '{}'",
@@ -358,11 +633,32 @@ impl Command {
                     }
                 }
 
-                for env in &mut profile.envs {
-                    if envs.iter().find(|&e| e.contains(&env.name)).is_none() {
+                let mut added = env_names(envs);
+
+                for arg in from_file.iter().flatten() {
+                    let env = env_from_file_arg(arg)?;
+
+                    if profile.envs.contains_key(&env.name) {
+                        return Err(Error::EnvExists(env.name));
+                    }
+
+                    added.insert(env.name.clone());
+                    profile.envs.push(env);
+                }
+
+                for mut env in profile.envs.iter_mut() {
+                    if !added.contains(&env.name) {
                         continue;
                     }
 
+                    if let Some(sensitive) = sensitive {
+                        *env.sensitive_mut() = *sensitive;
+                    }
+
+                    if let Some(tags) = tags {
+                        *env.tags_mut() = cli::normalize_tags(tags);
+                    }
+
                     if *add_comments {
                         let prompt =
                             Text::new(&format!("Enter a comment for '{}':", env.name)).prompt();
@@ -370,11 +666,13 @@ impl Command {
                         if let Err(e) = prompt {
                             return Err(Error::Msg(e.to_string()));
                         } else {
-                            env.comment = Some(prompt.unwrap());
+                            *env.comment_mut() = Some(prompt.unwrap());
                         }
                     }
 
-                    if *add_expiration_date {
+                    if expires.is_some() {
+                        *env.expiration_date_mut() = expires;
+                    } else if *add_expiration_date {
                         let prompt = DateSelect::new(&format!(
                             "Select an expiration date for '{}':",
                             env.name
@@ -385,54 +683,132 @@ impl Command {
                         if let Err(e) = prompt {
                             return Err(Error::Msg(e.to_string()));
                         } else {
-                            env.expiration_date = Some(prompt.unwrap());
+                            *env.expiration_date_mut() = Some(prompt.unwrap());
                         }
                     }
                 }
 
+                cli::check_schema(&profile)?;
+
                 println!("{}", "Applying Changes".green());
                 profile.push_changes()?;
             }
 
+            // With the shell hook set up `envio env` is run instead
+            #[cfg(target_family = "unix")]
+            Command::Load { .. } => {
+                return Err(cli::hook_missing_error(config.shell.as_deref()));
+            }
+
+            #[cfg(target_family = "windows")]
             Command::Load { profile_name } => {
-                #[cfg(target_family = "unix")]
-                {
-                    cli::load_profile(profile_name)?;
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
-                #[cfg(target_family = "windows")]
-                {
-                    if !Profile::does_exist(profile_name) {
-                        return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
-                    }
-
-                    let profile = load_profile!(profile_name, get_userkey)?;
-                    check_expired_envs(&profile);
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Consume, config)?;
 
-                    if let Err(e) = cli::load_profile(profile) {
-                        return Err(e);
-                    }
+                if let Err(e) = cli::load_profile(profile) {
+                    return Err(e);
                 }
             }
 
             #[cfg(target_family = "unix")]
             Command::Unload => {
-                cli::unload_profile()?;
+                return Err(cli::hook_missing_error(config.shell.as_deref()));
             }
 
             #[cfg(target_family = "windows")]
             Command::Unload { profile_name } => {
-                if !Profile::does_exist(profile_name) {
+                if !store.exists(profile_name) {
                     return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
-                let profile = load_profile!(profile_name, get_userkey)?;
-                check_expired_envs(&profile);
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
 
                 if let Err(e) = cli::unload_profile(profile) {
                     return Err(e);
                 }
             }
+
+            Command::Env {
+                profile_name,
+                shell,
+                unset,
+            } => {
+                let shell = Shell::detect(shell.as_deref().or(config.shell.as_deref()))?;
+
+                let profile = match profile_name {
+                    Some(profile_name) if !*unset => {
+                        if !store.exists(profile_name) {
+                            return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                        }
+
+                        let mut profile = load_profile(&store, profile_name)?;
+                        cli::enforce_expiration_policy(
+                            &mut profile,
+                            ProfileUsage::Consume,
+                            config,
+                        )?;
+
+                        Some(profile)
+                    }
+                    _ => None,
+                };
+
+                print!("{}", cli::shell_env(profile.as_ref(), shell));
+            }
+
+            Command::Hook { shell } => {
+                print!("{}", shell.parse::<Shell>()?.hook());
+            }
+
+            Command::Allow { path } => {
+                let project = Project::locate(path.as_deref())?;
+
+                // Parsed first so that mistakes are reported right away
+                let settings = project.settings()?;
+
+                if !store.exists(&settings.profile) {
+                    println!(
+                        "{}: Profile '{}' does not exist yet",
+                        "Warning".yellow(),
+                        settings.profile
+                    );
+                }
+
+                project.allow()?;
+
+                println!(
+                    "{}: Allowed {} to load profile '{}'",
+                    "Success".green(),
+                    project.path.display(),
+                    settings.profile
+                );
+            }
+
+            Command::Deny { path } => {
+                let project = Project::locate(path.as_deref())?;
+                project.deny()?;
+
+                println!("{}: Denied {}", "Success".green(), project.path.display());
+            }
+
+            Command::HookEnv { shell } => {
+                let shell = Shell::detect(shell.as_deref().or(config.shell.as_deref()))?;
+
+                let code = cli::project_env(shell, |profile_name| {
+                    let mut profile = load_profile(&store, profile_name)?;
+                    cli::enforce_expiration_policy(&mut profile, ProfileUsage::Consume, config)?;
+
+                    Ok(profile)
+                })?;
+
+                print!("{}", code);
+            }
+
             Command::Launch {
                 profile_name,
                 command,
@@ -441,12 +817,12 @@ impl Command {
                 let program = split_command[0];
                 let args = &split_command[1..];
 
-                if !Profile::does_exist(profile_name) {
+                if !store.exists(profile_name) {
                     return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
-                let profile = load_profile!(profile_name, get_userkey)?;
-                check_expired_envs(&profile);
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Consume, config)?;
 
                 let mut cmd = std::process::Command::new(program)
                     .envs::<HashMap<String, String>, _, _>(profile.envs.into())
@@ -472,13 +848,15 @@ impl Command {
             }
 
             Command::Remove { profile_name, envs } => {
-                if !Profile::does_exist(profile_name) {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
                     return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
                 if envs.is_some() && !envs.as_ref().unwrap().is_empty() {
-                    let mut profile = load_profile!(profile_name, get_userkey)?;
-                    check_expired_envs(&profile);
+                    let mut profile = load_profile(&store, profile_name)?;
+                    cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
 
                     for env in envs.as_ref().unwrap() {
                         profile.remove_env(env)?;
@@ -487,7 +865,7 @@ impl Command {
                     println!("{}", "Applying Changes".green());
                     profile.push_changes()?;
                 } else {
-                    cli::delete_profile(profile_name)?;
+                    cli::delete_profile(&store, profile_name)?;
                 }
             }
 
@@ -497,25 +875,55 @@ impl Command {
                 no_pretty_print,
                 display_comments,
                 display_expiration_date,
+                reveal,
             } => {
+                let reveal = *reveal || config.reveal();
+
                 if *profiles {
-                    cli::list_profiles(*no_pretty_print)?;
+                    cli::list_profiles(&store, *no_pretty_print)?;
                 } else if profile_name.is_some() && !profile_name.as_ref().unwrap().is_empty() {
-                    if !Profile::does_exist(profile_name.as_ref().unwrap()) {
+                    if !store.exists(profile_name.as_ref().unwrap()) {
                         return Err(Error::ProfileDoesNotExist(
                             profile_name.as_ref().unwrap().to_string(),
                         ));
                     }
 
-                    let profile = load_profile!(profile_name.as_ref().unwrap(), get_userkey)?;
-                    check_expired_envs(&profile);
+                    let mut profile = load_profile(&store, profile_name.as_ref().unwrap())?;
+
+                    // The raw output is what gets loaded into the shell
+                    let usage = if *no_pretty_print {
+                        ProfileUsage::Consume
+                    } else {
+                        ProfileUsage::Inspect
+                    };
+                    cli::enforce_expiration_policy(&mut profile, usage, config)?;
 
                     if *no_pretty_print {
-                        for env in profile.envs {
-                            println!("{}={}", env.name, env.value);
+                        // Values are quoted so that the output can be evaluated
+                        // by a POSIX shell
+                        for env in &profile.envs {
+                            if !is_valid_name(&env.name) {
+                                eprintln!(
+                                    "{}: '{}' is not a valid name for a shell variable and was left out",
+                                    "Warning".yellow(),
+                                    env.name
+                                );
+                                continue;
+                            }
+
+                            println!(
+                                "{}={}",
+                                env.name,
+                                escape::sh(cli::display_value(env, reveal))
+                            );
                         }
                     } else {
-                        cli::list_envs(&profile, *display_comments, *display_expiration_date);
+                        cli::list_envs(
+                            &profile,
+                            *display_comments,
+                            *display_expiration_date,
+                            reveal,
+                        );
                     }
                 }
             }
@@ -526,8 +934,13 @@ impl Command {
                 update_values,
                 update_comments,
                 update_expiration_date,
+                expires,
+                sensitive,
+                tags,
             } => {
-                if !Profile::does_exist(profile_name) {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
                     return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
@@ -537,10 +950,18 @@ impl Command {
                     ));
                 }
 
-                let mut profile = load_profile!(profile_name, get_userkey)?;
-                check_expired_envs(&profile);
+                let expires = expires.as_deref().map(parse_expiration_date).transpose()?;
 
-                if !*update_values && !*update_comments && !*update_expiration_date {
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
+
+                if !*update_values
+                    && !*update_comments
+                    && !*update_expiration_date
+                    && expires.is_none()
+                    && sensitive.is_none()
+                    && tags.is_none()
+                {
                     return Err(Error::Msg(
                         "You must provide at least one flag to update".to_string(),
                     ));
@@ -557,7 +978,12 @@ impl Command {
                                 }
 
                                 if let Some(value) = parts.next() {
-                                    profile.edit_env(key.to_string(), value.to_string())?
+                                    let new_env = env_from_key_value(key, value)?;
+
+                                    if let Some(mut e) = profile.envs.get_mut(key) {
+                                        *e.value_mut() = new_env.value;
+                                        *e.encoding_mut() = new_env.encoding;
+                                    }
                                 } else {
                                     return Err(Error::Msg(format!(
                                         "Unable to parse value for key '{}'",
@@ -592,11 +1018,21 @@ impl Command {
                     }
                 }
 
-                for env in &mut profile.envs {
-                    if envs.iter().find(|&e| e.contains(&env.name)).is_none() {
+                let updated = env_names(envs);
+
+                for mut env in profile.envs.iter_mut() {
+                    if !updated.contains(&env.name) {
                         continue;
                     }
 
+                    if let Some(sensitive) = sensitive {
+                        *env.sensitive_mut() = *sensitive;
+                    }
+
+                    if let Some(tags) = tags {
+                        *env.tags_mut() = cli::normalize_tags(tags);
+                    }
+
                     if *update_comments {
                         let prompt =
                             Text::new(&format!("Enter a new comment for '{}':", env.name)).prompt();
@@ -604,11 +1040,13 @@ impl Command {
                         if let Err(e) = prompt {
                             return Err(Error::Msg(e.to_string()));
                         } else {
-                            env.comment = Some(prompt.unwrap());
+                            *env.comment_mut() = Some(prompt.unwrap());
                         }
                     }
 
-                    if *update_expiration_date {
+                    if expires.is_some() {
+                        *env.expiration_date_mut() = expires;
+                    } else if *update_expiration_date {
                         let prompt = DateSelect::new(&format!(
                             "Select a new expiration date for '{}':",
                             env.name
@@ -619,36 +1057,279 @@ impl Command {
                         if let Err(e) = prompt {
                             return Err(Error::Msg(e.to_string()));
                         } else {
-                            env.expiration_date = Some(prompt.unwrap());
+                            *env.expiration_date_mut() = Some(prompt.unwrap());
                         }
                     }
                 }
 
+                cli::check_schema(&profile)?;
+
                 println!("{}", "Applying Changes".green());
                 profile.push_changes()?;
             }
 
+            Command::Edit { profile_name } => {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let editor = config.editor().ok_or_else(|| {
+                    Error::Msg(
+                        "No editor found, set $VISUAL or $EDITOR or run `envio config set editor <EDITOR>`"
+                            .to_string(),
+                    )
+                })?;
+
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
+
+                let original = cli::edit_buffer(&profile);
+                let mut buffer = original.clone();
+
+                let old_envs = loop {
+                    buffer = cli::edit_in_editor(&editor, &buffer)?;
+
+                    if buffer == original {
+                        println!("{}", "No changes made".bold());
+                        return Ok(());
+                    }
+
+                    let result = parse_annotated_envs_from_string(&buffer).and_then(|mut envs| {
+                        // The encoding and the rotation time are not part of
+                        // the buffer, keep the ones the variables had before
+                        for mut env in envs.iter_mut() {
+                            if let Some(old_env) = profile.envs.get_env(&env.name) {
+                                *env.encoding_mut() = old_env.encoding;
+                                *env.rotated_at_mut() = old_env.rotated_at;
+                            }
+                        }
+
+                        let old_envs = std::mem::replace(&mut profile.envs, envs);
+
+                        if let Err(e) = cli::check_schema(&profile) {
+                            profile.envs = old_envs;
+                            return Err(e);
+                        }
+
+                        Ok(old_envs)
+                    });
+
+                    match result {
+                        Ok(old_envs) => break old_envs,
+                        Err(e) => {
+                            println!("{}: {}", "Error".red(), e);
+
+                            let edit_again = Confirm::new("Do you want to edit the profile again?")
+                                .with_default(true)
+                                .prompt()
+                                .map_err(|e| Error::Msg(e.to_string()))?;
+
+                            if !edit_again {
+                                return Err(e);
+                            }
+                        }
+                    }
+                };
+
+                if cli::print_envs_diff(&old_envs, &profile.envs) == 0 {
+                    println!("{}", "No changes made".bold());
+                    return Ok(());
+                }
+
+                let apply = Confirm::new("Apply these changes?")
+                    .with_default(true)
+                    .prompt()
+                    .map_err(|e| Error::Msg(e.to_string()))?;
+
+                if !apply {
+                    println!("{}", "No changes made".bold());
+                    return Ok(());
+                }
+
+                println!("{}", "Applying Changes".green());
+                profile.push_changes()?;
+            }
+
+            Command::Generate {
+                profile_name,
+                key,
+                secret_type,
+                length,
+                charset,
+                separator,
+                print,
+                expires,
+            } => {
+                let options = SecretOptions {
+                    secret_type: secret_type
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    length: *length,
+                    charset: charset.as_deref().map(str::parse).transpose()?,
+                    separator: separator.clone(),
+                };
+
+                let expires = expires.as_deref().map(parse_expiration_date).transpose()?;
+
+                // Generated before asking for the key so that invalid options
+                // are reported right away
+                let secret = generate_secret(&options)?;
+
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
+
+                if profile.envs.contains_key(key) {
+                    let replace = Confirm::new(&format!(
+                        "'{}' already exists, do you want to replace its value?",
+                        key
+                    ))
+                    .with_default(false)
+                    .prompt()
+                    .map_err(|e| Error::Msg(e.to_string()))?;
+
+                    if !replace {
+                        println!("{}", "No changes made".bold());
+                        return Ok(());
+                    }
+                }
+
+                let mut env = profile.envs.entry(key.to_string()).or_insert(String::new());
+                *env.value_mut() = secret.clone();
+                *env.encoding_mut() = ValueEncoding::Text;
+                *env.sensitive_mut() = true;
+
+                if expires.is_some() {
+                    *env.expiration_date_mut() = expires;
+                }
+
+                cli::check_schema(&profile)?;
+
+                println!("{}", "Applying Changes".green());
+                profile.push_changes()?;
+
+                if *print {
+                    println!("{}", secret);
+                }
+            }
+
+            Command::Rotate {
+                profile_name,
+                key,
+                command,
+                period,
+            } => {
+                let period = period.as_deref().or(config.rotation_period());
+                let expires = period.map(date_after_period).transpose()?;
+
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Inspect, config)?;
+
+                let current = match profile.envs.get_env(key) {
+                    Some(env) => env.decoded_value()?,
+                    None => return Err(Error::EnvDoesNotExist(key.to_string())),
+                };
+
+                let value = cli::run_rotation_command(command, &current).map_err(|e| {
+                    Error::Msg(format!("{}, the old value of '{}' was kept", e, key))
+                })?;
+
+                if value == current {
+                    return Err(Error::Msg(format!(
+                        "The rotation command printed the current value of '{}'",
+                        key
+                    )));
+                }
+
+                let rotated = Env::from_bytes(key.to_string(), &value);
+
+                let mut env = profile.envs.get_mut(key).unwrap(); // unwrap is safe here because we checked that the env exists
+                *env.value_mut() = rotated.value;
+                *env.encoding_mut() = rotated.encoding;
+                *env.rotated_at_mut() = Some(Utc::now());
+
+                if expires.is_some() {
+                    *env.expiration_date_mut() = expires;
+                }
+
+                println!("{}", "Applying Changes".green());
+
+                // The secret has already been rotated at this point, losing the
+                // new value would lock the user out
+                if let Err(e) = cli::check_schema(&profile).and_then(|_| profile.push_changes()) {
+                    eprintln!(
+                        "{}: The new value of '{}' could not be saved, store it yourself before it is lost:\n{}",
+                        "Error".red(),
+                        key,
+                        String::from_utf8_lossy(&value)
+                    );
+
+                    return Err(e);
+                }
+
+                match expires {
+                    Some(date) => println!(
+                        "{}: Rotated '{}', it now expires on {}",
+                        "Success".green(),
+                        key,
+                        date
+                    ),
+                    None => println!("{}: Rotated '{}'", "Success".green(), key),
+                }
+            }
+
             Command::Export {
                 profile_name,
                 file,
                 envs,
+                include_sensitive,
+                format,
             } => {
-                if !Profile::does_exist(profile_name) {
+                if !store.exists(profile_name) {
                     return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
                 }
 
+                let format = match format {
+                    Some(format) => format.parse()?,
+                    None => config.export_format(),
+                };
+
                 let mut file_name = ".env";
 
                 if file.is_some() {
                     file_name = file.as_ref().unwrap()
                 }
 
-                let profile = load_profile!(profile_name, get_userkey)?;
-                check_expired_envs(&profile);
+                let mut profile = load_profile(&store, profile_name)?;
+                cli::enforce_expiration_policy(&mut profile, ProfileUsage::Consume, config)?;
 
                 if envs.is_some() && envs.as_ref().unwrap().contains(&"select".to_string()) {
+                    // Sensitive environment variables are not selected by default
+                    let default_options = profile
+                        .envs
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, e)| *include_sensitive || !e.sensitive)
+                        .map(|(i, _)| i)
+                        .collect::<Vec<usize>>();
+
                     let prompt = MultiSelect::new("Select the environment variables you want to export:", profile.envs.keys())
-                        .with_default(&(0..profile.envs.len()).collect::<Vec<usize>>())
+                        .with_default(&default_options)
                         .with_vim_mode(vim_mode)
                         .with_help_message("↑↓ to move, space to select/unselect one, → to all, ← to none, type to filter, enter to confirm")
                         .prompt();
@@ -668,12 +1349,14 @@ impl Command {
                                 .map(|s| s.to_owned())
                                 .collect(),
                         ),
+                        *include_sensitive,
+                        format,
                     )?;
 
                     return Ok(());
                 }
 
-                cli::export_envs(&profile, file_name, envs)?;
+                cli::export_envs(&profile, file_name, envs, *include_sensitive, format)?;
             }
 
             Command::Import {
@@ -681,14 +1364,18 @@ impl Command {
                 file,
                 url,
             } => {
-                if Profile::does_exist(profile_name) {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if store.exists(profile_name) {
                     return Err(Error::ProfileExists(profile_name.to_string()));
                 }
 
                 if url.is_some() && Url::parse(url.as_ref().unwrap()).is_ok() {
                     cli::download_profile(
+                        &store,
                         url.as_ref().unwrap().to_string(),
                         profile_name.to_string(),
+                        |content| profile_from_bytes(profile_name, content),
                     )?;
 
                     return Ok(());
@@ -696,8 +1383,10 @@ impl Command {
 
                 if file.is_some() {
                     cli::import_profile(
+                        &store,
                         file.as_ref().unwrap().to_string(),
                         profile_name.to_string(),
+                        |content| profile_from_bytes(profile_name, content),
                     )?;
                     return Ok(());
                 }
@@ -707,6 +1396,236 @@ impl Command {
                 ));
             }
 
+            Command::Schema {
+                profile_name,
+                file,
+                remove,
+            } => {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let mut profile = load_profile(&store, profile_name)?;
+
+                if *remove {
+                    profile.schema = None;
+
+                    println!("{}", "Applying Changes".green());
+                    profile.push_changes()?;
+                } else if let Some(file) = file {
+                    let schema = cli::read_schema(file)?;
+
+                    for key in schema.apply_defaults(&mut profile.envs) {
+                        println!("Using the default value of '{}' from the schema", key);
+                    }
+
+                    profile.schema = Some(schema);
+                    cli::check_schema(&profile)?;
+
+                    println!("{}", "Applying Changes".green());
+                    profile.push_changes()?;
+                } else {
+                    match &profile.schema {
+                        Some(schema) => print!("{}", schema.to_toml()?),
+                        None => println!("{}", "Profile does not have a schema".bold()),
+                    }
+                }
+            }
+
+            Command::Validate { profile_name } => {
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let profile = load_profile(&store, profile_name)?;
+
+                if profile.schema.is_none() {
+                    return Err(Error::Msg(format!(
+                        "Profile `{}` does not have a schema",
+                        profile_name
+                    )));
+                }
+
+                cli::check_schema(&profile)?;
+                println!("{}: Profile matches its schema", "Success".green());
+            }
+
+            Command::Policy {
+                profile_name,
+                policy,
+            } => {
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let mut profile = load_profile(&store, profile_name)?;
+
+                match policy.as_deref() {
+                    None => {
+                        let source = if profile.expiration_policy.is_some() {
+                            "profile"
+                        } else {
+                            "global"
+                        };
+
+                        println!(
+                            "{} {} ({})",
+                            "Expiration policy".green(),
+                            cli::get_expiration_policy(&profile, config)?,
+                            source
+                        );
+                    }
+                    Some(policy) => {
+                        profile.expiration_policy = if policy == "default" {
+                            None
+                        } else {
+                            Some(policy.parse::<ExpirationPolicy>()?)
+                        };
+
+                        println!("{}", "Applying Changes".green());
+                        profile.push_changes()?;
+                    }
+                }
+            }
+
+            Command::Describe {
+                profile_name,
+                description,
+                owner,
+                notes,
+            } => {
+                // The metadata is stored in plain text, so there is no need
+                // to decrypt the profile
+                let _lock = lock_profile(&store, profile_name)?;
+
+                if !store.exists(profile_name) {
+                    return Err(Error::ProfileDoesNotExist(profile_name.to_string()));
+                }
+
+                let mut metadata = store.read_metadata(profile_name)?;
+
+                if description.is_none() && owner.is_none() && notes.is_none() {
+                    cli::display_metadata(profile_name, &metadata);
+                    return Ok(());
+                }
+
+                // An empty value clears the field
+                let new_value = |value: &String| Some(value.clone()).filter(|v| !v.is_empty());
+
+                if let Some(description) = description {
+                    metadata.description = new_value(description);
+                }
+
+                if let Some(owner) = owner {
+                    metadata.owner = new_value(owner);
+                }
+
+                if let Some(notes) = notes {
+                    metadata.notes = new_value(notes);
+                }
+
+                metadata.touch();
+                store.write_metadata(profile_name, &metadata)?;
+
+                println!(
+                    "{}: Updated the description of the profile",
+                    "Success".green()
+                );
+            }
+
+            Command::Expiring { within, exit_code } => {
+                let within_days = (parse_duration(within)?.as_secs() / (60 * 60 * 24)) as i64;
+
+                let mut expirations = Vec::new();
+                let mut failed = 0;
+
+                for profile_name in store.list()? {
+                    match expiration_dates(&store, &profile_name) {
+                        Ok(dates) => expirations.push((profile_name, dates)),
+                        Err(e) => {
+                            eprintln!(
+                                "{}: Failed to check profile '{}': {}",
+                                "Error".red(),
+                                profile_name,
+                                e
+                            );
+                            failed += 1;
+                        }
+                    }
+                }
+
+                let count = cli::list_expiring_envs(&expirations, within_days);
+
+                if *exit_code {
+                    if count > 0 {
+                        return Err(Error::Msg(format!(
+                            "{} environment variable(s) expire within {} day(s)",
+                            count, within_days
+                        )));
+                    }
+
+                    if failed > 0 {
+                        eprintln!(
+                            "{}: {} profile(s) could not be checked",
+                            "Error".red(),
+                            failed
+                        );
+                        std::process::exit(EXPIRING_UNCHECKED_EXIT_CODE);
+                    }
+                }
+            }
+
+            Command::Store { command } => match command {
+                Some(StoreCommand::Migrate { to }) => cli::migrate_store(to.parse()?)?,
+                None => {
+                    let kind = StoreKind::current()?;
+
+                    let location = match kind {
+                        StoreKind::Fs => get_datadir()?.join("profiles"),
+                        StoreKind::Sqlite => get_datadir()?.join(DATABASE_FILE_NAME),
+                    };
+
+                    println!("{} {} ({})", "Store".green(), kind, location.display());
+                }
+            },
+
+            Command::Config { command } => match command {
+                ConfigCommand::List => {
+                    for (key, description) in config::KEYS {
+                        match config.get(key)? {
+                            Some(value) => println!("{} = {}", key.bold(), value),
+                            None => println!(
+                                "{} = {}",
+                                key.bold(),
+                                format!("({})", description).dimmed()
+                            ),
+                        }
+                    }
+                }
+                ConfigCommand::Get { key } => match config.get(key)? {
+                    Some(value) => println!("{}", value),
+                    None => println!("{}", "Not set".bold()),
+                },
+                ConfigCommand::Set { key, value } => {
+                    Config::edit(key, Some(value))?;
+
+                    println!("{}: Set `{}` to `{}`", "Success".green(), key, value);
+                }
+                ConfigCommand::Unset { key } => {
+                    Config::edit(key, None)?;
+
+                    println!(
+                        "{}: Reset `{}` to its default value",
+                        "Success".green(),
+                        key
+                    );
+                }
+            },
+
             Command::Version { verbose } => {
                 if *verbose {
                     println!("{} {}", "Version".green(), env!("BUILD_VERSION"));
@@ -0,0 +1,551 @@
+/// The global configuration of the CLI, stored in the `config.toml` file of
+/// the config directory
+///
+/// Every setting is optional, settings that are not set fall back to the
+/// defaults below. The file can be edited by hand or with `envio config`.
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use envio::error::{Error, Result};
+use envio::utils::{get_configdir, parse_duration, write_atomic};
+use envio::ExpirationPolicy;
+
+use crate::shell::Shell;
+use crate::utils::date_after_period;
+
+/// Name of the config file inside of the config directory
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Minimum length of new passphrases if none is configured
+const DEFAULT_PASSPHRASE_MIN_LENGTH: usize = 8;
+
+/// How often to check for new versions if no interval is configured
+const DEFAULT_UPDATE_CHECK_INTERVAL: &str = "7d";
+
+/// Every key that can be used with `envio config get/set`, along with a
+/// description of its values
+pub const KEYS: &[(&str, &str)] = &[
+    (
+        "encryption.type",
+        "encryption type of new profiles, age or gpg",
+    ),
+    (
+        "encryption.gpg_key",
+        "fingerprint of the GPG key new profiles are encrypted for",
+    ),
+    (
+        "passphrase.min_length",
+        "minimum length of the passphrase of new age profiles",
+    ),
+    ("export.format", "format of exported files, dotenv, shell or docker"),
+    (
+        "expiration.policy",
+        "what to do with expired variables of profiles without a policy, warn, block or strip, overridden by $ENVIO_EXPIRATION_POLICY",
+    ),
+    (
+        "rotation.period",
+        "how far `envio rotate` pushes the expiration date forward, e.g. 90d or 6m",
+    ),
+    (
+        "display.reveal",
+        "whether to show the values of sensitive variables without --reveal",
+    ),
+    (
+        "update_check.enabled",
+        "whether to check for new versions of envio",
+    ),
+    (
+        "update_check.interval",
+        "how often to check for new versions, e.g. 12h or 7d",
+    ),
+    (
+        "shell",
+        "shell `envio env` generates code for, bash, zsh, fish, nu, pwsh, elvish, xonsh or tcsh, detected by default",
+    ),
+    ("editor", "editor to use instead of $VISUAL and $EDITOR"),
+];
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    #[serde(default, skip_serializing_if = "EncryptionConfig::is_empty")]
+    pub encryption: EncryptionConfig,
+    #[serde(default, skip_serializing_if = "PassphraseConfig::is_empty")]
+    pub passphrase: PassphraseConfig,
+    #[serde(default, skip_serializing_if = "ExportConfig::is_empty")]
+    pub export: ExportConfig,
+    #[serde(default, skip_serializing_if = "ExpirationConfig::is_empty")]
+    pub expiration: ExpirationConfig,
+    #[serde(default, skip_serializing_if = "RotationConfig::is_empty")]
+    pub rotation: RotationConfig,
+    #[serde(default, skip_serializing_if = "DisplayConfig::is_empty")]
+    pub display: DisplayConfig,
+    #[serde(default, skip_serializing_if = "UpdateCheckConfig::is_empty")]
+    pub update_check: UpdateCheckConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionConfig {
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub encryption_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpg_key: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PassphraseConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExportConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ExportFormat>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExpirationConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DisplayConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reveal: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateCheckConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+}
+
+impl EncryptionConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl PassphraseConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl ExportConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl ExpirationConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl RotationConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl DisplayConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl UpdateCheckConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The formats profiles can be exported in
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// `KEY=value` lines, as read by dotenv libraries
+    #[default]
+    Dotenv,
+    /// `export KEY='value'` lines, which can be sourced by POSIX shells
+    Shell,
+    /// `KEY=value` lines without quoting, as read by `docker run --env-file`
+    Docker,
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dotenv" => Ok(ExportFormat::Dotenv),
+            "shell" => Ok(ExportFormat::Shell),
+            "docker" => Ok(ExportFormat::Docker),
+            _ => Err(Error::Msg(format!(
+                "Invalid export format: `{}`, expected one of dotenv, shell or docker",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Dotenv => write!(f, "dotenv"),
+            ExportFormat::Shell => write!(f, "shell"),
+            ExportFormat::Docker => write!(f, "docker"),
+        }
+    }
+}
+
+impl Config {
+    /// Get the path of the config file
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(get_configdir()?.join(CONFIG_FILE_NAME))
+    }
+
+    /// Read the config file
+    ///
+    /// # Returns
+    /// - `Result<Config>`: the config, the defaults if there is no config file
+    pub fn load() -> Result<Config> {
+        let path = Config::file_path()?;
+
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let invalid = |e: &dyn fmt::Display| {
+            Error::Msg(format!(
+                "Invalid config file `{}`: {}\nFix it with `envio config set/unset` or by editing it",
+                path.display(),
+                e.to_string().trim_end()
+            ))
+        };
+
+        let config: Config =
+            toml::from_str(&std::fs::read_to_string(&path)?).map_err(|e| invalid(&e))?;
+
+        config.validate().map_err(|e| invalid(&e))?;
+
+        Ok(config)
+    }
+
+    /// Change a setting in the config file without loading the other settings
+    ///
+    /// This also works if the rest of the file is invalid, which is how
+    /// `envio config set/unset` repair it. Keys that are not known, e.g.
+    /// misspelled ones, can be unset too.
+    ///
+    /// # Parameters
+    /// - `key` - the key of the setting, see [KEYS]
+    /// - `value` - the new value, `None` to go back to the default
+    ///
+    /// # Returns
+    /// - `Result<()>`: an error if the value is invalid or the file is not
+    ///   valid TOML
+    pub fn edit(key: &str, value: Option<&str>) -> Result<()> {
+        let path = Config::file_path()?;
+
+        let mut table: toml::Table = if path.exists() {
+            toml::from_str(&std::fs::read_to_string(&path)?).map_err(|e| {
+                Error::Msg(format!(
+                    "Invalid config file `{}`: {}\nIt has to be fixed by editing it",
+                    path.display(),
+                    e.to_string().trim_end()
+                ))
+            })?
+        } else {
+            toml::Table::new()
+        };
+
+        let (section, name) = match key.split_once('.') {
+            Some((section, name)) => (Some(section), name),
+            None => (None, key),
+        };
+
+        match value {
+            Some(value) => {
+                // The value is checked and converted by a config that only has
+                // this setting
+                let mut single = Config::default();
+                single.set(key, Some(value))?;
+
+                let single = toml::Table::try_from(&single)
+                    .map_err(|e| Error::Serialization(e.to_string()))?;
+                let value = lookup(&single, section, name)
+                    .cloned()
+                    .ok_or_else(|| unknown_key(key))?;
+
+                let table = match section {
+                    Some(section) => table
+                        .entry(section)
+                        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                        .as_table_mut()
+                        .ok_or_else(|| {
+                            Error::Msg(format!("`{}` is not a section of the config", section))
+                        })?,
+                    None => &mut table,
+                };
+
+                table.insert(name.to_string(), value);
+            }
+            None => {
+                let removed = match section {
+                    Some(section) => {
+                        let removed = table
+                            .get_mut(section)
+                            .and_then(toml::Value::as_table_mut)
+                            .and_then(|t| t.remove(name));
+
+                        if table
+                            .get(section)
+                            .and_then(toml::Value::as_table)
+                            .is_some_and(toml::Table::is_empty)
+                        {
+                            table.remove(section);
+                        }
+
+                        removed
+                    }
+                    None => table.remove(name),
+                };
+
+                if removed.is_none() {
+                    // Unsetting a known key that is not set is not an error
+                    Config::default().get(key)?;
+                }
+            }
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let content = toml::to_string(&table).map_err(|e| Error::Serialization(e.to_string()))?;
+
+        write_atomic(&path, content.as_bytes())
+    }
+
+    /// Check the values that are not checked when the file is parsed
+    fn validate(&self) -> Result<()> {
+        if let Some(encryption_type) = &self.encryption.encryption_type {
+            if encryption_type != "age" && encryption_type != "gpg" {
+                return Err(Error::InvalidEncryptionType(encryption_type.to_string()));
+            }
+        }
+
+        if let Some(policy) = &self.expiration.policy {
+            policy.parse::<ExpirationPolicy>()?;
+        }
+
+        if let Some(period) = &self.rotation.period {
+            date_after_period(period)?;
+        }
+
+        if let Some(interval) = &self.update_check.interval {
+            parse_duration(interval)?;
+        }
+
+        if let Some(shell) = &self.shell {
+            shell.parse::<Shell>()?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the value of a setting
+    ///
+    /// # Parameters
+    /// - `key` - the key of the setting, see [KEYS]
+    ///
+    /// # Returns
+    /// - `Result<Option<String>>`: the value or `None` if it is not set
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let value = match key {
+            "encryption.type" => self.encryption.encryption_type.clone(),
+            "encryption.gpg_key" => self.encryption.gpg_key.clone(),
+            "passphrase.min_length" => self.passphrase.min_length.map(|v| v.to_string()),
+            "export.format" => self.export.format.map(|v| v.to_string()),
+            "expiration.policy" => self.expiration.policy.clone(),
+            "rotation.period" => self.rotation.period.clone(),
+            "display.reveal" => self.display.reveal.map(|v| v.to_string()),
+            "update_check.enabled" => self.update_check.enabled.map(|v| v.to_string()),
+            "update_check.interval" => self.update_check.interval.clone(),
+            "shell" => self.shell.clone(),
+            "editor" => self.editor.clone(),
+            _ => return Err(unknown_key(key)),
+        };
+
+        Ok(value)
+    }
+
+    /// Change the value of a setting
+    ///
+    /// # Parameters
+    /// - `key` - the key of the setting, see [KEYS]
+    /// - `value` - the new value, `None` to go back to the default
+    ///
+    /// # Returns
+    /// - `Result<()>`: an error if the key is unknown or the value is invalid
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        let string = || value.map(str::to_string);
+
+        match key {
+            "encryption.type" => self.encryption.encryption_type = string(),
+            "encryption.gpg_key" => self.encryption.gpg_key = string(),
+            "passphrase.min_length" => {
+                self.passphrase.min_length = value.map(|v| parse_value(key, v)).transpose()?
+            }
+            "export.format" => self.export.format = value.map(str::parse).transpose()?,
+            "expiration.policy" => {
+                self.expiration.policy = value
+                    .map(|v| v.parse::<ExpirationPolicy>().map(|p| p.to_string()))
+                    .transpose()?
+            }
+            "rotation.period" => self.rotation.period = string(),
+            "display.reveal" => {
+                self.display.reveal = value.map(|v| parse_value(key, v)).transpose()?
+            }
+            "update_check.enabled" => {
+                self.update_check.enabled = value.map(|v| parse_value(key, v)).transpose()?
+            }
+            "update_check.interval" => self.update_check.interval = string(),
+            "shell" => self.shell = string(),
+            "editor" => self.editor = string(),
+            _ => return Err(unknown_key(key)),
+        }
+
+        self.validate()
+    }
+
+    /// Get the encryption type of new profiles, `age` by default
+    pub fn encryption_type(&self) -> &str {
+        self.encryption.encryption_type.as_deref().unwrap_or("age")
+    }
+
+    /// Get the minimum length of the passphrase of new profiles
+    pub fn passphrase_min_length(&self) -> usize {
+        self.passphrase
+            .min_length
+            .unwrap_or(DEFAULT_PASSPHRASE_MIN_LENGTH)
+    }
+
+    /// Get the format profiles are exported in
+    pub fn export_format(&self) -> ExportFormat {
+        self.export.format.unwrap_or_default()
+    }
+
+    /// Get the expiration policy of profiles that do not have their own
+    ///
+    /// The `ENVIO_EXPIRATION_POLICY` environment variable takes precedence
+    /// over the config, the default is to warn.
+    ///
+    /// # Returns
+    /// - `Result<ExpirationPolicy>`: the policy, an error if the environment
+    ///   variable is not a valid policy
+    pub fn expiration_policy(&self) -> Result<ExpirationPolicy> {
+        match std::env::var("ENVIO_EXPIRATION_POLICY") {
+            Ok(policy) if !policy.is_empty() => policy.parse(),
+            // The policy of the config is validated when the config is loaded
+            _ => Ok(self
+                .expiration
+                .policy
+                .as_deref()
+                .and_then(|policy| policy.parse().ok())
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Get how far `envio rotate` pushes the expiration date of a rotated
+    /// environment variable forward, `None` to leave it as it is
+    pub fn rotation_period(&self) -> Option<&str> {
+        self.rotation.period.as_deref()
+    }
+
+    /// Whether to show the values of sensitive environment variables by
+    /// default
+    pub fn reveal(&self) -> bool {
+        self.display.reveal.unwrap_or(false)
+    }
+
+    /// Get how often to check for new versions, `None` if the check is
+    /// disabled
+    pub fn update_check_interval(&self) -> Option<Duration> {
+        if !self.update_check.enabled.unwrap_or(true) {
+            return None;
+        }
+
+        let interval = self
+            .update_check
+            .interval
+            .as_deref()
+            .unwrap_or(DEFAULT_UPDATE_CHECK_INTERVAL);
+
+        // The interval is validated when the config is loaded
+        parse_duration(interval).ok()
+    }
+
+    /// Get the editor the user wants to use, from the config or from the
+    /// `VISUAL` and `EDITOR` environment variables
+    pub fn editor(&self) -> Option<String> {
+        self.editor
+            .clone()
+            .or_else(|| std::env::var("VISUAL").ok())
+            .or_else(|| std::env::var("EDITOR").ok())
+            .filter(|editor| !editor.trim().is_empty())
+    }
+}
+
+/// Get a value of a config table, `section` is `None` for top level keys
+fn lookup<'a>(
+    table: &'a toml::Table,
+    section: Option<&str>,
+    name: &str,
+) -> Option<&'a toml::Value> {
+    match section {
+        Some(section) => table.get(section)?.as_table()?.get(name),
+        None => table.get(name),
+    }
+}
+
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::Msg(format!("Invalid value for `{}`: `{}`", key, value)))
+}
+
+fn unknown_key(key: &str) -> Error {
+    Error::Msg(format!(
+        "Unknown config key: `{}`, run `envio config list` to see all keys",
+        key
+    ))
+}
@@ -0,0 +1,221 @@
+/// Generation of random secrets for `envio generate`
+///
+/// Every secret is generated with the random number generator of the
+/// operating system, which is suitable for cryptographic use.
+use std::fmt;
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::{rngs::OsRng, seq::SliceRandom, Rng, RngCore};
+
+use envio::error::{Error, Result};
+
+/// Word list used for passphrases, one word per line
+///
+/// It has 1437 words, so every word adds about 10.5 bits of entropy.
+const WORDS: &str = include_str!("words.txt");
+
+const LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+/// Punctuation added by [Charset::Symbols], without quotes, backslashes and
+/// brackets so that secrets are easy to copy around
+const SYMBOLS: &str = "!#%+-.:=@^_~";
+
+/// The kinds of secrets that can be generated
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SecretType {
+    /// Random characters from a [Charset]
+    #[default]
+    Chars,
+    /// Random bytes encoded as lowercase hex
+    Hex,
+    /// Random bytes encoded as base64
+    Base64,
+    /// A random (version 4) UUID
+    Uuid,
+    /// Random words from a word list, 8 words (about 84 bits of entropy) by
+    /// default
+    Words,
+}
+
+impl SecretType {
+    /// Get the length that is used if none is given, in characters, bytes or
+    /// words depending on the type
+    fn default_length(&self) -> usize {
+        match self {
+            SecretType::Chars | SecretType::Hex | SecretType::Base64 => 32,
+            SecretType::Uuid => 16,
+            SecretType::Words => 8,
+        }
+    }
+}
+
+impl FromStr for SecretType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "chars" => Ok(SecretType::Chars),
+            "hex" => Ok(SecretType::Hex),
+            "base64" => Ok(SecretType::Base64),
+            "uuid" => Ok(SecretType::Uuid),
+            "words" => Ok(SecretType::Words),
+            _ => Err(Error::Msg(format!(
+                "Invalid secret type: `{}`, expected one of chars, hex, base64, uuid or words",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for SecretType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretType::Chars => write!(f, "chars"),
+            SecretType::Hex => write!(f, "hex"),
+            SecretType::Base64 => write!(f, "base64"),
+            SecretType::Uuid => write!(f, "uuid"),
+            SecretType::Words => write!(f, "words"),
+        }
+    }
+}
+
+/// The characters a secret of type [SecretType::Chars] is made of
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Charset {
+    /// Letters and digits
+    #[default]
+    Alphanumeric,
+    /// Letters only
+    Letters,
+    /// Digits only
+    Digits,
+    /// Letters, digits and a few symbols
+    Symbols,
+}
+
+impl Charset {
+    fn chars(&self) -> Vec<char> {
+        let chars = match self {
+            Charset::Alphanumeric => format!("{}{}", LETTERS, DIGITS),
+            Charset::Letters => LETTERS.to_string(),
+            Charset::Digits => DIGITS.to_string(),
+            Charset::Symbols => format!("{}{}{}", LETTERS, DIGITS, SYMBOLS),
+        };
+
+        chars.chars().collect()
+    }
+}
+
+impl FromStr for Charset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "alphanumeric" => Ok(Charset::Alphanumeric),
+            "letters" => Ok(Charset::Letters),
+            "digits" => Ok(Charset::Digits),
+            "symbols" => Ok(Charset::Symbols),
+            _ => Err(Error::Msg(format!(
+                "Invalid charset: `{}`, expected one of alphanumeric, letters, digits or symbols",
+                s
+            ))),
+        }
+    }
+}
+
+/// Options for [generate_secret]
+#[derive(Debug, Default, Clone)]
+pub struct SecretOptions {
+    pub secret_type: SecretType,
+    /// Number of characters, bytes or words, depending on the type
+    pub length: Option<usize>,
+    /// Only used by [SecretType::Chars]
+    pub charset: Option<Charset>,
+    /// Only used by [SecretType::Words], `-` by default
+    pub separator: Option<String>,
+}
+
+/// Generate a random secret
+///
+/// # Parameters
+/// - `options` - what kind of secret to generate
+///
+/// # Returns
+/// - `Result<String>`: the secret, an error if the options do not make sense
+///   for the type of secret
+pub fn generate_secret(options: &SecretOptions) -> Result<String> {
+    let secret_type = options.secret_type;
+    let length = options.length.unwrap_or(secret_type.default_length());
+
+    if length == 0 {
+        return Err(Error::Msg("The length must be at least 1".to_string()));
+    }
+
+    if options.charset.is_some() && secret_type != SecretType::Chars {
+        return Err(Error::Msg(format!(
+            "A charset can not be used with secrets of type {}",
+            secret_type
+        )));
+    }
+
+    if options.separator.is_some() && secret_type != SecretType::Words {
+        return Err(Error::Msg(format!(
+            "A separator can not be used with secrets of type {}",
+            secret_type
+        )));
+    }
+
+    if options.length.is_some() && secret_type == SecretType::Uuid {
+        return Err(Error::Msg("UUIDs always have the same length".to_string()));
+    }
+
+    let secret = match secret_type {
+        SecretType::Chars => {
+            let chars = options.charset.unwrap_or_default().chars();
+
+            (0..length)
+                .map(|_| chars[OsRng.gen_range(0..chars.len())])
+                .collect()
+        }
+        SecretType::Hex => random_bytes(length)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+        SecretType::Base64 => BASE64.encode(random_bytes(length)),
+        SecretType::Uuid => {
+            let mut bytes = random_bytes(length);
+
+            // Set the version (4) and the variant (RFC 4122)
+            bytes[6] = (bytes[6] & 0x0f) | 0x40;
+            bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[0..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..32]
+            )
+        }
+        SecretType::Words => {
+            let words: Vec<&str> = WORDS.lines().collect();
+
+            (0..length)
+                .map(|_| *words.choose(&mut OsRng).unwrap()) // unwrap is safe here because the word list is not empty
+                .collect::<Vec<_>>()
+                .join(options.separator.as_deref().unwrap_or("-"))
+        }
+    };
+
+    Ok(secret)
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
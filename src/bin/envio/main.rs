@@ -1,6 +1,10 @@
 mod clap_app;
 mod cli;
 mod commands;
+mod config;
+mod generate;
+mod project;
+mod shell;
 mod utils;
 mod version;
 
@@ -9,6 +13,7 @@ use colored::Colorize;
 use semver::Version;
 
 use clap_app::ClapApp;
+use config::Config;
 use version::get_latest_version;
 
 #[cfg(target_family = "unix")]
@@ -17,33 +22,54 @@ use utils::initalize_config;
 fn main() {
     color_eyre::install().unwrap();
 
-    let latest_version = get_latest_version();
+    let args = ClapApp::parse();
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) if args.command.runs_without_config() => {
+            eprintln!("{}: {}", "Warning".yellow(), e);
+            Config::default()
+        }
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(interval) = config.update_check_interval() {
+        if args.command.checks_for_updates() {
+            check_for_updates(interval);
+        }
+    }
+
+    #[cfg(target_family = "unix")]
+    if let Err(e) = initalize_config() {
+        eprintln!("{}: {}", "Error".red(), e);
+    }
+
+    if let Err(e) = args.command.run(&config) {
+        eprintln!("{}: {}", "Error".red(), e);
+        std::process::exit(1);
+    }
+}
+
+/// Tell the user if a newer version of envio is available
+fn check_for_updates(interval: std::time::Duration) {
+    let latest_version = get_latest_version(interval);
 
     let current_version = if let Ok(val) = Version::parse(env!("BUILD_VERSION")) {
         val
     } else {
-        println!("{}: Failed to parse current version", "Error".red());
+        eprintln!("{}: Failed to parse current version", "Error".red());
         "0.0.0".parse().unwrap()
     };
 
     if latest_version > current_version {
-        println!(
+        eprintln!(
             "{}: {} -> {}",
             "New version available".yellow(),
             current_version,
             latest_version
         );
     }
-
-    let args = ClapApp::parse();
-
-    #[cfg(target_family = "unix")]
-    if let Err(e) = initalize_config() {
-        println!("{}: {}", "Error".red(), e);
-    }
-
-    if let Err(e) = args.command.run() {
-        println!("{}: {}", "Error".red(), e);
-        std::process::exit(1);
-    }
 }
@@ -0,0 +1,183 @@
+/// Project files which load a profile while the shell is inside of a
+/// directory
+///
+/// A `.envio` file names the profile of a project and optionally the keys to
+/// load from it:
+///
+/// ```toml
+/// profile = "my-project"
+/// keys = ["DATABASE_URL", "REDIS_URL"]
+/// ```
+///
+/// The file is usually checked in, so it is only loaded after the user allowed
+/// it with `envio allow`. The allowance is bound to the location and the
+/// content of the file, any change to the file revokes it.
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use envio::error::{Error, Result};
+use envio::utils::{get_datadir, write_atomic};
+
+/// Name of the project file
+pub const PROJECT_FILE_NAME: &str = ".envio";
+
+/// Name of the directory in the data directory that holds a record for every
+/// allowed project file
+const ALLOWED_DIR_NAME: &str = "allowed";
+
+/// Content of a project file
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectSettings {
+    /// Name of the profile to load
+    pub profile: String,
+    /// Keys to load from the profile, all of them if `None`
+    #[serde(default)]
+    pub keys: Option<Vec<String>>,
+}
+
+/// A project file found on disk
+pub struct Project {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    content: String,
+    hash: String,
+}
+
+impl Project {
+    /// Find the project file of a directory, looking in its parents if it does
+    /// not have one
+    ///
+    /// # Parameters
+    /// - `dir` - the directory to start in
+    ///
+    /// # Returns
+    /// - `Option<PathBuf>`: the path of the project file, if there is one
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(PROJECT_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// Read the project file at `path`, or the one of the current directory if
+    /// no path is given
+    ///
+    /// # Parameters
+    /// - `path` - the path of the project file or of the directory it is in
+    ///
+    /// # Returns
+    /// - `Result<Project>`: the project file
+    pub fn locate(path: Option<&str>) -> Result<Project> {
+        if let Some(path) = path {
+            return Project::open(Path::new(path));
+        }
+
+        match Project::find(&std::env::current_dir()?) {
+            Some(path) => Project::open(&path),
+            None => Err(Error::Msg(format!(
+                "No {} file found in the current directory or its parents",
+                PROJECT_FILE_NAME
+            ))),
+        }
+    }
+
+    /// Read a project file
+    ///
+    /// # Parameters
+    /// - `path` - the path of the project file or of the directory it is in
+    ///
+    /// # Returns
+    /// - `Result<Project>`: the project file, its content is not validated yet
+    ///   see [settings](Project::settings)
+    pub fn open(path: &Path) -> Result<Project> {
+        let path = if path.is_dir() {
+            path.join(PROJECT_FILE_NAME)
+        } else {
+            path.to_path_buf()
+        };
+
+        let path = path.canonicalize().map_err(|e| {
+            Error::Msg(format!(
+                "Failed to open project file `{}`: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let content = std::fs::read_to_string(&path)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(b"\n");
+        hasher.update(content.as_bytes());
+
+        Ok(Project {
+            hash: to_hex(&hasher.finalize()),
+            path,
+            content,
+        })
+    }
+
+    /// Get the hash of the location and the content of the file, which
+    /// changes whenever the file is moved or edited
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Parse the content of the file
+    ///
+    /// # Returns
+    /// - `Result<ProjectSettings>`: the settings, an error if the file is not
+    ///   valid
+    pub fn settings(&self) -> Result<ProjectSettings> {
+        toml::from_str(&self.content).map_err(|e| {
+            Error::Msg(format!(
+                "Invalid project file `{}`: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Check whether the user allowed the file in its current state
+    pub fn is_allowed(&self) -> Result<bool> {
+        match std::fs::read_to_string(self.record_path()?) {
+            Ok(hash) => Ok(hash.trim() == self.hash),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Allow the file to be loaded until it changes
+    pub fn allow(&self) -> Result<()> {
+        let record_path = self.record_path()?;
+
+        if let Some(dir) = record_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        write_atomic(&record_path, format!("{}\n", self.hash).as_bytes())
+    }
+
+    /// Revoke the allowance of the file
+    pub fn deny(&self) -> Result<()> {
+        match std::fs::remove_file(self.record_path()?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the path of the record of the file, which is named after the hash
+    /// of its location so that there is at most one per project file
+    fn record_path(&self) -> Result<PathBuf> {
+        let name = to_hex(&Sha256::digest(self.path.to_string_lossy().as_bytes()));
+
+        Ok(get_datadir()?.join(ALLOWED_DIR_NAME).join(name))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
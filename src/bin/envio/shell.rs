@@ -0,0 +1,509 @@
+/// Integration with the shells profiles are loaded into
+///
+/// A program can not change the environment of the shell that started it, so
+/// `envio env` prints shell code that does it instead and the functions
+/// printed by `envio hook` evaluate that code whenever `envio load` or
+/// `envio unload` is run. The hook also runs `envio hook-env` before every
+/// prompt, which loads the profile of the project the shell is in, see
+/// [project](crate::project).
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use colored::Colorize;
+use envio::error::{Error, Result};
+use envio::escape;
+
+/// Environment variable holding the keys loaded by `envio load`, separated by
+/// colons, so that `envio unload` knows what to remove
+pub const LOADED_KEYS_ENV: &str = "ENVIO_LOADED_KEYS";
+
+/// Environment variable holding the name of the profile loaded by
+/// `envio load`
+pub const LOADED_PROFILE_ENV: &str = "ENVIO_LOADED_PROFILE";
+
+/// Environment variable holding the hash of the project file the prompt hook
+/// handled last, see [Project::hash](crate::project::Project::hash)
+pub const PROJECT_HASH_ENV: &str = "ENVIO_PROJECT_HASH";
+
+/// Environment variable holding what the prompt hook did with the project
+/// file, one of `loaded`, `blocked` or `failed`
+pub const PROJECT_STATE_ENV: &str = "ENVIO_PROJECT_STATE";
+
+/// Environment variable holding the keys loaded from the project file and the
+/// values they had before, restored when the project is left. Entries are
+/// separated by colons, `KEY=<base64 value>` for a key that was set and `KEY`
+/// for one that was not.
+pub const PROJECT_SAVED_ENV: &str = "ENVIO_PROJECT_SAVED";
+
+/// Names of the supported shells, for messages
+pub const SUPPORTED_SHELLS: &str = "bash, zsh, fish, nu, pwsh, elvish, xonsh or tcsh";
+
+/// The shells envio can generate code for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Nu,
+    Pwsh,
+    Elvish,
+    Xonsh,
+    /// Also used for csh
+    Tcsh,
+}
+
+/// A change to the environment of the shell
+///
+/// Names must be valid, see [is_valid_name]
+pub enum EnvChange<'a> {
+    /// Set the variable to the value
+    Set(&'a str, &'a str),
+    /// Remove the variable
+    Unset(&'a str),
+}
+
+impl Shell {
+    /// Get the shell to generate code for
+    ///
+    /// Without a name the shell is detected from the process that started
+    /// envio, which is the shell itself when its output is evaluated, and
+    /// from `$SHELL` if that fails. Only the parent process tells apart a
+    /// shell started from another one, e.g. nu started from bash.
+    ///
+    /// # Parameters
+    /// - `shell` - the name or path of the shell, detected if it is `None`
+    ///
+    /// # Returns
+    /// - `Result<Shell>`: the shell, an error if it is not supported
+    pub fn detect(shell: Option<&str>) -> Result<Shell> {
+        if let Some(shell) = shell {
+            return Shell::from_path(shell);
+        }
+
+        if let Some(shell) = Shell::parent() {
+            return Ok(shell);
+        }
+
+        let shell = std::env::var("SHELL").map_err(|_| {
+            Error::Msg("Failed to detect your shell, pass it with --shell".to_string())
+        })?;
+
+        Shell::from_path(&shell)
+    }
+
+    /// Parse the name or path of a shell, e.g. `/usr/bin/zsh`, `-bash` for a
+    /// login shell or `pwsh.exe`
+    fn from_path(shell: &str) -> Result<Shell> {
+        let name = Path::new(shell)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(shell);
+
+        let name = name.trim_start_matches('-');
+        let name = name.strip_suffix(".exe").unwrap_or(name);
+
+        name.parse()
+    }
+
+    /// Get the shell that started envio, if it is a supported one
+    #[cfg(target_os = "linux")]
+    fn parent() -> Option<Shell> {
+        let parent_id = std::os::unix::process::parent_id();
+        let name = std::fs::read_to_string(format!("/proc/{}/comm", parent_id)).ok()?;
+
+        Shell::from_path(name.trim()).ok()
+    }
+
+    /// Get the shell that started envio, if it is a supported one
+    ///
+    /// There is no procfs on macOS and the BSDs, `ps` is asked instead. Its
+    /// `-o comm=` output is POSIX, some systems print the full path.
+    #[cfg(all(unix, not(target_os = "linux")))]
+    fn parent() -> Option<Shell> {
+        let parent_id = std::os::unix::process::parent_id();
+        let output = std::process::Command::new("ps")
+            .args(["-o", "comm=", "-p", &parent_id.to_string()])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        Shell::from_path(String::from_utf8(output.stdout).ok()?.trim()).ok()
+    }
+
+    #[cfg(not(unix))]
+    fn parent() -> Option<Shell> {
+        None
+    }
+
+    /// Get the instructions for setting up the hook
+    pub fn setup(&self) -> &'static str {
+        match self {
+            Shell::Bash => "add `eval \"$(envio hook bash)\"` to ~/.bashrc",
+            Shell::Zsh => "add `eval \"$(envio hook zsh)\"` to ~/.zshrc",
+            Shell::Fish => "add `envio hook fish | source` to ~/.config/fish/config.fish",
+            Shell::Nu => {
+                "run `envio hook nu | save --force ~/.config/nushell/envio.nu` and add \
+                 `source ~/.config/nushell/envio.nu` to ~/.config/nushell/config.nu"
+            }
+            Shell::Pwsh => "add `Invoke-Expression (& envio hook pwsh | Out-String)` to $PROFILE",
+            Shell::Elvish => "add `eval (envio hook elvish | slurp)` to ~/.config/elvish/rc.elv",
+            Shell::Xonsh => "add `execx($(envio hook xonsh))` to ~/.xonshrc",
+            Shell::Tcsh => {
+                "run `envio hook tcsh > ~/.envio.tcsh` and add `source ~/.envio.tcsh` to \
+                 ~/.tcshrc"
+            }
+        }
+    }
+
+    /// Check whether the shell can be given `value`
+    ///
+    /// Only tcsh has values it can not represent, see
+    /// [escape::tcsh]
+    pub fn supports_value(&self, value: &str) -> bool {
+        match self {
+            Shell::Tcsh => escape::tcsh(value).is_ok(),
+            _ => true,
+        }
+    }
+
+    /// Get the code that applies `changes` to the environment
+    ///
+    /// Values that are not supported by the shell, see
+    /// [supports_value](Shell::supports_value), are left out with a warning
+    pub fn script(&self, changes: &[EnvChange]) -> String {
+        if *self == Shell::Nu {
+            // Nushell can not evaluate code, so it gets a record that is
+            // applied by the hook, `null` removes a variable
+            let fields: Vec<String> = changes
+                .iter()
+                .map(|change| match change {
+                    EnvChange::Set(name, value) => {
+                        format!("{}: {}", escape::json(name), escape::json(value))
+                    }
+                    EnvChange::Unset(name) => format!("{}: null", escape::json(name)),
+                })
+                .collect();
+
+            return format!("{{{}}}\n", fields.join(", "));
+        }
+
+        let statements: Vec<String> = changes
+            .iter()
+            .filter_map(|change| self.statement(change))
+            .collect();
+
+        match self {
+            // tcsh can only evaluate a single line
+            Shell::Tcsh => format!("{}\n", statements.join(" ")),
+            _ => statements.iter().map(|s| format!("{}\n", s)).collect(),
+        }
+    }
+
+    fn statement(&self, change: &EnvChange) -> Option<String> {
+        let statement = match (self, change) {
+            (Shell::Bash | Shell::Zsh, EnvChange::Set(name, value)) => {
+                format!("export {}={};", name, escape::sh(value))
+            }
+            (Shell::Bash | Shell::Zsh, EnvChange::Unset(name)) => format!("unset {};", name),
+            (Shell::Fish, EnvChange::Set(name, value)) => {
+                format!("set -gx {} {};", name, escape::fish(value))
+            }
+            (Shell::Fish, EnvChange::Unset(name)) => format!("set -e {};", name),
+            (Shell::Pwsh, EnvChange::Set(name, value)) => {
+                format!("$env:{} = {}", name, escape::pwsh(value))
+            }
+            (Shell::Pwsh, EnvChange::Unset(name)) => {
+                format!(
+                    "Remove-Item -Path Env:{} -ErrorAction SilentlyContinue",
+                    name
+                )
+            }
+            (Shell::Elvish, EnvChange::Set(name, value)) => {
+                format!("set-env {} {}", name, escape::elvish(value))
+            }
+            (Shell::Elvish, EnvChange::Unset(name)) => format!("unset-env {}", name),
+            (Shell::Xonsh, EnvChange::Set(name, value)) => {
+                format!("${{...}}['{}'] = {}", name, escape::python(value))
+            }
+            (Shell::Xonsh, EnvChange::Unset(name)) => format!("${{...}}.pop('{}', None)", name),
+            (Shell::Tcsh, EnvChange::Set(name, value)) => match escape::tcsh(value) {
+                Ok(value) => format!("setenv {} {};", name, value),
+                Err(_) => {
+                    eprintln!(
+                        "{}: The value of '{}' can not be passed to {} and was left out",
+                        "Warning".yellow(),
+                        name,
+                        self
+                    );
+                    return None;
+                }
+            },
+            (Shell::Tcsh, EnvChange::Unset(name)) => format!("unsetenv {};", name),
+            // Handled by script
+            (Shell::Nu, _) => String::new(),
+        };
+
+        Some(statement)
+    }
+
+    /// Get the functions that make `envio load` and `envio unload` change the
+    /// environment of the current shell, and the prompt hook that loads the
+    /// profile of the current project
+    pub fn hook(&self) -> String {
+        let prompt_hook = match self {
+            Shell::Bash => {
+                r#"
+_envio_hook() {
+    local previous_exit_status=$?
+    local __envio_code
+    __envio_code="$(command envio hook-env --shell bash)" && eval "$__envio_code"
+    return $previous_exit_status
+}
+
+if [[ ";${PROMPT_COMMAND[*]:-};" != *";_envio_hook;"* ]]; then
+    if [[ "$(declare -p PROMPT_COMMAND 2>&1)" == "declare -a"* ]]; then
+        PROMPT_COMMAND=(_envio_hook "${PROMPT_COMMAND[@]}")
+    else
+        PROMPT_COMMAND="_envio_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+    fi
+fi
+"#
+            }
+            Shell::Zsh => {
+                r#"
+_envio_hook() {
+    local __envio_code
+    __envio_code="$(command envio hook-env --shell zsh)" && eval "$__envio_code"
+}
+
+typeset -ag precmd_functions
+if (( ! ${precmd_functions[(I)_envio_hook]} )); then
+    precmd_functions=(_envio_hook $precmd_functions)
+fi
+"#
+            }
+            Shell::Fish => {
+                r#"
+function __envio_hook --on-event fish_prompt
+    set -l __envio_code (command envio hook-env --shell fish | string collect)
+    and eval $__envio_code
+end
+"#
+            }
+            Shell::Nu => {
+                r#"
+$env.config.hooks.pre_prompt = (($env.config.hooks.pre_prompt? | default []) | append {||
+    __envio_apply (^envio hook-env --shell nu)
+})
+"#
+            }
+            Shell::Pwsh => {
+                r#"
+if (-not $global:__envio_prompt) {
+    $global:__envio_prompt = $function:prompt
+
+    function global:prompt {
+        $previous_exit_code = $global:LASTEXITCODE
+        $envio = Get-Command -Name envio -CommandType Application | Select-Object -First 1
+        $code = & $envio hook-env --shell pwsh
+        if ($LASTEXITCODE -eq 0 -and $code) {
+            Invoke-Expression ($code -join "`n")
+        }
+        $global:LASTEXITCODE = $previous_exit_code
+        & $global:__envio_prompt
+    }
+}
+"#
+            }
+            Shell::Elvish => {
+                r#"
+set edit:before-readline = [$@edit:before-readline {
+    eval (e:envio hook-env --shell elvish | slurp)
+}]
+"#
+            }
+            Shell::Xonsh => {
+                r#"
+@events.on_pre_prompt
+def _envio_hook(**kwargs):
+    result = subprocess.run([shutil.which('envio'), 'hook-env', '--shell', 'xonsh'], stdout=subprocess.PIPE, text=True)
+    if result.returncode == 0:
+        execx(result.stdout)
+"#
+            }
+            Shell::Tcsh => {
+                r#"
+alias precmd 'eval "`\envio hook-env --shell tcsh`"'
+"#
+            }
+        };
+
+        let functions = match self {
+            Shell::Bash | Shell::Zsh => format!(
+                r#"envio() {{
+    local __envio_code
+    case "$1" in
+        load)
+            shift
+            __envio_code="$(command envio env --shell {shell} "$@")" && eval "$__envio_code"
+            ;;
+        unload)
+            shift
+            __envio_code="$(command envio env --shell {shell} --unset "$@")" && eval "$__envio_code"
+            ;;
+        *)
+            command envio "$@"
+            ;;
+    esac
+}}
+"#,
+                shell = self
+            ),
+            Shell::Fish => r#"function envio
+    switch "$argv[1]"
+        case load
+            set -l __envio_code (command envio env --shell fish $argv[2..-1] | string collect)
+            and eval $__envio_code
+        case unload
+            set -l __envio_code (command envio env --shell fish --unset $argv[2..-1] | string collect)
+            and eval $__envio_code
+        case '*'
+            command envio $argv
+    end
+end
+"#
+            .to_string(),
+            Shell::Nu => r#"def --env __envio_apply [code: string] {
+    if ($code | str trim | is-empty) { return }
+    let changes = ($code | from json)
+    let unset = ($changes | columns | where {|name| ($changes | get $name) == null })
+    if not ($unset | is-empty) { hide-env --ignore-errors ...$unset }
+    load-env ($changes | reject ...$unset)
+}
+
+def --env --wrapped envio [...args] {
+    match $args.0? {
+        "load" => { __envio_apply (^envio env --shell nu ...($args | skip 1)) }
+        "unload" => { __envio_apply (^envio env --shell nu --unset ...($args | skip 1)) }
+        _ => { ^envio ...$args }
+    }
+}
+"#
+            .to_string(),
+            Shell::Pwsh => r#"function global:envio {
+    $envio = Get-Command -Name envio -CommandType Application | Select-Object -First 1
+    switch ($args[0]) {
+        'load' {
+            $rest = @($args | Select-Object -Skip 1)
+            $code = & $envio env --shell pwsh @rest
+        }
+        'unload' {
+            $rest = @($args | Select-Object -Skip 1)
+            $code = & $envio env --shell pwsh --unset @rest
+        }
+        default {
+            & $envio @args
+            return
+        }
+    }
+    if ($LASTEXITCODE -eq 0 -and $code) {
+        Invoke-Expression ($code -join "`n")
+    }
+}
+"#
+            .to_string(),
+            Shell::Elvish => r#"edit:add-var envio~ {|@args|
+    if (and (> (count $args) 0) (eq $args[0] load)) {
+        eval (e:envio env --shell elvish (all $args[1..]) | slurp)
+    } elif (and (> (count $args) 0) (eq $args[0] unload)) {
+        eval (e:envio env --shell elvish --unset (all $args[1..]) | slurp)
+    } else {
+        e:envio $@args
+    }
+}
+"#
+            .to_string(),
+            Shell::Xonsh => r#"import shutil
+import subprocess
+
+def _envio(args):
+    envio = shutil.which('envio')
+    if args and args[0] in ('load', 'unload'):
+        unset = ['--unset'] if args[0] == 'unload' else []
+        result = subprocess.run([envio, 'env', '--shell', 'xonsh', *unset, *args[1:]], stdout=subprocess.PIPE, text=True)
+        if result.returncode == 0:
+            execx(result.stdout)
+        return result.returncode
+    return subprocess.run([envio, *args]).returncode
+
+aliases['envio'] = _envio
+"#
+            .to_string(),
+            Shell::Tcsh => r#"alias envio 'set __envio_args = (\!*)\
+if ($#__envio_args == 0) then\
+    \envio\
+else if ("$__envio_args[1]" == "load") then\
+    eval "`\envio env --shell tcsh $__envio_args[2-]:q`"\
+else if ("$__envio_args[1]" == "unload") then\
+    eval "`\envio env --shell tcsh --unset $__envio_args[2-]:q`"\
+else\
+    \envio $__envio_args:q\
+endif'
+"#
+            .to_string(),
+        };
+
+        functions + prompt_hook
+    }
+}
+
+impl FromStr for Shell {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            "nu" | "nushell" => Ok(Shell::Nu),
+            "pwsh" | "powershell" => Ok(Shell::Pwsh),
+            "elvish" => Ok(Shell::Elvish),
+            "xonsh" => Ok(Shell::Xonsh),
+            "tcsh" | "csh" => Ok(Shell::Tcsh),
+            _ => Err(Error::Msg(format!(
+                "Unsupported shell: `{}`, expected one of {}",
+                s, SUPPORTED_SHELLS
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shell::Bash => write!(f, "bash"),
+            Shell::Zsh => write!(f, "zsh"),
+            Shell::Fish => write!(f, "fish"),
+            Shell::Nu => write!(f, "nu"),
+            Shell::Pwsh => write!(f, "pwsh"),
+            Shell::Elvish => write!(f, "elvish"),
+            Shell::Xonsh => write!(f, "xonsh"),
+            Shell::Tcsh => write!(f, "tcsh"),
+        }
+    }
+}
+
+/// Check whether `name` can be used as the name of a shell variable
+///
+/// Names that are not valid could not be exported and could inject code into
+/// the generated statements, so they are never passed to the shell.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -1,112 +1,65 @@
-use std::fs::File;
 /// Utility functions used throughout the binary crate
-use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use chrono::{Days, Local, Months, NaiveDate};
+use envio::dotenv;
 use envio::error::{Error, Result};
+use envio::store::{open_default, ProfileStore};
 use envio::{Env, EnvVec};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::Client;
 
+/// Content of the `setenv.sh` file older versions of envio loaded profiles
+/// with, see [initalize_config]
+#[cfg(target_family = "unix")]
+const LEGACY_SHELLSCRIPT: &str =
+    "# envio no longer uses this file, see `envio hook --help` for how to load profiles\n";
+
 #[cfg(target_family = "unix")]
 pub fn initalize_config() -> Result<()> {
     use colored::Colorize;
-    use inquire::Text;
+    use envio::utils::{get_configdir, get_datadir};
     use std::path::Path;
 
     let configdir = get_configdir()?;
-    let homedir = get_homedir()?;
+    let datadir = get_datadir()?;
 
     if !Path::new(&configdir).exists() {
-        println!("{}", "Creating config directory".bold());
-
-        std::fs::create_dir(&configdir)?;
-        std::fs::create_dir(configdir.join("profiles"))?;
+        eprintln!("{}", "Creating config directory".bold());
+        std::fs::create_dir_all(&configdir)?;
     }
 
-    if !Path::new(&configdir.join("setenv.sh")).exists() {
-        println!("{}", "Creating shellscript".bold());
-        std::fs::write(configdir.join("setenv.sh"), "")?;
-
-        let shellconfig = get_shell_config()?;
-
-        let mut file_path =
-            PathBuf::from(&(homedir.to_str().unwrap().to_owned() + &format!("/{}", shellconfig)));
-        if !file_path.exists() {
-            let input = Text::new(
-                "Shell config file not found, please enter the path to your shell config file:",
-            )
-            .prompt();
-
-            file_path = if let Ok(val) = input {
-                PathBuf::from(val)
-            } else {
-                return Err(Error::Msg(
-                    "Failed to get shell config file path".to_string(),
-                ));
-            };
-
-            if !file_path.exists() {
-                return Err(Error::Msg(
-                    "Specified shell config file does not exist".to_string(),
-                ));
-            }
-        }
+    if !Path::new(&datadir.join("profiles")).exists() {
+        std::fs::create_dir_all(datadir.join("profiles"))?;
+    }
 
-        let mut file = std::fs::OpenOptions::new()
-            .append(true)
-            .open(file_path)
-            .unwrap();
-
-        let shellscript_path = &configdir.join("setenv.sh");
-
-        let buffer = if shellconfig.contains("fish") {
-            println!(
-                    "To use the shellscript properly you need to install the {}(https://github.com/edc/bass) plugin for fish",
-                    "bass".bold()
-                );
-            format!(
-                "
-# envio DO NOT MODIFY
-bass source {}
-",
-                shellscript_path.to_str().unwrap()
-            )
-        } else {
-            format!(
-                "
-#envio DO NOT MODIFY
-source {}
-",
-                shellscript_path.to_str().unwrap()
-            )
-        };
-
-        writeln!(file, "{}", buffer)?
+    // Older versions of envio added a line sourcing this script to the shell
+    // config, it is emptied instead of removed so that the shell still starts
+    // without errors until the user removes the line
+    let shellscript_path = configdir.join("setenv.sh");
+
+    if shellscript_path.exists()
+        && std::fs::read_to_string(&shellscript_path)? != LEGACY_SHELLSCRIPT
+    {
+        std::fs::write(&shellscript_path, LEGACY_SHELLSCRIPT)?;
+
+        eprintln!(
+            "{}: envio no longer uses {}, remove the line that sources it from your shell config and set up the shell hook instead, see `envio hook --help`",
+            "Warning".yellow(),
+            shellscript_path.display()
+        );
     }
 
     Ok(())
 }
-/// Get the home directory
-///
-/// # Returns
-/// - `PathBuf`: the home directory
-pub fn get_homedir() -> Result<PathBuf> {
-    match dirs::home_dir() {
-        Some(home) => Ok(home),
-        None => Err(Error::Io(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "Could not find home directory",
-        ))),
-    }
-}
 
-/// Get the config directory
+/// Open the store the profiles are kept in, see `envio store`
 ///
 /// # Returns
-/// - `PathBuf`: the config directory
-pub fn get_configdir() -> Result<PathBuf> {
-    Ok(get_homedir()?.join(".envio"))
+/// - `Result<Arc<dyn ProfileStore>>`: the store
+pub fn open_store() -> Result<Arc<dyn ProfileStore>> {
+    open_default()
 }
 
 pub fn contains_path_separator(s: &str) -> bool {
@@ -117,52 +70,211 @@ pub fn get_cwd() -> PathBuf {
     std::env::current_dir().unwrap()
 }
 
-/// Parse environment variables from a string
+/// Parse environment variables from a string in the dotenv format, see
+/// [dotenv](envio::dotenv) for what is supported
 ///
 /// # Parameters
 /// - `buffer`: &str - the buffer to parse
 ///
 /// # Returns
-/// - `Result<HashMap<String, String>>`: the parsed environment variables
+/// - `Result<EnvVec>`: the parsed environment variables
 pub fn parse_envs_from_string(buffer: &str) -> Result<EnvVec> {
+    parse_envs(buffer, false)
+}
+
+/// Parse environment variables from a string written by
+/// [cli::edit_buffer](crate::cli::edit_buffer)
+///
+/// Same as [parse_envs_from_string], but the `# @comment`, `# @expires`,
+/// `# @sensitive` and `# @tags` annotations above a key are applied to it and
+/// a key may only appear once.
+///
+/// # Parameters
+/// - `buffer`: &str - the buffer to parse
+///
+/// # Returns
+/// - `Result<EnvVec>`: the parsed environment variables
+pub fn parse_annotated_envs_from_string(buffer: &str) -> Result<EnvVec> {
+    parse_envs(buffer, true)
+}
+
+fn parse_envs(buffer: &str, annotated: bool) -> Result<EnvVec> {
     let mut envs_vec = EnvVec::new();
 
-    for buf in buffer.lines() {
-        if buf.is_empty() || !buf.contains('=') {
+    for entry in dotenv::parse(buffer)? {
+        let mut env = Env::from_key_value(entry.key, entry.value);
+
+        if !annotated {
+            envs_vec.push(env);
             continue;
         }
 
-        let mut split = buf.split('=');
+        for (name, value) in &entry.annotations {
+            apply_annotation(&mut env, name, value)?;
+        }
+
+        envs_vec.try_insert(env)?;
+    }
 
-        let key = split.next();
-        let mut value = split.next();
+    Ok(envs_vec)
+}
 
-        if key.is_none() {
-            return Err(Error::Msg("Can not parse key from buffer".to_string()));
-        }
+/// Apply an annotation of an edit buffer to an environment variable
+fn apply_annotation(env: &mut Env, name: &str, value: &str) -> Result<()> {
+    let invalid = || {
+        Error::Msg(format!(
+            "Invalid value for `@{}` of '{}': `{}`",
+            name, env.name, value
+        ))
+    };
 
-        if value.is_none() {
-            value = Some("");
+    match name {
+        "comment" => env.comment = Some(value.to_string()).filter(|c| !c.is_empty()),
+        "expires" => {
+            env.expiration_date =
+                Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| invalid())?)
         }
+        "sensitive" => env.sensitive = value.parse().map_err(|_| invalid())?,
+        "tags" => {
+            let tags: Vec<String> = value.split(',').map(str::to_string).collect();
+            env.tags = crate::cli::normalize_tags(&tags);
+        }
+        _ => {
+            return Err(Error::Msg(format!(
+                "Unknown annotation `@{}` for '{}', expected one of @comment, @expires, @sensitive or @tags",
+                name, env.name
+            )))
+        }
+    }
+
+    Ok(())
+}
 
-        envs_vec.push(Env::from_key_value(
-            key.unwrap().to_string(),
-            value.unwrap().to_string(),
-        ));
+/// Build an environment variable from a key and a value given on the command
+/// line
+///
+/// A value starting with `@` is interpreted as the path of a file to read the
+/// value from, e.g. `TLS_CERT=@cert.pem`. Use `@@` for a literal value that
+/// starts with `@`.
+///
+/// # Parameters
+/// - `key`: &str - the name of the environment variable
+/// - `value`: &str - the value or `@` followed by the path of a file
+///
+/// # Returns
+/// - `Result<Env>`: the environment variable
+pub fn env_from_key_value(key: &str, value: &str) -> Result<Env> {
+    if let Some(value) = value.strip_prefix("@@") {
+        return Ok(Env::from_key_value(key.to_string(), format!("@{}", value)));
     }
 
-    Ok(envs_vec)
+    if let Some(path) = value.strip_prefix('@') {
+        return read_env_from_file(key, path);
+    }
+
+    Ok(Env::from_key_value(key.to_string(), value.to_string()))
+}
+
+/// Build an environment variable from a `KEY=PATH` pair, the value is the
+/// content of the file
+///
+/// # Parameters
+/// - `arg`: &str - the `KEY=PATH` pair
+///
+/// # Returns
+/// - `Result<Env>`: the environment variable
+pub fn env_from_file_arg(arg: &str) -> Result<Env> {
+    match arg.split_once('=') {
+        Some((key, path)) if !key.is_empty() && !path.is_empty() => read_env_from_file(key, path),
+        _ => Err(Error::Msg(format!(
+            "Unable to parse '{}', expected KEY=PATH",
+            arg
+        ))),
+    }
+}
+
+/// Read the value of an environment variable from a file
+///
+/// Binary files are stored as base64
+fn read_env_from_file(key: &str, path: &str) -> Result<Env> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| Error::Msg(format!("Unable to read file '{}': {}", path, e)))?;
+
+    Ok(Env::from_bytes(key.to_string(), &bytes))
+}
+
+/// Parse an expiration date
+///
+/// The date can either be given as `YYYY-MM-DD` or relative to today as
+/// `+<amount><unit>` where the unit is one of `d` (days), `w` (weeks), `m`
+/// (months) or `y` (years). If the unit is omitted, days are assumed.
+///
+/// # Parameters
+/// - `s`: &str - the date to parse, e.g. `2025-06-30` or `+90d`
+///
+/// # Returns
+/// - `Result<NaiveDate>`: the parsed date
+pub fn parse_expiration_date(s: &str) -> Result<NaiveDate> {
+    let invalid = || {
+        Error::Msg(format!(
+            "Invalid expiration date `{}`, expected a date like 2025-06-30 or a relative date like +90d",
+            s
+        ))
+    };
+
+    let relative = match s.trim().strip_prefix('+') {
+        Some(relative) => relative,
+        None => return NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").map_err(|_| invalid()),
+    };
+
+    let (amount, unit) = match relative.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => relative.split_at(i),
+        None => (relative, "d"),
+    };
+
+    let amount: u32 = amount.parse().map_err(|_| invalid())?;
+    let today = Local::now().date_naive();
+
+    let date = match unit {
+        "d" => today.checked_add_days(Days::new(amount.into())),
+        "w" => today.checked_add_days(Days::new(u64::from(amount) * 7)),
+        "m" => today.checked_add_months(Months::new(amount)),
+        "y" => amount
+            .checked_mul(12)
+            .and_then(|months| today.checked_add_months(Months::new(months))),
+        _ => None,
+    };
+
+    date.ok_or_else(invalid)
+}
+
+/// Get the date that is `period` from today
+///
+/// # Parameters
+/// - `period`: &str - the period, e.g. `90d`, `12w`, `6m` or `1y`, see
+///   [parse_expiration_date]
+///
+/// # Returns
+/// - `Result<NaiveDate>`: the date
+pub fn date_after_period(period: &str) -> Result<NaiveDate> {
+    let relative = period.trim().trim_start_matches('+');
+
+    parse_expiration_date(&format!("+{}", relative)).map_err(|_| {
+        Error::Msg(format!(
+            "Invalid period `{}`, expected a period like 90d, 12w, 6m or 1y",
+            period
+        ))
+    })
 }
 
 /// Download a file from a url with a progress bar
 ///
 /// # Parameters
 /// - `url`: &str - the url to download the file from
-/// - `file_name`: &str - the name of the file to save the downloaded file to
 ///
 /// # Returns
-/// - `Result<()>`: an empty result
-pub async fn download_file(url: &str, file_name: &str) -> Result<()> {
+/// - `Result<Vec<u8>>`: the content of the file
+pub async fn download_file(url: &str) -> Result<Vec<u8>> {
     let client = Client::new();
     let mut resp = if let Err(e) = client.get(url).send().await {
         return Err(Error::Msg(e.to_string()));
@@ -170,7 +282,7 @@ pub async fn download_file(url: &str, file_name: &str) -> Result<()> {
         client.get(url).send().await.unwrap()
     };
 
-    let mut file = File::create(file_name)?;
+    let mut content = Vec::new();
 
     let mut content_length = if resp.content_length().is_none() {
         return Err(Error::Msg("Content length is not available".to_string()));
@@ -187,42 +299,12 @@ pub async fn download_file(url: &str, file_name: &str) -> Result<()> {
 
     while let Some(chunk) = resp.chunk().await.unwrap() {
         let chunk_size = chunk.len();
-        file.write_all(&chunk)?;
+        content.extend_from_slice(&chunk);
 
         pb.inc(chunk_size as u64);
         content_length -= chunk_size as u64;
     }
 
     pb.finish();
-    Ok(())
-}
-
-/// Unix specific code
-/// Returns the shell that is being used
-///
-/// # Returns
-/// - `Result<&'static str>`: the shell that is being used
-#[cfg(target_family = "unix")]
-pub fn get_shell_config() -> Result<&'static str> {
-    // Gets your default shell
-    // This is used to determine which shell config file to edit
-    let shell_env_value = if let Ok(e) = std::env::var("SHELL") {
-        e
-    } else {
-        return Err(Error::Msg("Failed to get shell".to_string()));
-    };
-
-    let shell_as_vec = shell_env_value.split('/').collect::<Vec<&str>>();
-    let shell = shell_as_vec[shell_as_vec.len() - 1];
-
-    let mut shell_config = "";
-    if shell.contains("bash") {
-        shell_config = ".bashrc";
-    } else if shell.contains("zsh") {
-        shell_config = ".zshrc";
-    } else if shell.contains("fish") {
-        shell_config = ".config/fish/config.fish"
-    }
-
-    Ok(shell_config)
+    Ok(content)
 }
@@ -23,7 +23,7 @@ fn get_cache_dir() -> Option<PathBuf> {
         let app_cache_dir = cache_dir.join(app_name);
         if !app_cache_dir.exists() {
             if let Err(e) = create_dir_all(&app_cache_dir) {
-                println!(
+                eprintln!(
                     "{}: Failed to create cache directory {}: {}",
                     "Error".red(),
                     app_cache_dir.display(),
@@ -34,24 +34,27 @@ fn get_cache_dir() -> Option<PathBuf> {
         }
         Some(app_cache_dir)
     } else {
-        println!("{}: Failed to get cache directory", "Error".red());
+        eprintln!("{}: Failed to get cache directory", "Error".red());
         None
     }
 }
 
 /// Get the latest version from the cache file, GitHub API or git If the cache
 /// file doesn't exist, fetch the latest version from the Github API or git and
-/// create the cache file If the cache file is older than 7 days, fetch the
+/// create the cache file If the cache file is older than `interval`, fetch the
 /// latest version from GitHub API If the GitHub API fails, fetch the latest
 /// version from git
 ///
+/// # Parameters
+/// - `interval`: std::time::Duration - how long the cached version is used for
+///
 /// # Returns
 /// - `Version`: the latest version
-pub fn get_latest_version() -> Version {
+pub fn get_latest_version(interval: std::time::Duration) -> Version {
     let cache_dir = if let Some(cache_dir) = get_cache_dir() {
         cache_dir
     } else {
-        println!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
+        eprintln!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
         return Version::parse("0.1.0").unwrap();
     };
 
@@ -66,8 +69,8 @@ pub fn get_latest_version() -> Version {
             let file = if let Ok(file) = File::create(&cache_file) {
                 file
             } else {
-                println!("{}: Failed to create cache file", "Error".red());
-                println!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
+                eprintln!("{}: Failed to create cache file", "Error".red());
+                eprintln!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
                 return Version::parse("0.0.0").unwrap();
             };
 
@@ -83,9 +86,9 @@ pub fn get_latest_version() -> Version {
         }
     };
 
-    let seven_days_ago = Utc::now() - Duration::days(7);
+    let interval = Duration::from_std(interval).unwrap_or(Duration::days(7));
 
-    if cache_data.last_update_time <= seven_days_ago {
+    if cache_data.last_update_time <= Utc::now() - interval {
         let latest_version = fetch_latest_version(&cache_data.version);
 
         let mut new_cache_data = cache_data;
@@ -98,8 +101,8 @@ pub fn get_latest_version() -> Version {
                 serialize_into(&mut writer, &new_cache_data).unwrap();
             }
             Err(e) => {
-                println!("{}: Failed to create cache file: {}", "Error".red(), e);
-                println!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
+                eprintln!("{}: Failed to create cache file: {}", "Error".red(), e);
+                eprintln!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
                 return Version::parse("0.0.0").unwrap();
             }
         };
@@ -108,8 +111,8 @@ pub fn get_latest_version() -> Version {
     } else if let Ok(version) = Version::parse(&cache_data.version) {
         version
     } else {
-        println!("{}: Failed to parse version from cache file", "Error".red());
-        println!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
+        eprintln!("{}: Failed to parse version from cache file", "Error".red());
+        eprintln!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
         Version::parse("0.0.0").unwrap()
     }
 }
@@ -128,16 +131,16 @@ fn fetch_latest_version(fallback_version: &str) -> Version {
         if let Some(val) = fetch_version_from_git() {
             val
         } else {
-            println!("{}:  Failed to get latest version", "Error".red());
-            println!(
+            eprintln!("{}:  Failed to get latest version", "Error".red());
+            eprintln!(
                 "{}: You can still use envio but won't be notified about new versions!",
                 "Warning".yellow()
             );
             if let Ok(version) = Version::parse(fallback_version) {
                 version
             } else {
-                println!("{}: Failed to parse fallback version", "Error".red());
-                println!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
+                eprintln!("{}: Failed to parse fallback version", "Error".red());
+                eprintln!("{}: Using 0.0.0 as fallback version", "Warning".yellow());
                 Version::parse("0.0.0").unwrap()
             }
         }
@@ -195,7 +198,7 @@ async fn fetch_version_from_github_api() -> Option<Version> {
 
 fn fetch_version_from_git() -> Option<Version> {
     if Command::new("git").arg("--version").output().is_err() {
-        println!("{}: Git is not installed", "Error".red());
+        eprintln!("{}: Git is not installed", "Error".red());
         return None;
     }
 
@@ -11,7 +11,7 @@ pub const IDENTITY_BYTES: &[u8] = b"-----AGE ENCRYPTED FILE-----";
 
 /// AGE is not a real encryption type, but rather a wrapper around the `age` crate
 /// It is supposed to represent the password-based encryption method that `envio` provides
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct AGE {
     key: String,
 }
@@ -34,6 +34,10 @@ impl EncryptionType for AGE {
         "age"
     }
 
+    fn clone_box(&self) -> Box<dyn EncryptionType> {
+        Box::new(self.clone())
+    }
+
     fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
         let encryptor = age::Encryptor::with_user_passphrase(Secret::new(self.key.to_owned()));
 
@@ -54,9 +58,16 @@ impl EncryptionType for AGE {
     }
 
     fn decrypt(&self, encrypted_data: &[u8]) -> Result<Vec<u8>> {
-        let decryptor = match age::Decryptor::new(encrypted_data).unwrap() {
-            age::Decryptor::Passphrase(d) => d,
-            _ => unreachable!(),
+        let decryptor = match age::Decryptor::new(encrypted_data) {
+            Ok(age::Decryptor::Passphrase(d)) => d,
+            Ok(_) => {
+                return Err(Error::Crypto(
+                    "The data was not encrypted using a passphrase".to_string(),
+                ));
+            }
+            Err(e) => {
+                return Err(Error::Crypto(e.to_string()));
+            }
         };
 
         let mut decrypted = vec![];
@@ -17,7 +17,7 @@ use crate::utils;
 // Bytes that identify the file as being encrypted using the `gpg` method
 pub const IDENTITY_BYTES: &[u8] = b"-----GPG ENCRYPTED FILE-----";
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct GPG {
     key_fingerprint: String,
 }
@@ -40,6 +40,10 @@ impl EncryptionType for GPG {
         "gpg"
     }
 
+    fn clone_box(&self) -> Box<dyn EncryptionType> {
+        Box::new(self.clone())
+    }
+
     fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
         let mut encrypted_data = Vec::new();
 
@@ -196,6 +200,45 @@ impl GPG {
             Ok(false)
         }
     }
+
+    /// Check if the encrypted data was encrypted using GPG, for data written
+    /// before envio appended the identity bytes
+    ///
+    /// Unlike [is_this_type_fallback](GPG::is_this_type_fallback) the data does
+    /// not have to be stored in a file. Only the packets of the data are
+    /// inspected, nothing is decrypted.
+    ///
+    /// # Parameters
+    /// - `encrypted_data`: &[u8] - the encrypted data
+    ///
+    /// # Returns
+    /// - `bool`: true if the data was encrypted using GPG, false if it was not
+    ///   or if GPG is not installed
+    pub fn is_this_type_fallback_from_bytes(encrypted_data: &[u8]) -> bool {
+        let gpg_process = Command::new("gpg")
+            .arg("--batch")
+            .arg("--list-packets")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+
+        let mut gpg_process = match gpg_process {
+            Ok(process) => process,
+            Err(_) => return false,
+        };
+
+        if let Some(mut stdin) = gpg_process.stdin.take() {
+            // gpg stops reading as soon as it finds out that the data is not
+            // an OpenPGP message, so a failed write is not an error
+            let _ = stdin.write_all(encrypted_data);
+        }
+
+        match gpg_process.wait_with_output() {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).contains(":pubkey enc packet:"),
+            Err(_) => false,
+        }
+    }
 }
 
 /// Get the GPG keys available on the system
@@ -273,9 +316,9 @@ pub fn get_gpg_keys() -> Option<Vec<(String, String)>> {
     let output = Command::new("gpg")
         .args(["--list-keys", "--keyid-format", "LONG"])
         .output()
-        .expect("Failed to execute command");
+        .ok()?;
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stdout = String::from_utf8(output.stdout).ok()?;
 
     if stdout.trim().is_empty() {
         return Some(vec![]);
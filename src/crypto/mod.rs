@@ -13,8 +13,11 @@ use crate::{
 /// Trait for encryption types
 ///
 /// Used to define the methods that an encryption type must implement.
+///
+/// Encryption types are `Send` and `Sync` so that profiles can be moved to
+/// other threads, e.g. to decrypt them without blocking an async runtime.
 #[typetag::serde(tag = "type")]
-pub trait EncryptionType {
+pub trait EncryptionType: Send + Sync {
     fn new(key: String) -> Self
     where
         Self: Sized;
@@ -57,6 +60,12 @@ pub trait EncryptionType {
     /// - `&'static str`: the string representation of the encryption type
     fn as_string(&self) -> &'static str;
 
+    /// Clone the encryption type, including its key
+    ///
+    /// # Returns
+    /// - `Box<dyn EncryptionType>`: the cloned encryption type
+    fn clone_box(&self) -> Box<dyn EncryptionType>;
+
     /// Check if the encrypted data was encrypted using this encryption type
     ///
     /// # Parameters
@@ -70,6 +79,12 @@ pub trait EncryptionType {
         Self: Sized;
 }
 
+impl Clone for Box<dyn EncryptionType> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
 /// Create an encryption type based on the `str` provided to
 /// `encryption_type_str` argument
 ///
@@ -119,24 +134,107 @@ pub fn create_encryption_type(
 /// # Example
 ///
 /// ```rust
-/// use envio::utils::get_profile_content;
 /// use envio::crypto::get_encryption_type;
 ///
-/// let encrypted_content = get_profile_content("my_profile").unwrap();
-///
-/// let encryption_type = get_encryption_type(&encrypted_content).unwrap();
+/// let encryption_type = get_encryption_type("my_profile").unwrap();
 ///
 /// println!("{}", encryption_type.as_string());
 /// ```
 pub fn get_encryption_type(profile_name: &str) -> Result<Box<dyn EncryptionType>> {
     let encrypted_content = utils::get_profile_content(profile_name)?;
 
-    let e_type =
-        if GPG::is_this_type(&encrypted_content) || GPG::is_this_type_fallback(profile_name)? {
-            "gpg"
-        } else {
-            "age"
-        };
+    get_encryption_type_from_bytes(&encrypted_content)
+}
+
+/// Get the encryption type used to encrypt the content of a profile
+///
+/// Same as [get_encryption_type] but works on the encrypted content itself, so
+/// the profile does not have to be stored in the profiles directory.
+///
+/// # Parameters
+/// - `encrypted_content`: &[u8] - the encrypted content of the profile
+///
+/// # Returns
+/// - `Result<Box<dyn EncryptionType>>`: the encryption type, without a key
+///
+/// # Example
+///
+/// ```rust
+/// use envio::crypto::get_encryption_type_from_bytes;
+///
+/// let encrypted_content = std::fs::read("my_profile.env").unwrap();
+///
+/// let encryption_type = get_encryption_type_from_bytes(&encrypted_content).unwrap();
+///
+/// println!("{}", encryption_type.as_string());
+/// ```
+pub fn get_encryption_type_from_bytes(encrypted_content: &[u8]) -> Result<Box<dyn EncryptionType>> {
+    let e_type = if GPG::is_this_type(encrypted_content) {
+        "gpg"
+    } else if AGE::is_this_type(encrypted_content) {
+        "age"
+    } else if GPG::is_this_type_fallback_from_bytes(encrypted_content) {
+        "gpg"
+    } else {
+        "age"
+    };
 
     create_encryption_type("".to_string(), e_type)
 }
+
+/// Async version of [get_encryption_type](get_encryption_type)
+///
+/// Telling GPG encrypted profiles apart may run `gpg`, which is done on
+/// tokio's blocking thread pool.
+///
+/// # Parameters
+/// - `profile_name` - the name of the profile or the path to the profile file
+///
+/// # Returns
+/// - `Result<Box<dyn EncryptionType>>`: the encryption type, without a key
+#[cfg(feature = "async")]
+pub async fn get_encryption_type_async(profile_name: &str) -> Result<Box<dyn EncryptionType>> {
+    let profile_name = profile_name.to_string();
+
+    utils::spawn_blocking(move || get_encryption_type(&profile_name)).await
+}
+
+/// Encrypt data on tokio's blocking thread pool
+///
+/// # Parameters
+/// - `encryption_type` - the encryption type to use, it is cloned
+/// - `data` - the data to encrypt
+///
+/// # Returns
+/// - `Result<Vec<u8>>`: the encrypted data
+#[cfg(feature = "async")]
+pub async fn encrypt_async(encryption_type: &dyn EncryptionType, data: Vec<u8>) -> Result<Vec<u8>> {
+    let encryption_type = encryption_type.clone_box();
+
+    utils::spawn_blocking(move || encryption_type.encrypt(&data)).await
+}
+
+/// Decrypt data on tokio's blocking thread pool
+///
+/// GPG decryption can take seconds while it waits for pinentry, this keeps it
+/// from blocking the runtime. The identity bytes [encrypt_async] appends are
+/// removed before decrypting, so its output can be passed as is.
+///
+/// # Parameters
+/// - `encryption_type` - the encryption type to use, it is cloned
+/// - `encrypted_data` - the encrypted data
+///
+/// # Returns
+/// - `Result<Vec<u8>>`: the decrypted data
+#[cfg(feature = "async")]
+pub async fn decrypt_async(
+    encryption_type: &dyn EncryptionType,
+    encrypted_data: Vec<u8>,
+) -> Result<Vec<u8>> {
+    let encryption_type = encryption_type.clone_box();
+
+    utils::spawn_blocking(move || {
+        encryption_type.decrypt(&utils::truncate_identity_bytes(&encrypted_data))
+    })
+    .await
+}
@@ -0,0 +1,707 @@
+//! Deserialization of environment variables into user defined types
+//!
+//! The names of the environment variables are mapped onto the fields of a
+//! struct, the same way [envy](https://docs.rs/envy) maps the environment of
+//! the process:
+//!
+//! ```rust
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Database {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     debug: bool,                // APP_DEBUG=true
+//!     workers: Option<usize>,     // APP_WORKERS=4, or not set at all
+//!     allowed_hosts: Vec<String>, // APP_ALLOWED_HOSTS=localhost,example.com
+//!     database: Database,         // APP_DATABASE__HOST and APP_DATABASE__PORT
+//! }
+//!
+//! let profile = envio::load_profile!("my-profile").unwrap();
+//!
+//! let config: Config = profile
+//!     .deserialize_with(&envio::de::DeserializeOptions::new().prefix("APP_"))
+//!     .unwrap();
+//! ```
+//!
+//! Sequences are read from comma separated values and nested structs from
+//! names joined by a [separator](DeserializeOptions::separator), `__` by
+//! default.
+//!
+//! Error messages name the environment variable that failed to deserialize but
+//! never include its value, since it may be a secret.
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::de::value::{SeqDeserializer, StrDeserializer};
+use serde::de::{self, DeserializeOwned, Error as _, IntoDeserializer, MapAccess, Visitor};
+
+use crate::error::{Error, Result};
+use crate::{Env, EnvVec};
+
+/// How the names of the environment variables are converted before they are
+/// matched against the fields of a struct
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyCase {
+    /// `DATABASE_URL` becomes `database_url`, matching snake case fields
+    #[default]
+    Lowercase,
+    /// The names are used as is
+    Preserve,
+    /// `DATABASE_URL` becomes `databaseUrl`
+    CamelCase,
+    /// `DATABASE_URL` becomes `database-url`
+    KebabCase,
+}
+
+impl KeyCase {
+    fn convert(&self, name: &str) -> String {
+        match self {
+            KeyCase::Lowercase => name.to_lowercase(),
+            KeyCase::Preserve => name.to_string(),
+            KeyCase::CamelCase => name
+                .split('_')
+                .filter(|w| !w.is_empty())
+                .enumerate()
+                .map(|(i, word)| {
+                    let word = word.to_lowercase();
+                    if i == 0 {
+                        return word;
+                    }
+
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => first.to_uppercase().chain(chars).collect(),
+                        None => word,
+                    }
+                })
+                .collect(),
+            KeyCase::KebabCase => name.to_lowercase().replace('_', "-"),
+        }
+    }
+
+    /// Convert the name of a field back into the name of an environment
+    /// variable, used to name the missing ones
+    fn revert(&self, field: &str) -> String {
+        if *self == KeyCase::Preserve {
+            return field.to_string();
+        }
+
+        let mut name = String::with_capacity(field.len());
+
+        for (i, c) in field.chars().enumerate() {
+            if c == '-' {
+                name.push('_');
+            } else if c.is_uppercase() && i != 0 {
+                name.push('_');
+                name.push(c);
+            } else {
+                name.extend(c.to_uppercase());
+            }
+        }
+
+        name
+    }
+}
+
+/// Options used to map the environment variables of a profile onto a type
+#[derive(Clone, Debug)]
+pub struct DeserializeOptions {
+    prefix: Option<String>,
+    separator: String,
+    case: KeyCase,
+}
+
+impl Default for DeserializeOptions {
+    fn default() -> Self {
+        DeserializeOptions {
+            prefix: None,
+            separator: "__".to_string(),
+            case: KeyCase::default(),
+        }
+    }
+}
+
+impl DeserializeOptions {
+    pub fn new() -> DeserializeOptions {
+        DeserializeOptions::default()
+    }
+
+    /// Only use the environment variables whose name starts with `prefix`,
+    /// the prefix is removed before the names are matched against the fields
+    pub fn prefix(mut self, prefix: impl Into<String>) -> DeserializeOptions {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Set the separator between the names of nested fields, `__` by default
+    ///
+    /// An empty separator disables nesting.
+    pub fn separator(mut self, separator: impl Into<String>) -> DeserializeOptions {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Set how the names are converted, [KeyCase::Lowercase] by default
+    pub fn case(mut self, case: KeyCase) -> DeserializeOptions {
+        self.case = case;
+        self
+    }
+}
+
+/// Deserialize environment variables into a type
+///
+/// # Parameters
+/// - `envs` - the environment variables
+/// - `options` - how the names are mapped onto the fields of the type
+///
+/// # Returns
+/// - `Result<T>`: the deserialized value
+pub fn from_envs<T: DeserializeOwned>(envs: &EnvVec, options: &DeserializeOptions) -> Result<T> {
+    let root = Node::build(envs, options);
+
+    T::deserialize(NodeDeserializer {
+        node: &root,
+        options,
+    })
+    .map_err(Error::from)
+}
+
+/// Environment variables grouped by the segments of their names
+struct Node<'a> {
+    /// Name of the environment variable, or of the group for nested fields
+    name: String,
+    env: Option<&'a Env>,
+    children: IndexMap<String, Node<'a>>,
+}
+
+impl<'a> Node<'a> {
+    fn new(name: String) -> Node<'a> {
+        Node {
+            name,
+            env: None,
+            children: IndexMap::new(),
+        }
+    }
+
+    fn build(envs: &'a EnvVec, options: &DeserializeOptions) -> Node<'a> {
+        let prefix = options.prefix.as_deref().unwrap_or("");
+        let mut root = Node::new(prefix.to_string());
+
+        for env in envs {
+            let Some(name) = env.name.strip_prefix(prefix) else {
+                continue;
+            };
+
+            let segments: Vec<&str> = if options.separator.is_empty() {
+                vec![name]
+            } else {
+                name.split(options.separator.as_str()).collect()
+            };
+
+            // Names such as `A____B` can not be mapped onto a field
+            if segments.iter().any(|s| s.is_empty()) {
+                continue;
+            }
+
+            let mut node = &mut root;
+            let mut path = prefix.to_string();
+
+            for (i, segment) in segments.iter().enumerate() {
+                if i != 0 {
+                    path.push_str(&options.separator);
+                }
+                path.push_str(segment);
+
+                node = node
+                    .children
+                    .entry(options.case.convert(segment))
+                    .or_insert_with(|| Node::new(path.clone()));
+            }
+
+            node.env = Some(env);
+        }
+
+        root
+    }
+
+    /// Name of the environment variable expected for a field of this node
+    fn field_name(&self, field: &str, options: &DeserializeOptions) -> String {
+        let field = options.case.revert(field);
+
+        // Only the root is named after the prefix, nested names are longer
+        if self.name == options.prefix.as_deref().unwrap_or("") {
+            return format!("{}{}", self.name, field);
+        }
+
+        format!("{}{}{}", self.name, options.separator, field)
+    }
+}
+
+/// Error returned by the deserializers of this module, converted to an
+/// [Error] once deserialization is done
+#[derive(Debug)]
+pub struct DeError {
+    key: Option<String>,
+    message: String,
+    missing: bool,
+}
+
+impl DeError {
+    /// Attach the name of an environment variable if none was attached yet
+    fn at(mut self, key: &str) -> DeError {
+        if self.key.is_none() {
+            self.key = Some(key.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "`{}`: {}", key, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        DeError {
+            key: None,
+            message: msg.to_string(),
+            missing: false,
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        DeError {
+            key: None,
+            message: field.to_string(),
+            missing: true,
+        }
+    }
+
+    // The default implementations include the unexpected value in the message
+
+    fn invalid_type(_: de::Unexpected, exp: &dyn de::Expected) -> Self {
+        DeError::custom(format_args!("invalid type, expected {}", exp))
+    }
+
+    fn invalid_value(_: de::Unexpected, exp: &dyn de::Expected) -> Self {
+        DeError::custom(format_args!("invalid value, expected {}", exp))
+    }
+
+    fn unknown_variant(_: &str, expected: &'static [&'static str]) -> Self {
+        DeError::custom(format_args!(
+            "unknown variant, expected one of `{}`",
+            expected.join("`, `")
+        ))
+    }
+}
+
+impl From<DeError> for Error {
+    fn from(e: DeError) -> Self {
+        match (e.key, e.missing) {
+            (Some(key), true) => Error::EnvDoesNotExist(key),
+            (Some(key), false) => Error::InvalidEnv {
+                key,
+                message: e.message,
+            },
+            (None, true) => Error::EnvDoesNotExist(e.message),
+            (None, false) => Error::Deserialization(e.message),
+        }
+    }
+}
+
+/// Deserializer for a group of environment variables
+struct NodeDeserializer<'a, 'o> {
+    node: &'a Node<'a>,
+    options: &'o DeserializeOptions,
+}
+
+impl<'a, 'o> NodeDeserializer<'a, 'o> {
+    fn value(&self) -> std::result::Result<ValueDeserializer<'a>, DeError> {
+        match self.node.env {
+            Some(env) => Ok(ValueDeserializer {
+                key: &env.name,
+                value: &env.value,
+            }),
+            None => Err(DeError::custom(format_args!(
+                "expected a value but only nested environment variables such as `{}{}` are set",
+                self.node.name, self.options.separator
+            ))
+            .at(&self.node.name)),
+        }
+    }
+
+    fn visit_map<'de, V: Visitor<'de>>(self, visitor: V) -> std::result::Result<V::Value, DeError> {
+        let node = self.node;
+        let options = self.options;
+
+        visitor
+            .visit_map(NodeMapAccess {
+                children: node.children.iter(),
+                value: None,
+                options,
+            })
+            .map_err(|e| match e.key {
+                None if e.missing => {
+                    let key = node.field_name(&e.message, options);
+                    e.at(&key)
+                }
+                _ => e,
+            })
+    }
+}
+
+macro_rules! forward_to_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> std::result::Result<V::Value, DeError> {
+                self.value()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, 'o> de::Deserializer<'de> for NodeDeserializer<'a, 'o> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        match self.node.env {
+            Some(_) => self.value()?.deserialize_any(visitor),
+            None => self.visit_map(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        if self.node.env.is_some() && self.node.children.is_empty() {
+            return self.value()?.deserialize_map(visitor);
+        }
+
+        self.visit_map(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        let value = self.value()?;
+
+        match self.node.env {
+            // Binary values are stored encoded, hand out the original bytes
+            Some(env) if env.is_binary() => match env.decoded_value() {
+                Ok(bytes) => visitor
+                    .visit_byte_buf(bytes)
+                    .map_err(|e: DeError| e.at(&env.name)),
+                Err(e) => Err(DeError::custom(e).at(&env.name)),
+            },
+            _ => value.deserialize_byte_buf(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.value()?.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.value()?.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.value()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.value()?.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_unit deserialize_seq deserialize_identifier
+    }
+
+    // Unknown keys may be groups as well, which have no value to look at
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+}
+
+struct NodeMapAccess<'a, 'o> {
+    children: indexmap::map::Iter<'a, String, Node<'a>>,
+    value: Option<&'a Node<'a>>,
+    options: &'o DeserializeOptions,
+}
+
+impl<'de, 'a, 'o> MapAccess<'de> for NodeMapAccess<'a, 'o> {
+    type Error = DeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> std::result::Result<Option<K::Value>, DeError> {
+        match self.children.next() {
+            Some((key, node)) => {
+                self.value = Some(node);
+                let deserializer: StrDeserializer<DeError> = key.as_str().into_deserializer();
+                seed.deserialize(deserializer)
+                    .map(Some)
+                    .map_err(|e: DeError| e.at(&node.name))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        let node = self
+            .value
+            .take()
+            .ok_or_else(|| DeError::custom("value requested before its key"))?;
+
+        seed.deserialize(NodeDeserializer {
+            node,
+            options: self.options,
+        })
+    }
+}
+
+/// Deserializer for the value of a single environment variable
+#[derive(Clone, Copy)]
+struct ValueDeserializer<'a> {
+    key: &'a str,
+    value: &'a str,
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> std::result::Result<V::Value, DeError> {
+                match self.value.trim().parse() {
+                    Ok(v) => visitor.$visit(v).map_err(|e: DeError| e.at(self.key)),
+                    Err(e) => Err(DeError::custom(e).at(self.key)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor
+            .visit_str(self.value)
+            .map_err(|e: DeError| e.at(self.key))
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor
+            .visit_bytes(self.value.as_bytes())
+            .map_err(|e: DeError| e.at(self.key))
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_unit().map_err(|e: DeError| e.at(self.key))
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        let key = self.key;
+        let values: Vec<&str> = if self.value.is_empty() {
+            Vec::new()
+        } else {
+            self.value.split(',').collect()
+        };
+
+        let mut seq = SeqDeserializer::new(
+            values
+                .into_iter()
+                .map(|value| ValueDeserializer { key, value }),
+        );
+
+        let value = visitor
+            .visit_seq(&mut seq)
+            .map_err(|e: DeError| e.at(key))?;
+        seq.end().map_err(|e: DeError| e.at(key))?;
+
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        let deserializer: StrDeserializer<DeError> = self.value.trim().into_deserializer();
+
+        visitor
+            .visit_enum(deserializer)
+            .map_err(|e: DeError| e.at(self.key))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string identifier map struct
+    }
+}
+
+impl<'de, 'a> IntoDeserializer<'de, DeError> for ValueDeserializer<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
@@ -0,0 +1,196 @@
+//! Parsing of dotenv files
+//!
+//! Besides plain `KEY=value` lines the parser understands everything
+//! [escape::dotenv](crate::escape::dotenv) and [escape::sh](crate::escape::sh)
+//! write, so exported files can be imported again without changing a value:
+//!
+//! - a line may start with `export `
+//! - values in double quotes may contain the escape sequences `\n`, `\r`,
+//!   `\t`, `\\`, `\"` and `\$`
+//! - values in single quotes are taken literally
+//! - quoted values may span multiple lines, and quoted parts that follow each
+//!   other are joined like in a shell, e.g. `'it'\''s'`
+//!
+//! Unquoted values end at the end of the line or at a ` #` comment, a `#`
+//! that is not preceded by whitespace is part of the value. Trailing
+//! whitespace is removed. Only whitespace and a comment may follow a quoted
+//! value, anything else is an error instead of being dropped silently.
+//!
+//! Lines starting with `#` and lines without a `=` are ignored, except for
+//! `# @name value` annotations which are attached to the next entry.
+
+use crate::error::{Error, Result};
+
+/// An environment variable read from a dotenv file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: String,
+    /// The `# @name value` comments above the entry, as `(name, value)` pairs
+    pub annotations: Vec<(String, String)>,
+}
+
+/// Parse the content of a dotenv file
+///
+/// # Parameters
+/// - `buffer`: &str - the content to parse
+///
+/// # Returns
+/// - `Result<Vec<Entry>>`: the entries in the order they appear, an error if
+///   a key is missing, a quoted value is not terminated or is followed by
+///   something else than a comment
+pub fn parse(buffer: &str) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut chars = buffer.chars().peekable();
+    let mut annotations: Vec<(String, String)> = Vec::new();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let line: String = take_until(&mut chars, |c| c == '=' || c == '\n');
+
+        if line.starts_with('#') || chars.peek() != Some(&'=') {
+            // Comments and lines without a value are skipped
+            let line = line + &take_until(&mut chars, |c| c == '\n');
+
+            if let Some(annotation) = line.strip_prefix('#').map(str::trim) {
+                if let Some(annotation) = annotation.strip_prefix('@') {
+                    let (name, value) = annotation.split_once(' ').unwrap_or((annotation, ""));
+                    annotations.push((name.to_string(), value.trim().to_string()));
+                }
+            }
+
+            continue;
+        }
+
+        chars.next(); // Skip the `=`
+
+        let key = line.trim();
+        let key = key.strip_prefix("export ").unwrap_or(key).trim();
+
+        if key.is_empty() {
+            return Err(Error::Msg("Can not parse key from buffer".to_string()));
+        }
+
+        take_until(&mut chars, |c| c != ' ' && c != '\t');
+
+        let value = match chars.peek() {
+            Some('"' | '\'') => {
+                let value = parse_quoted(&mut chars, key)?;
+                let rest = take_until(&mut chars, |c| c == '\n');
+                let rest = rest.trim();
+
+                if !rest.is_empty() && !rest.starts_with('#') {
+                    return Err(Error::Msg(format!(
+                        "Unexpected '{}' after the quoted value of key '{}'",
+                        rest, key
+                    )));
+                }
+
+                value
+            }
+            _ => strip_comment(&take_until(&mut chars, |c| c == '\n'))
+                .trim_end()
+                .to_string(),
+        };
+
+        entries.push(Entry {
+            key: key.to_string(),
+            value,
+            annotations: std::mem::take(&mut annotations),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Parse quoted parts until something else than a quote or an escaped
+/// character follows
+fn parse_quoted<I>(chars: &mut std::iter::Peekable<I>, key: &str) -> Result<String>
+where
+    I: Iterator<Item = char>,
+{
+    let unterminated = || Error::Msg(format!("Unterminated quoted value for key '{}'", key));
+    let mut value = String::new();
+
+    loop {
+        match chars.peek() {
+            Some('"') => {
+                chars.next();
+
+                loop {
+                    match chars.next().ok_or_else(unterminated)? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or_else(unterminated)? {
+                            'n' => value.push('\n'),
+                            'r' => value.push('\r'),
+                            't' => value.push('\t'),
+                            c @ ('\\' | '"' | '$') => value.push(c),
+                            c => {
+                                value.push('\\');
+                                value.push(c);
+                            }
+                        },
+                        c => value.push(c),
+                    }
+                }
+            }
+            Some('\'') => {
+                chars.next();
+                value += &take_until(chars, |c| c == '\'');
+
+                if chars.next().is_none() {
+                    return Err(unterminated());
+                }
+            }
+            Some('\\') => {
+                chars.next();
+
+                match chars.next() {
+                    Some(c) if c != '\n' => value.push(c),
+                    _ => return Err(unterminated()),
+                }
+            }
+            _ => return Ok(value),
+        }
+    }
+}
+
+/// Remove a ` #` comment from an unquoted value
+fn strip_comment(value: &str) -> &str {
+    let mut previous = None;
+
+    for (i, c) in value.char_indices() {
+        if c == '#' && matches!(previous, Some(' ' | '\t')) {
+            return &value[..i];
+        }
+
+        previous = Some(c);
+    }
+
+    value
+}
+
+/// Consume characters until `stop` returns true, the matching character is not
+/// consumed
+fn take_until<I, F>(chars: &mut std::iter::Peekable<I>, stop: F) -> String
+where
+    I: Iterator<Item = char>,
+    F: Fn(char) -> bool,
+{
+    let mut taken = String::new();
+
+    while let Some(&c) = chars.peek() {
+        if stop(c) {
+            break;
+        }
+
+        taken.push(c);
+        chars.next();
+    }
+
+    taken
+}
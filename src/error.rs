@@ -23,10 +23,31 @@ pub enum Error {
     EnvDoesNotExist(String),
     #[error("Environment variable `{0}` already exists")]
     EnvExists(String),
+    #[error("Invalid value for environment variable `{key}`: {message}")]
+    InvalidEnv { key: String, message: String },
     #[error("Crypto error: {0}")]
     Crypto(String),
     #[error("Invalid encryption type: {0}")]
     InvalidEncryptionType(String),
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+    #[error("{} schema violation(s) found", .0.len())]
+    SchemaViolations(Vec<crate::schema::Violation>),
+    #[error("Invalid expiration policy: `{0}`, expected one of warn, block or strip")]
+    InvalidExpirationPolicy(String),
+    #[error("Profile `{0}` contains expired environment variables")]
+    ExpiredEnvs(String),
+    #[error("Invalid store: `{0}`, expected one of fs or sqlite")]
+    InvalidStoreKind(String),
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("Invalid duration: `{0}`")]
+    InvalidDuration(String),
+    #[error(
+        "Profile format version {0} is not supported by this version of envio, please update envio"
+    )]
+    UnsupportedFormatVersion(u32),
     #[error("Invalid UTF-8: {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
     #[error("{0}")]
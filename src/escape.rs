@@ -0,0 +1,236 @@
+//! Escaping of values for the places profiles are exported to
+//!
+//! Every function returns the value in a form that the target reads back as
+//! the exact same string, so a value can never be split, expanded or executed
+//! by the program that reads it. Values written by [dotenv] and [sh] are also
+//! read back by the [dotenv parser](crate::dotenv::parse), which is how
+//! `envio create --file` imports them.
+
+use crate::error::{Error, Result};
+
+/// Characters that never have to be quoted, for targets that allow bare values
+const SAFE_CHARS: &str = "_./:@%+,=-";
+
+fn is_safe(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || SAFE_CHARS.contains(c))
+}
+
+/// Escape a value so that a POSIX shell (sh, dash, bash, zsh, ...) reads it as
+/// a single word
+///
+/// Values that only contain safe characters are returned as is, all other
+/// values are single-quoted. Inside of single quotes nothing is interpreted, a
+/// single quote is written by closing the quotes, escaping it and opening them
+/// again. Newlines are kept as they are, which all POSIX shells support.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn sh(value: &str) -> String {
+    if is_safe(value) {
+        return value.to_string();
+    }
+
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Escape a value so that fish reads it as a single word
+///
+/// Inside of single quotes fish only interprets `\\` and `\'`, everything
+/// else including newlines is kept as is.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn fish(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Escape a value for a dotenv file
+///
+/// Values that contain whitespace, control characters, quotes, `#`, `$`,
+/// backslashes or backticks are double-quoted, using the escape sequences
+/// `\n`, `\r`, `\t`, `\\`, `\"` and `\$` which dotenv libraries agree on.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn dotenv(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '\\' | '#' | '$' | '`')
+        });
+
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut escaped = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\\' | '"' | '$' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/// Escape a value for a docker env file (`docker run --env-file`)
+///
+/// Docker takes everything after the first `=` of a line literally, there is
+/// no quoting at all. Values are therefore written as they are, and values
+/// with line breaks, which would end the line early, can not be written.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `Result<String>`: the escaped value, an error if it contains a line break
+pub fn docker_env_file(value: &str) -> Result<String> {
+    if value.contains(['\n', '\r']) {
+        return Err(Error::Msg(
+            "Values with line breaks can not be written to docker env files".to_string(),
+        ));
+    }
+
+    Ok(value.to_string())
+}
+
+/// Escape a value as a JSON string, which is how values are passed to nushell
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn json(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\\' | '"' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Escape a value so that PowerShell reads it as a single word
+///
+/// Single-quoted strings are taken literally, only quotes have to be doubled.
+/// PowerShell also treats the typographic single quotes as quotes, so they are
+/// doubled as well.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn pwsh(value: &str) -> String {
+    let mut quoted = String::from("'");
+
+    for c in value.chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+
+    quoted.push('\'');
+    quoted
+}
+
+/// Escape a value so that elvish reads it as a single word
+///
+/// Single-quoted strings are taken literally, including newlines, only quotes
+/// have to be doubled.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn elvish(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Escape a value as a Python string literal, which is how values are passed
+/// to xonsh
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `String`: the escaped value
+pub fn python(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\\' | '"' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c if c.is_ascii_control() => quoted.push_str(&format!("\\x{:02x}", c as u8)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Escape a value so that tcsh reads it as a single word
+///
+/// tcsh has no way to write a newline or another control character that
+/// survives `eval`, so such values can not be quoted. `!` is escaped outside
+/// of the quotes because history substitution also happens inside of them.
+///
+/// # Parameters
+/// - `value`: &str - the value to escape
+///
+/// # Returns
+/// - `Result<String>`: the escaped value, an error if it contains control
+///   characters
+pub fn tcsh(value: &str) -> Result<String> {
+    if value.chars().any(|c| c.is_control()) {
+        return Err(Error::Msg(
+            "Values with control characters can not be passed to tcsh".to_string(),
+        ));
+    }
+
+    Ok(format!(
+        "'{}'",
+        value.replace('\'', "'\\''").replace('!', "'\\!'")
+    ))
+}
@@ -0,0 +1,209 @@
+//! Versioning of the serialized profile format
+//!
+//! Before a profile is encrypted it is serialized into the following payload:
+//!
+//! | bytes | content                                   |
+//! |-------|-------------------------------------------|
+//! | 0..8  | the magic bytes `ENVIOPRF`                |
+//! | 8..12 | the format version, a little endian `u32` |
+//! | 12..  | the profile serialized using bincode      |
+//!
+//! Profiles written by older versions of envio do not start with the magic
+//! bytes. They are recognized by their content and migrated to the current
+//! format:
+//!
+//! - version `0`: plain `KEY=VALUE` lines, used by the first versions of envio
+//! - version `1`: bincode without a header, used up to envio 0.6.1
+//! - version `2`: the current format
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::crypto::EncryptionType;
+use crate::error::{Error, Result};
+use crate::legacy::{self, ProfileV1};
+use crate::Profile;
+
+/// Magic bytes every versioned payload starts with
+const MAGIC: &[u8; 8] = b"ENVIOPRF";
+
+/// Version of the format profiles are written in
+pub const CURRENT_FORMAT_VERSION: u32 = 2;
+
+/// Options controlling how a profile is loaded by [Profile::load]
+#[derive(Default)]
+pub struct LoadOptions<'a> {
+    /// Write profiles stored in an older format back in the current format.
+    /// The profile is not locked while doing so, only enable this while
+    /// holding the [lock](crate::store::ProfileStore::lock) of the profile.
+    pub write_back: bool,
+    /// Called before a profile in the plain text format (version `0`) is
+    /// migrated. Such profiles can not be reliably told apart from a corrupted
+    /// profile file, returning `false` aborts the loading. When `None` the
+    /// profile is migrated without asking.
+    pub confirm_text_format: Option<&'a (dyn Fn() -> bool + Sync)>,
+}
+
+/// Something the user of a profile should be made aware of
+///
+/// The library never prints, it is up to the application to decide how and
+/// whether to present these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LoadWarning {
+    /// The profile was stored in an older format and has been migrated
+    Migrated { from_version: u32 },
+    /// The profile was read as plain text lines. This is how the first
+    /// versions of envio stored profiles, but it may also mean that the
+    /// profile file has been tampered with.
+    TextFormat,
+    /// The profile contains environment variables that have expired
+    ExpiredEnvs(Vec<String>),
+}
+
+impl fmt::Display for LoadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadWarning::Migrated { from_version } => write!(
+                f,
+                "the profile was migrated from format version {} to {}",
+                from_version, CURRENT_FORMAT_VERSION
+            ),
+            LoadWarning::TextFormat => write!(
+                f,
+                "the profile was read as plain text, it was either created with an old version of envio or has been tampered with"
+            ),
+            LoadWarning::ExpiredEnvs(names) => write!(
+                f,
+                "the following environment variables have expired: {}",
+                names.join(", ")
+            ),
+        }
+    }
+}
+
+/// Information about how a profile was loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadReport {
+    /// The format version the profile was stored in
+    pub found_version: u32,
+    /// Whether the profile had to be migrated to the current format
+    pub migrated: bool,
+    /// Whether the migrated profile was written back to the profile file
+    pub written_back: bool,
+    /// Everything that was noticed while loading the profile
+    pub warnings: Vec<LoadWarning>,
+}
+
+/// A profile in one of the formats envio has used over time
+enum Stored {
+    Text(String),
+    V1(ProfileV1),
+    Current(Box<Profile>),
+}
+
+impl Stored {
+    fn version(&self) -> u32 {
+        match self {
+            Stored::Text(_) => 0,
+            Stored::V1(_) => 1,
+            Stored::Current(_) => CURRENT_FORMAT_VERSION,
+        }
+    }
+}
+
+/// Serialize a profile into a payload in the current format
+///
+/// # Parameters
+/// - `profile` - the profile to serialize
+///
+/// # Returns
+/// - `Result<Vec<u8>>`: the payload, ready to be encrypted
+pub(crate) fn encode(profile: &Profile) -> Result<Vec<u8>> {
+    let body = bincode::serialize(profile).map_err(|e| Error::Serialization(e.to_string()))?;
+
+    let mut payload = Vec::with_capacity(MAGIC.len() + 4 + body.len());
+    payload.extend_from_slice(MAGIC);
+    payload.extend_from_slice(&CURRENT_FORMAT_VERSION.to_le_bytes());
+    payload.extend_from_slice(&body);
+
+    Ok(payload)
+}
+
+/// Figure out which format a decrypted payload is stored in and read it
+fn detect(payload: &[u8]) -> Result<Stored> {
+    if let Some(rest) = payload.strip_prefix(MAGIC) {
+        if rest.len() < 4 {
+            return Err(Error::Deserialization(
+                "The profile content is truncated".to_string(),
+            ));
+        }
+
+        let (version, body) = rest.split_at(4);
+        let version = u32::from_le_bytes([version[0], version[1], version[2], version[3]]);
+
+        return match version {
+            CURRENT_FORMAT_VERSION => legacy::deserialize_exact::<Profile>(body)
+                .map(|profile| Stored::Current(Box::new(profile)))
+                .map_err(|e| Error::Deserialization(e.to_string())),
+            v if v > CURRENT_FORMAT_VERSION => Err(Error::UnsupportedFormatVersion(v)),
+            v => Err(Error::Deserialization(format!(
+                "Format version {} is never stored with a header",
+                v
+            ))),
+        };
+    }
+
+    if let Ok(profile) = legacy::deserialize_exact::<ProfileV1>(payload) {
+        return Ok(Stored::V1(profile));
+    }
+
+    match std::str::from_utf8(payload) {
+        Ok(text) if legacy::is_text_format(text) => Ok(Stored::Text(text.to_string())),
+        _ => Err(Error::Deserialization(
+            "Unable to deserialize the profile content".to_string(),
+        )),
+    }
+}
+
+/// Read a decrypted payload in any of the supported formats and migrate it to
+/// the current format
+///
+/// # Parameters
+/// - `payload` - the decrypted content of the profile file
+/// - `profile_name` - the name of the profile
+/// - `profile_file_path` - the path to the profile file
+/// - `encryption_type` - the encryption type used to decrypt the payload
+/// - `options` - the options the profile is loaded with
+///
+/// # Returns
+/// - `Result<(Profile, u32)>`: the profile and the version it was stored in
+pub(crate) fn decode(
+    payload: &[u8],
+    profile_name: &str,
+    profile_file_path: PathBuf,
+    encryption_type: Box<dyn EncryptionType>,
+    options: &LoadOptions,
+) -> Result<(Profile, u32)> {
+    let stored = detect(payload)?;
+    let found_version = stored.version();
+
+    if let (Stored::Text(_), Some(confirm)) = (&stored, options.confirm_text_format) {
+        if !confirm() {
+            return Err(Error::Deserialization(
+                "Unable to deserialize the profile content".to_string(),
+            ));
+        }
+    }
+
+    // The encryption type is only needed to migrate the plain text format,
+    // later formats store their own
+    let profile = match stored {
+        Stored::Text(text) => {
+            ProfileV1::from_text(&text, profile_name, profile_file_path, encryption_type).into()
+        }
+        Stored::V1(profile) => profile.into(),
+        Stored::Current(profile) => *profile,
+    };
+
+    Ok((profile, found_version))
+}
@@ -0,0 +1,151 @@
+//! Layouts of profiles written by older versions of envio
+//!
+//! See the [format](crate::format) module for how the layouts are told apart
+//! and migrated.
+//!
+//! Profiles are serialized using bincode which is not a self describing
+//! format, so whenever a field is added to [Env] or [Profile] the previous
+//! layout has to be kept around to be able to read the profiles that were
+//! written before the change.
+use std::path::PathBuf;
+
+use bincode::Options;
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::crypto::EncryptionType;
+use crate::{Env, EnvVec, Profile, ValueEncoding};
+
+/// Deserialize `bytes` using the same encoding as `bincode::serialize` but
+/// refuse to succeed if any bytes are left over
+///
+/// Reading a profile with the wrong layout usually fails, but because of the
+/// variable length fields it can also happen that it succeeds by accident
+/// without consuming the whole input. Rejecting the trailing bytes makes the
+/// detection of the layout reliable.
+pub(crate) fn deserialize_exact<T: DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    bincode::options()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+}
+
+/// Environment variable as stored by envio 0.6.1 and older
+#[derive(Deserialize)]
+pub(crate) struct EnvV1 {
+    name: String,
+    value: String,
+    comment: Option<String>,
+    expiration_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct EnvVecV1 {
+    envs: Vec<EnvV1>,
+}
+
+/// Profile as stored by envio 0.6.1 and older
+#[derive(Deserialize)]
+pub(crate) struct ProfileV1 {
+    name: String,
+    envs: EnvVecV1,
+    profile_file_path: PathBuf,
+    encryption_type: Box<dyn EncryptionType>,
+}
+
+/// Whether a decrypted payload looks like a profile stored as plain
+/// `KEY=VALUE` lines by the first versions of envio
+///
+/// Those profiles hold at most one line without a `=`, the key of the profile,
+/// and every name is a single word. Anything else is rather a corrupted or
+/// tampered with profile than a profile in the plain text format.
+pub(crate) fn is_text_format(content: &str) -> bool {
+    let mut key_lines = 0;
+
+    for line in content.lines().filter(|line| !line.is_empty()) {
+        match line.split_once('=') {
+            Some((name, _)) => {
+                if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                    return false;
+                }
+            }
+            None => key_lines += 1,
+        }
+    }
+
+    key_lines <= 1
+}
+
+impl ProfileV1 {
+    /// Read a profile stored as plain `KEY=VALUE` lines by the first versions
+    /// of envio
+    ///
+    /// Lines without a `=` hold the key of the profile.
+    pub(crate) fn from_text(
+        content: &str,
+        name: &str,
+        profile_file_path: PathBuf,
+        mut encryption_type: Box<dyn EncryptionType>,
+    ) -> ProfileV1 {
+        let mut envs: Vec<EnvV1> = Vec::new();
+
+        for line in content.lines() {
+            if line.is_empty() {
+                continue;
+            }
+
+            if !line.contains('=') {
+                encryption_type.set_key(line.to_string());
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+                // Later lines overwrite earlier ones, as they always have
+                envs.retain(|e| e.name != key);
+                envs.push(EnvV1 {
+                    name: key.to_string(),
+                    value: value.to_string(),
+                    comment: None,
+                    expiration_date: None,
+                });
+            }
+        }
+
+        ProfileV1 {
+            name: name.to_string(),
+            envs: EnvVecV1 { envs },
+            profile_file_path,
+            encryption_type,
+        }
+    }
+}
+
+impl From<EnvV1> for Env {
+    fn from(env: EnvV1) -> Self {
+        Env {
+            // The sensitivity of the variable is derived from its name
+            sensitive: Env::is_sensitive_name(&env.name),
+            name: env.name,
+            value: env.value,
+            comment: env.comment,
+            expiration_date: env.expiration_date,
+            encoding: ValueEncoding::Text,
+            tags: Vec::new(),
+            rotated_at: None,
+        }
+    }
+}
+
+impl From<ProfileV1> for Profile {
+    fn from(profile: ProfileV1) -> Self {
+        let envs: Vec<Env> = profile.envs.envs.into_iter().map(Env::from).collect();
+
+        Profile::new(
+            profile.name,
+            EnvVec::from(envs),
+            profile.profile_file_path,
+            profile.encryption_type,
+        )
+    }
+}
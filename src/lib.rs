@@ -14,7 +14,7 @@
 //! simple example:
 //!
 //! ```rust
-//!    envio::load!("my_profile");
+//!    envio::load!("my_profile").unwrap();
 //!    println!("{}", std::env::var("MY_ENV_VAR").unwrap());
 //! ```
 //!
@@ -37,7 +37,7 @@
 //! method, you would have to provide a key when loading the profile:
 //!
 //! ```rust
-//!   envio::load!("my_profile", || "mysecretkey".to_string()); // The load macro expects a closure that returns the key
+//!   envio::load!("my_profile", || "mysecretkey".to_string()).unwrap(); // The load macro expects a closure that returns the key
 //!   println!("{}", std::env::var("MY_ENV_VAR").unwrap());
 //! ```
 //!
@@ -60,15 +60,48 @@
 //! The CLI tool makes use of the library and also has some additional
 //! functionality which is specific to the CLI tool only. However the building
 //! blocks were provided by the library.
+//!
+//! The dependencies of the CLI tool are behind the `cli` feature, which is
+//! enabled by default. Projects that only use the library can disable it:
+//!
+//! ```toml
+//! [dependencies]
+//! envio = { version = "0.6", default-features = false }
+//! ```
+//!
+//! The `async` feature adds async versions of the functions that block, such
+//! as [Profile::load_async](crate::Profile::load_async) and
+//! [Profile::push_changes_async](crate::Profile::push_changes_async). They
+//! run the blocking work on tokio's blocking thread pool and therefore have to
+//! be called from within a tokio runtime.
+//!
+//! The `sqlite` feature, enabled by default, adds a [store](crate::store) that
+//! keeps every profile in a single SQLite database.
 
+mod legacy;
 mod profile;
 pub mod utils;
 
 pub mod crypto;
+pub mod de;
+pub mod dotenv;
 pub mod error;
+pub mod escape;
+pub mod format;
+pub mod loader;
+pub mod lock;
+pub mod metadata;
+pub mod schema;
+pub mod store;
+pub use loader::{Loader, ResolvedEnvs};
+pub use metadata::ProfileMetadata;
 pub use profile::Env;
+pub use profile::EnvEntry;
+pub use profile::EnvMut;
 pub use profile::EnvVec; // Re-export EnvVec so that users don't have to use envio::profile::EnvVec
+pub use profile::ExpirationPolicy;
 pub use profile::Profile; // Re-export Profile so that users don't have to use envio::profile::Profile // Re-export Env so that users don't have to use envio::profile::Env
+pub use profile::ValueEncoding;
 
 /// Main macro used to load profiles
 ///
@@ -76,46 +109,30 @@ pub use profile::Profile; // Re-export Profile so that users don't have to use e
 /// key. This is only required if the profile was encrypted using the `age`
 /// method.
 ///
+/// Existing environment variables are overwritten, use a [Loader] for more
+/// control over what gets loaded.
+///
+/// # Returns
+/// - `Result<()>`: whether the environment variables of the profile were set
+///
 /// # Example
 ///
 /// ```rust
 /// // Assuming the profile was encrypted using the `gpg` method
-/// envio::load!("my_profile");
+/// envio::load!("my_profile").unwrap();
 /// println!("{}", std::env::var("MY_ENV_VAR").unwrap());
 /// ```
 #[macro_export]
 macro_rules! load {
     ($name:expr $(, $get_key:expr)?) => {
-        (||->envio::error::Result<()> {
-            use envio::Profile;
-            use envio::crypto;
-            use envio::utils;
-
-            let encrypted_content = utils::get_profile_content($name)?;
-            let mut encryption_type;
-
-            match crypto::get_encryption_type(&encrypted_content) {
-                Ok(t) => encryption_type = t,
-                Err(e) => return Err(e.into()),
-            }
-
-            if encryption_type.as_string() == "age" {
-                $(
-                    let key = $get_key();
-                    encryption_type.set_key(key);
-                )?
-            }
-
-            let profile = match Profile::from_content($name, &encrypted_content, encryption_type) {
-                Ok(profile) => profile,
-                Err(e) => return Err(e.into()),
-            };
-
-            for (env_var, value) in &profile.envs {
-                std::env::set_var(env_var, value);
-            }
+        (|| -> $crate::error::Result<()> {
+            $crate::Loader::new($name)
+                $(.key_provider($get_key))?
+                .override_existing(true)
+                .load()?
+                .apply_to_process();
 
             Ok(())
-         })()
+        })()
     };
 }
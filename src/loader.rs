@@ -0,0 +1,290 @@
+//! Fine grained loading of the environment variables of a profile
+//!
+//! The [load](crate::load) macro sets every environment variable of a profile
+//! in the current process, overwriting the existing values. A [Loader] gives
+//! control over which environment variables are loaded and where they end up:
+//!
+//! ```rust
+//! use envio::Loader;
+//!
+//! let envs = Loader::new("my-profile")
+//!     .key_provider(|| "my-key".to_string())
+//!     .prefix("APP_")
+//!     .strip_prefix(true)
+//!     .load()
+//!     .unwrap();
+//!
+//! // Either read the values directly
+//! let port = envs.get("PORT");
+//!
+//! // Or pass them on to a child process
+//! let mut command = std::process::Command::new("my-server");
+//! envs.apply_to_command(&mut command);
+//! ```
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use crate::crypto::get_encryption_type_from_bytes;
+use crate::error::Result;
+use crate::store::{self, ProfileStore};
+use crate::Profile;
+
+/// Builder used to load the environment variables of a profile
+pub struct Loader<'a> {
+    profile_name: String,
+    store: Option<Arc<dyn ProfileStore>>,
+    key_provider: Option<Box<dyn FnOnce() -> String + 'a>>,
+    override_existing: bool,
+    prefix: Option<String>,
+    strip_prefix: bool,
+    tags: Vec<String>,
+    dry_run: bool,
+}
+
+impl<'a> Loader<'a> {
+    /// Create a loader for a profile
+    ///
+    /// # Parameters
+    /// - `profile_name` - the name of the profile or the path to the profile
+    ///   file
+    pub fn new(profile_name: impl Into<String>) -> Loader<'a> {
+        Loader {
+            profile_name: profile_name.into(),
+            store: None,
+            key_provider: None,
+            override_existing: false,
+            prefix: None,
+            strip_prefix: false,
+            tags: Vec::new(),
+            dry_run: false,
+        }
+    }
+
+    /// Read the profile from a [ProfileStore] instead of the store envio keeps
+    /// its profiles in
+    pub fn store(mut self, store: Arc<dyn ProfileStore>) -> Loader<'a> {
+        self.store = Some(store);
+        self
+    }
+
+    /// Set the closure that returns the key of the profile
+    ///
+    /// It is only called if the profile is encrypted using the `age`
+    /// encryption type.
+    pub fn key_provider<F>(mut self, key_provider: F) -> Loader<'a>
+    where
+        F: FnOnce() -> String + 'a,
+    {
+        self.key_provider = Some(Box::new(key_provider));
+        self
+    }
+
+    /// Whether to overwrite environment variables that are already set,
+    /// `false` by default
+    pub fn override_existing(mut self, override_existing: bool) -> Loader<'a> {
+        self.override_existing = override_existing;
+        self
+    }
+
+    /// Only load the environment variables whose name starts with `prefix`
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Loader<'a> {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Whether to remove the [prefix](Loader::prefix) from the names of the
+    /// loaded environment variables, `false` by default
+    pub fn strip_prefix(mut self, strip_prefix: bool) -> Loader<'a> {
+        self.strip_prefix = strip_prefix;
+        self
+    }
+
+    /// Only load the environment variables labeled with at least one of
+    /// `tags`, see [Env::tags](crate::Env::tags)
+    pub fn tags<I, S>(mut self, tags: I) -> Loader<'a>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether [apply_to_process](ResolvedEnvs::apply_to_process) and
+    /// [apply_to_command](ResolvedEnvs::apply_to_command) should only report
+    /// what they would set, `false` by default
+    pub fn dry_run(mut self, dry_run: bool) -> Loader<'a> {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Decrypt the profile and resolve the environment variables to load
+    ///
+    /// Nothing is written, neither to the profile file nor to the environment
+    /// of the process.
+    ///
+    /// # Returns
+    /// - `Result<ResolvedEnvs>`: the environment variables to load
+    pub fn load(self) -> Result<ResolvedEnvs> {
+        let store = match self.store {
+            Some(store) => store,
+            None => store::store_for(&self.profile_name)?,
+        };
+        let encrypted_content = store.read(&self.profile_name)?;
+
+        let mut encryption_type = get_encryption_type_from_bytes(&encrypted_content)?;
+
+        if encryption_type.as_string() == "age" {
+            if let Some(key_provider) = self.key_provider {
+                encryption_type.set_key(key_provider());
+            }
+        }
+
+        let profile = Profile::from_bytes(&self.profile_name, &encrypted_content, encryption_type)?;
+
+        let mut vars = IndexMap::new();
+
+        for env in profile.envs {
+            if !self.tags.is_empty() && !self.tags.iter().any(|t| env.has_tag(t)) {
+                continue;
+            }
+
+            let name = match &self.prefix {
+                Some(prefix) => match env.name.strip_prefix(prefix.as_str()) {
+                    Some(stripped) if self.strip_prefix => stripped.to_string(),
+                    Some(_) => env.name,
+                    None => continue,
+                },
+                None => env.name,
+            };
+
+            // A variable named exactly like the prefix has no name left
+            if name.is_empty() {
+                continue;
+            }
+
+            vars.insert(name, env.value);
+        }
+
+        Ok(ResolvedEnvs {
+            vars,
+            override_existing: self.override_existing,
+            dry_run: self.dry_run,
+        })
+    }
+}
+
+/// Environment variables resolved by a [Loader]
+#[derive(Debug, Clone)]
+pub struct ResolvedEnvs {
+    vars: IndexMap<String, String>,
+    override_existing: bool,
+    dry_run: bool,
+}
+
+impl ResolvedEnvs {
+    /// Get the value of an environment variable
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(|v| v.as_str())
+    }
+
+    /// Return an iterator over the names and values, in the order of the
+    /// profile
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Get the number of environment variables
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Check to see if no environment variables were resolved
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Set the environment variables in the current process
+    ///
+    /// Variables that are already set are skipped unless the loader was told
+    /// to [override](Loader::override_existing) them.
+    ///
+    /// <div class="warning">Modifying the environment of the process is not
+    /// thread safe on most platforms. Call this before spawning any threads or
+    /// use <a href="#method.apply_to_command">apply_to_command</a> instead.</div>
+    ///
+    /// # Returns
+    /// - `Vec<String>`: the names of the environment variables that were set,
+    ///   or would have been set in [dry run](Loader::dry_run) mode
+    pub fn apply_to_process(&self) -> Vec<String> {
+        let mut applied = Vec::new();
+
+        for (name, value) in &self.vars {
+            if !self.override_existing && std::env::var_os(name).is_some() {
+                continue;
+            }
+
+            if !self.dry_run {
+                std::env::set_var(name, value);
+            }
+
+            applied.push(name.clone());
+        }
+
+        applied
+    }
+
+    /// Set the environment variables of a command that is yet to be spawned
+    ///
+    /// Variables that are already set, either on the command or in the current
+    /// process which the command inherits from, are skipped unless the loader
+    /// was told to [override](Loader::override_existing) them.
+    ///
+    /// # Parameters
+    /// - `command` - the command to set the environment variables of
+    ///
+    /// # Returns
+    /// - `Vec<String>`: the names of the environment variables that were set,
+    ///   or would have been set in [dry run](Loader::dry_run) mode
+    pub fn apply_to_command(&self, command: &mut Command) -> Vec<String> {
+        let mut applied = Vec::new();
+
+        for (name, value) in &self.vars {
+            if !self.override_existing {
+                let set_on_command = command
+                    .get_envs()
+                    .any(|(k, v)| k == name.as_str() && v.is_some());
+
+                if set_on_command || std::env::var_os(name).is_some() {
+                    continue;
+                }
+            }
+
+            if !self.dry_run {
+                command.env(name, value);
+            }
+
+            applied.push(name.clone());
+        }
+
+        applied
+    }
+}
+
+impl From<ResolvedEnvs> for HashMap<String, String> {
+    fn from(envs: ResolvedEnvs) -> Self {
+        envs.vars.into_iter().collect()
+    }
+}
+
+impl IntoIterator for ResolvedEnvs {
+    type Item = (String, String);
+    type IntoIter = indexmap::map::IntoIter<String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vars.into_iter()
+    }
+}
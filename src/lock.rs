@@ -0,0 +1,136 @@
+//! Advisory locking of profiles
+//!
+//! Loading a profile, changing it and pushing the changes is not atomic. When
+//! two processes do this at the same time, the changes of one of them are
+//! lost. A [ProfileLock] prevents this as long as every writer acquires it
+//! before loading the profile and holds it until the changes are pushed.
+//!
+//! The lock is advisory, it does not prevent other programs from reading or
+//! writing the profile file.
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+
+use crate::error::Result;
+use crate::store::FsStore;
+
+/// Exclusive lock on a profile, released when dropped
+///
+/// The lock is taken on a separate `.<profile>.lock` file next to the profile
+/// file, since the profile file itself is replaced on every write.
+#[derive(Debug)]
+pub struct ProfileLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl ProfileLock {
+    /// Lock a profile, waiting for other processes holding the lock to release
+    /// it
+    ///
+    /// The profile does not have to exist yet, so that its creation can be
+    /// locked as well.
+    ///
+    /// # Parameters
+    /// - `profile_name` - the name of the profile or the path to the profile
+    ///   file
+    ///
+    /// # Returns
+    /// - `Result<ProfileLock>`: the lock
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::lock::ProfileLock;
+    ///
+    /// let _lock = ProfileLock::acquire("my-profile").unwrap();
+    ///
+    /// let mut profile = envio::load_profile!("my-profile").unwrap();
+    /// profile.insert_env("NEW_ENV".to_string(), "new_value".to_string());
+    /// profile.push_changes().unwrap();
+    /// ```
+    pub fn acquire(profile_name: &str) -> Result<ProfileLock> {
+        ProfileLock::acquire_path(&FsStore::from_datadir()?.profile_path(profile_name))
+    }
+
+    /// Lock a profile if no other process holds the lock
+    ///
+    /// # Parameters
+    /// - `profile_name` - the name of the profile or the path to the profile
+    ///   file
+    ///
+    /// # Returns
+    /// - `Result<Option<ProfileLock>>`: the lock or `None` if another process
+    ///   holds it
+    pub fn try_acquire(profile_name: &str) -> Result<Option<ProfileLock>> {
+        ProfileLock::try_acquire_path(&FsStore::from_datadir()?.profile_path(profile_name))
+    }
+
+    /// Lock the profile stored in a profile file, waiting for other processes
+    /// holding the lock to release it
+    pub(crate) fn acquire_path(profile_file_path: &Path) -> Result<ProfileLock> {
+        let (file, path) = ProfileLock::open(profile_file_path)?;
+        FileExt::lock_exclusive(&file)?;
+
+        Ok(ProfileLock { file, path })
+    }
+
+    /// Lock the profile stored in a profile file if no other process holds the
+    /// lock
+    pub(crate) fn try_acquire_path(profile_file_path: &Path) -> Result<Option<ProfileLock>> {
+        let (file, path) = ProfileLock::open(profile_file_path)?;
+
+        match FileExt::try_lock_exclusive(&file) {
+            Ok(()) => Ok(Some(ProfileLock { file, path })),
+            Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get the path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the path of the lock file belonging to a profile file
+    ///
+    /// # Parameters
+    /// - `profile_file_path` - the path to the profile file
+    ///
+    /// # Returns
+    /// - `PathBuf`: the path to the lock file
+    pub fn file_path(profile_file_path: &Path) -> PathBuf {
+        let stem = profile_file_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        profile_file_path.with_file_name(format!(".{}.lock", stem))
+    }
+
+    fn open(profile_file_path: &Path) -> Result<(File, PathBuf)> {
+        let path = ProfileLock::file_path(profile_file_path);
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        Ok((file, path))
+    }
+}
+
+impl Drop for ProfileLock {
+    fn drop(&mut self) {
+        // The lock file is kept, removing it would allow another process to
+        // lock a new file while the old one is still locked
+        let _ = FileExt::unlock(&self.file);
+    }
+}
@@ -0,0 +1,109 @@
+//! Descriptive information about a profile
+//!
+//! The metadata of a profile is stored in plain text next to the profile file
+//! (`my-profile.env` has its metadata in `my-profile.meta`) so that it can be
+//! displayed without having to decrypt the profile. It must therefore never
+//! contain any secrets.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::utils::write_atomic;
+
+/// Extension of the file the metadata of a profile is stored in
+pub const METADATA_FILE_EXTENSION: &str = "meta";
+
+/// Metadata of a profile
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProfileMetadata {
+    /// What the profile is used for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Who to contact about the profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Free-form notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// When the profile was first written, `None` for profiles created before
+    /// envio kept track of it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the profile was last written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The encryption type the profile was last written with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_type: Option<String>,
+    /// How many environment variables the profile held when it was last
+    /// written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_count: Option<usize>,
+    /// The expiration dates of the environment variables that have one, by
+    /// name, when the profile was last written
+    ///
+    /// They let `envio expiring` check a profile without decrypting it. Only
+    /// the names are recorded, never the values. `None` for profiles written
+    /// before envio kept track of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_dates: Option<BTreeMap<String, NaiveDate>>,
+}
+
+impl ProfileMetadata {
+    /// Get the path of the metadata file belonging to a profile file
+    ///
+    /// # Parameters
+    /// - `profile_file_path` - the path to the profile file
+    ///
+    /// # Returns
+    /// - `PathBuf`: the path to the metadata file
+    pub fn file_path(profile_file_path: &Path) -> PathBuf {
+        profile_file_path.with_extension(METADATA_FILE_EXTENSION)
+    }
+
+    /// Read the metadata belonging to a profile file
+    ///
+    /// # Parameters
+    /// - `profile_file_path` - the path to the profile file
+    ///
+    /// # Returns
+    /// - `Result<ProfileMetadata>`: the metadata, empty if the profile does not
+    ///   have a metadata file
+    pub fn load(profile_file_path: &Path) -> Result<ProfileMetadata> {
+        let path = ProfileMetadata::file_path(profile_file_path);
+
+        if !path.exists() {
+            return Ok(ProfileMetadata::default());
+        }
+
+        toml::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| Error::Deserialization(e.to_string()))
+    }
+
+    /// Write the metadata belonging to a profile file
+    ///
+    /// # Parameters
+    /// - `profile_file_path` - the path to the profile file
+    ///
+    /// # Returns
+    /// - `Result<()>`: whether the operation was successful
+    pub fn save(&self, profile_file_path: &Path) -> Result<()> {
+        let content = toml::to_string(self).map_err(|e| Error::Serialization(e.to_string()))?;
+
+        write_atomic(
+            &ProfileMetadata::file_path(profile_file_path),
+            content.as_bytes(),
+        )
+    }
+
+    /// Record that the profile has been modified just now
+    pub fn touch(&mut self) {
+        let now = Utc::now();
+
+        self.created_at.get_or_insert(now);
+        self.modified_at = Some(now);
+    }
+}
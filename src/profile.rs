@@ -1,15 +1,48 @@
-use std::collections::HashMap;
-use std::{io::Write, path::PathBuf};
-
-use chrono::NaiveDate;
-use colored::Colorize;
-use inquire::Confirm;
-use serde::{Deserialize, Serialize};
-
-use crate::utils::{self, get_configdir, truncate_identity_bytes};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::de::{self, DeserializeOptions};
+use crate::format::{self, LoadOptions, LoadReport, LoadWarning, CURRENT_FORMAT_VERSION};
+use crate::metadata::ProfileMetadata;
+use crate::store::{self, FsStore, ProfileStore, PROFILE_FILE_EXTENSION};
+use crate::utils::{contains_path_separator, truncate_identity_bytes};
 
 use crate::crypto::EncryptionType;
 use crate::error::{Error, Result};
+use crate::schema::{Schema, Violation};
+
+/// Fragments of an environment variable name that indicate that its value is a
+/// secret
+const SENSITIVE_NAME_PATTERNS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "PASSPHRASE",
+    "SECRET",
+    "TOKEN",
+    "PRIVATE_KEY",
+    "API_KEY",
+    "ACCESS_KEY",
+    "CREDENTIAL",
+];
+
+/// How the value of an environment variable is stored
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValueEncoding {
+    /// The value is stored as is
+    #[default]
+    Text,
+    /// The value is binary data stored as base64
+    Base64,
+}
 
 /// Representation of an environment variable
 #[derive(Serialize, Deserialize, Clone)]
@@ -18,74 +51,285 @@ pub struct Env {
     pub value: String,
     pub comment: Option<String>,
     pub expiration_date: Option<NaiveDate>,
+    /// Whether the value is a secret that should be masked when displayed
+    pub sensitive: bool,
+    /// How `value` is encoded, binary data is stored as base64
+    pub encoding: ValueEncoding,
+    /// Labels used to select a subset of the environment variables, see
+    /// [Loader::tags](crate::Loader::tags)
+    pub tags: Vec<String>,
+    /// When the value was last rotated, `None` if it never was
+    pub rotated_at: Option<DateTime<Utc>>,
 }
 
 impl Env {
+    /// Create a new environment variable
+    ///
+    /// The variable is classified as sensitive or not based on its name, see
+    /// [is_sensitive_name](Env::is_sensitive_name)
     pub fn new(
         name: String,
         value: String,
         comment: Option<String>,
         expiration_date: Option<NaiveDate>,
     ) -> Env {
+        let sensitive = Env::is_sensitive_name(&name);
+
         Env {
             name,
             value,
             comment,
             expiration_date,
+            sensitive,
+            encoding: ValueEncoding::Text,
+            tags: Vec::new(),
+            rotated_at: None,
         }
     }
 
     pub fn from_key_value(key: String, value: String) -> Env {
-        Env {
-            name: key,
-            value,
-            comment: None,
-            expiration_date: None,
+        Env::new(key, value, None, None)
+    }
+
+    /// Create a new environment variable from raw bytes, e.g. the content of a
+    /// file
+    ///
+    /// Valid UTF-8 is stored as text, including multi-line values such as PEM
+    /// certificates. Anything else is stored as base64 and marked as such.
+    ///
+    /// # Parameters
+    /// - `key` - The name of the environment variable
+    /// - `bytes` - The value of the environment variable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::{Env, ValueEncoding};
+    ///
+    /// let env = Env::from_bytes("KEYSTORE".to_string(), &[0xfe, 0xed, 0xfe, 0xed]);
+    ///
+    /// assert_eq!(env.encoding, ValueEncoding::Base64);
+    /// assert_eq!(env.decoded_value().unwrap(), vec![0xfe, 0xed, 0xfe, 0xed]);
+    /// ```
+    pub fn from_bytes(key: String, bytes: &[u8]) -> Env {
+        match std::str::from_utf8(bytes) {
+            Ok(value) => Env::from_key_value(key, value.to_string()),
+            Err(_) => {
+                let mut env = Env::from_key_value(key, BASE64.encode(bytes));
+                env.encoding = ValueEncoding::Base64;
+                env
+            }
         }
     }
+
+    /// Get the raw bytes of the value, decoding it if it is stored as base64
+    ///
+    /// # Returns
+    /// - `Result<Vec<u8>>`: the decoded value
+    pub fn decoded_value(&self) -> Result<Vec<u8>> {
+        match self.encoding {
+            ValueEncoding::Text => Ok(self.value.as_bytes().to_vec()),
+            ValueEncoding::Base64 => BASE64
+                .decode(&self.value)
+                .map_err(|e| Error::Deserialization(e.to_string())),
+        }
+    }
+
+    /// Check to see if the environment variable is labeled with `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Check to see if the value of the environment variable is binary data
+    pub fn is_binary(&self) -> bool {
+        self.encoding == ValueEncoding::Base64
+    }
+
+    /// Check to see if the value of the environment variable spans multiple
+    /// lines
+    pub fn is_multiline(&self) -> bool {
+        self.value.contains('\n')
+    }
+
+    /// Check to see if the environment variable has expired
+    ///
+    /// An environment variable expires at the start of its expiration date
+    ///
+    /// # Returns
+    /// - `bool`: indicating whether the environment variable has expired or not
+    pub fn is_expired(&self) -> bool {
+        self.days_until_expiration().is_some_and(|days| days <= 0)
+    }
+
+    /// Get the number of days left until the environment variable expires
+    ///
+    /// # Returns
+    /// - `Option<i64>`: the number of days left, zero or negative if the
+    ///   environment variable has already expired and `None` if it does not
+    ///   have an expiration date
+    pub fn days_until_expiration(&self) -> Option<i64> {
+        self.expiration_date
+            .map(|date| (date - Local::now().date_naive()).num_days())
+    }
+
+    /// Check to see if the name of an environment variable suggests that its
+    /// value is a secret
+    ///
+    /// The check is case insensitive and matches names such as `GITHUB_TOKEN`,
+    /// `DB_PASSWORD`, `AWS_SECRET_ACCESS_KEY` or `SIGNING_KEY`
+    ///
+    /// # Parameters
+    /// - `name` - The name of the environment variable
+    ///
+    /// # Returns
+    /// - `bool`: indicating whether the name looks sensitive or not
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::Env;
+    ///
+    /// assert!(Env::is_sensitive_name("GITHUB_TOKEN"));
+    /// assert!(!Env::is_sensitive_name("PORT"));
+    /// ```
+    pub fn is_sensitive_name(name: &str) -> bool {
+        let name = name.to_uppercase();
+
+        SENSITIVE_NAME_PATTERNS
+            .iter()
+            .any(|pattern| name.contains(pattern))
+            || name.ends_with("_KEY")
+            || name.ends_with("_PWD")
+    }
 }
 
-/// Wrapper around a vector of `Env`
-#[derive(Serialize, Deserialize, Clone)]
+/// What to do with expired environment variables when a profile is used
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExpirationPolicy {
+    /// Only warn about the expired environment variables
+    #[default]
+    Warn,
+    /// Refuse to use the profile while it contains expired environment
+    /// variables
+    Block,
+    /// Leave the expired environment variables out when the profile is loaded
+    Strip,
+}
+
+impl ExpirationPolicy {
+    /// Get the string representation of the policy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpirationPolicy::Warn => "warn",
+            ExpirationPolicy::Block => "block",
+            ExpirationPolicy::Strip => "strip",
+        }
+    }
+}
+
+impl fmt::Display for ExpirationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExpirationPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "warn" => Ok(ExpirationPolicy::Warn),
+            "block" => Ok(ExpirationPolicy::Block),
+            "strip" => Ok(ExpirationPolicy::Strip),
+            _ => Err(Error::InvalidExpirationPolicy(s.to_string())),
+        }
+    }
+}
+
+/// Collection of `Env` keyed by their name
+///
+/// The environment variables keep the order in which they were inserted and
+/// every key is unique. [insert](EnvVec::insert) (and [push](EnvVec::push))
+/// overwrite an existing environment variable with the same key, while
+/// [try_insert](EnvVec::try_insert) returns an error instead.
+#[derive(Deserialize, Clone)]
+#[serde(from = "EnvVecData<Env>")]
 pub struct EnvVec {
-    envs: Vec<Env>,
+    envs: IndexMap<String, Env>,
+}
+
+/// Serialized representation of an `EnvVec`
+///
+/// This is the layout profiles have always been stored in, a list of `Env`.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EnvVecData<E> {
+    pub(crate) envs: Vec<E>,
+}
+
+impl Serialize for EnvVec {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        EnvVecData {
+            envs: self.envs.values().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Profiles written before keys were unique may contain duplicates, the last
+/// occurrence of a key wins
+impl From<EnvVecData<Env>> for EnvVec {
+    fn from(data: EnvVecData<Env>) -> Self {
+        data.envs.into()
+    }
 }
 
 /// Build a `EnvVec` from a `Vec<Env>` or a `HashMap<String, String>`
+///
+/// If a key occurs more than once the last occurrence wins.
 impl From<Vec<Env>> for EnvVec {
     fn from(envs: Vec<Env>) -> Self {
-        EnvVec { envs }
+        envs.into_iter().collect()
     }
 }
 
 impl From<HashMap<String, String>> for EnvVec {
     fn from(envs: HashMap<String, String>) -> Self {
-        let mut env_vec = EnvVec::new();
-
-        for (key, value) in envs {
-            env_vec.push(Env::from_key_value(key, value));
-        }
-
-        env_vec
+        envs.into_iter()
+            .map(|(key, value)| Env::from_key_value(key, value))
+            .collect()
     }
 }
 
 /// Convert a `EnvVec` to a `Vec<Env>` or a `HashMap<String, String>`
 impl From<EnvVec> for Vec<Env> {
     fn from(val: EnvVec) -> Self {
-        val.envs
+        val.envs.into_values().collect()
     }
 }
 
 impl From<EnvVec> for HashMap<String, String> {
     fn from(val: EnvVec) -> Self {
-        let mut envs = HashMap::new();
+        val.envs.into_iter().map(|(k, e)| (k, e.value)).collect()
+    }
+}
 
-        for e in val.envs {
-            envs.insert(e.name, e.value);
-        }
+impl FromIterator<Env> for EnvVec {
+    fn from_iter<I: IntoIterator<Item = Env>>(iter: I) -> Self {
+        let mut env_vec = EnvVec::new();
+        env_vec.extend(iter);
+        env_vec
+    }
+}
 
-        envs
+/// Insert all the environment variables, overwriting existing ones
+impl Extend<Env> for EnvVec {
+    fn extend<I: IntoIterator<Item = Env>>(&mut self, iter: I) {
+        for env in iter {
+            self.insert(env);
+        }
     }
 }
 
@@ -97,11 +341,73 @@ impl Default for EnvVec {
 
 impl EnvVec {
     pub fn new() -> EnvVec {
-        EnvVec { envs: Vec::new() }
+        EnvVec {
+            envs: IndexMap::new(),
+        }
+    }
+
+    /// Insert an environment variable into the `EnvVec`
+    ///
+    /// If an environment variable with the same key already exists it is
+    /// replaced and keeps its position.
+    ///
+    /// # Parameters
+    /// - `env` - The environment variable to insert
+    ///
+    /// # Returns
+    /// - `Option<Env>`: The environment variable that was replaced, if any
+    ///
+    /// # Examples
+    /// ```
+    /// use envio::EnvVec;
+    ///
+    /// let mut envs = EnvVec::new();
+    ///
+    /// envs.insert(envio::Env::from_key_value("NEW_ENV".to_string(), "OLD_VALUE".to_string()));
+    /// let old = envs.insert(envio::Env::from_key_value("NEW_ENV".to_string(), "NEW_VALUE".to_string()));
+    ///
+    /// assert_eq!(old.unwrap().value, "OLD_VALUE");
+    /// assert_eq!(envs.len(), 1);
+    /// ```
+    pub fn insert(&mut self, env: Env) -> Option<Env> {
+        self.envs.insert(env.name.clone(), env)
+    }
+
+    /// Insert an environment variable into the `EnvVec` unless its key already
+    /// exists
+    ///
+    /// # Parameters
+    /// - `env` - The environment variable to insert
+    ///
+    /// # Returns
+    /// - `Result<()>`: an [EnvExists](Error::EnvExists) error if the key
+    ///   already exists, the `EnvVec` is left untouched in that case
+    ///
+    /// # Examples
+    /// ```
+    /// use envio::EnvVec;
+    ///
+    /// let mut envs = EnvVec::new();
+    ///
+    /// envs.try_insert(envio::Env::from_key_value("NEW_ENV".to_string(), "NEW_VALUE".to_string())).unwrap();
+    ///
+    /// assert!(envs.try_insert(envio::Env::from_key_value("NEW_ENV".to_string(), "OTHER_VALUE".to_string())).is_err());
+    /// ```
+    pub fn try_insert(&mut self, env: Env) -> Result<()> {
+        match self.envs.entry(env.name.clone()) {
+            indexmap::map::Entry::Occupied(_) => Err(Error::EnvExists(env.name)),
+            indexmap::map::Entry::Vacant(entry) => {
+                entry.insert(env);
+                Ok(())
+            }
+        }
     }
 
     /// Add a new environment variable to the `EnvVec`
     ///
+    /// Same as [insert](EnvVec::insert), an existing environment variable with
+    /// the same key is overwritten.
+    ///
     /// # Parameters
     /// - `env` - The environment variable to add. Has to be an instance of the
     ///   [Env](crate::Env) struct
@@ -116,14 +422,41 @@ impl EnvVec {
     ///
     /// ```
     pub fn push(&mut self, env: Env) {
-        self.envs.push(env);
+        self.insert(env);
+    }
+
+    /// Get the entry of a key for in-place manipulation
+    ///
+    /// # Parameters
+    /// - `key` - The key of the environment variable
+    ///
+    /// # Examples
+    /// ```
+    /// use envio::EnvVec;
+    ///
+    /// let mut envs = EnvVec::new();
+    ///
+    /// envs.entry("COUNTER".to_string())
+    ///     .and_modify(|mut e| e.value_mut().push('1'))
+    ///     .or_insert("0".to_string());
+    ///
+    /// assert_eq!(envs.get("COUNTER").unwrap(), "0");
+    /// ```
+    pub fn entry(&mut self, key: String) -> EnvEntry<'_> {
+        EnvEntry {
+            inner: self.envs.entry(key),
+        }
     }
 
     /// Remove an environment variable from the `EnvVec`
     ///
+    /// The order of the remaining environment variables is preserved.
+    ///
     /// # Parameters
-    /// - `env` - The name of the environment variable to remove. Has to be an
-    ///   instance of the [Env](crate::Env) struct
+    /// - `env` - The name of the environment variable to remove
+    ///
+    /// # Returns
+    /// - `Option<Env>`: The removed environment variable, if it existed
     ///
     /// # Examples
     ///
@@ -137,18 +470,34 @@ impl EnvVec {
     /// envs.remove("NEW_ENV");
     ///
     /// ```
-    pub fn remove(&mut self, env: &str) {
-        self.envs.retain(|e| e.name != env);
+    pub fn remove(&mut self, env: &str) -> Option<Env> {
+        self.envs.shift_remove(env)
     }
 
-    /// Return an iterator over the `EnvVec`
-    pub fn iter(&self) -> std::slice::Iter<Env> {
-        self.envs.iter()
+    /// Return an iterator over the `EnvVec` in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &Env> {
+        self.envs.values()
+    }
+
+    /// Return a mutable iterator over the `EnvVec` in insertion order
+    ///
+    /// Everything but the name of the environment variables can be changed,
+    /// see [EnvMut].
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = EnvMut<'_>> {
+        self.envs.values_mut().map(|env| EnvMut { env })
     }
 
-    /// Return a mutable iterator over the `EnvVec`
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<Env> {
-        self.envs.iter_mut()
+    /// Return an iterator over the `EnvVec` sorted by key, the `EnvVec` itself
+    /// is left untouched
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &Env> {
+        let mut envs: Vec<&Env> = self.envs.values().collect();
+        envs.sort_by(|a, b| a.name.cmp(&b.name));
+        envs.into_iter()
+    }
+
+    /// Sort the `EnvVec` by key
+    pub fn sort_keys(&mut self) {
+        self.envs.sort_keys();
     }
 
     /// Return a vector of all the keys in the `EnvVec`
@@ -172,7 +521,7 @@ impl EnvVec {
     /// }
     /// ```
     pub fn keys(&self) -> Vec<String> {
-        self.envs.iter().map(|e| e.name.clone()).collect()
+        self.envs.keys().cloned().collect()
     }
 
     /// Check to see if an environment variable with the given key exists in the
@@ -203,7 +552,7 @@ impl EnvVec {
     /// }
     /// ```
     pub fn contains_key(&self, key: &str) -> bool {
-        self.envs.iter().any(|e| e.name == key)
+        self.envs.contains_key(key)
     }
 
     /// Get the value of an environment variable with the given key
@@ -232,13 +581,46 @@ impl EnvVec {
     /// }
     /// ```
     pub fn get(&self, key: &str) -> Option<&String> {
-        for e in self.envs.iter() {
-            if e.name == key {
-                return Some(&e.value);
-            }
-        }
+        self.envs.get(key).map(|e| &e.value)
+    }
 
-        None
+    /// Get the environment variable with the given key
+    ///
+    /// # Parameters
+    /// - `key` - The key of the environment variable
+    ///
+    /// # Returns
+    /// - `Option<&Env>`: The environment variable if it exists
+    pub fn get_env(&self, key: &str) -> Option<&Env> {
+        self.envs.get(key)
+    }
+
+    /// Get mutable access to the environment variable with the given key
+    ///
+    /// Everything but the name of the environment variable can be changed,
+    /// see [EnvMut].
+    ///
+    /// # Parameters
+    /// - `key` - The key of the environment variable
+    ///
+    /// # Returns
+    /// - `Option<EnvMut>`: The environment variable if it exists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::EnvVec;
+    ///
+    /// let mut envs = EnvVec::new();
+    ///
+    /// envs.push(envio::Env::from_key_value("NEW_ENV".to_string(), "NEW_VALUE".to_string()));
+    ///
+    /// if let Some(mut env) = envs.get_mut("NEW_ENV") {
+    ///     *env.comment_mut() = Some("Updated".to_string());
+    /// }
+    /// ```
+    pub fn get_mut(&mut self, key: &str) -> Option<EnvMut<'_>> {
+        self.envs.get_mut(key).map(|env| EnvMut { env })
     }
 
     /// Check to see if the `EnvVec` is empty
@@ -274,39 +656,135 @@ impl EnvVec {
     ///
     /// envs.retain(|e| e.name == "NEW_ENV"); // Only keep the environment variable with the key "NEW_ENV"
     /// ```
-    pub fn retain<F>(&mut self, f: F)
+    pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&Env) -> bool,
     {
-        self.envs.retain(f);
+        self.envs.retain(|_, e| f(e));
+    }
+}
+
+/// A view into a single key of an [EnvVec], obtained with
+/// [entry](EnvVec::entry)
+pub struct EnvEntry<'a> {
+    inner: indexmap::map::Entry<'a, String, Env>,
+}
+
+impl<'a> EnvEntry<'a> {
+    /// Get the key of the entry
+    pub fn key(&self) -> &str {
+        self.inner.key()
+    }
+
+    /// Check to see if the key already has an environment variable
+    pub fn is_occupied(&self) -> bool {
+        matches!(self.inner, indexmap::map::Entry::Occupied(_))
+    }
+
+    /// Modify the environment variable if the key is occupied
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(EnvMut<'_>),
+    {
+        EnvEntry {
+            inner: self.inner.and_modify(|env| f(EnvMut { env })),
+        }
+    }
+
+    /// Insert an environment variable with the given value if the key is
+    /// vacant
+    ///
+    /// # Returns
+    /// - `EnvMut`: the environment variable of the key
+    pub fn or_insert(self, value: String) -> EnvMut<'a> {
+        self.or_insert_with(|| value)
+    }
+
+    /// Insert an environment variable with the value returned by `f` if the
+    /// key is vacant
+    ///
+    /// # Returns
+    /// - `EnvMut`: the environment variable of the key
+    pub fn or_insert_with<F>(self, f: F) -> EnvMut<'a>
+    where
+        F: FnOnce() -> String,
+    {
+        let env = match self.inner {
+            indexmap::map::Entry::Occupied(entry) => entry.into_mut(),
+            indexmap::map::Entry::Vacant(entry) => {
+                let env = Env::from_key_value(entry.key().clone(), f());
+                entry.insert(env)
+            }
+        };
+
+        EnvMut { env }
+    }
+}
+
+/// Mutable access to an environment variable of an [EnvVec]
+///
+/// The name of the environment variable is its key in the `EnvVec` and can
+/// not be changed, remove the environment variable and insert it under the new
+/// name instead. Everything else can be changed through the `*_mut` methods,
+/// and the environment variable can be read through `Deref`.
+pub struct EnvMut<'a> {
+    env: &'a mut Env,
+}
+
+impl EnvMut<'_> {
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.env.value
+    }
+
+    pub fn comment_mut(&mut self) -> &mut Option<String> {
+        &mut self.env.comment
+    }
+
+    pub fn expiration_date_mut(&mut self) -> &mut Option<NaiveDate> {
+        &mut self.env.expiration_date
+    }
+
+    pub fn sensitive_mut(&mut self) -> &mut bool {
+        &mut self.env.sensitive
+    }
+
+    pub fn encoding_mut(&mut self) -> &mut ValueEncoding {
+        &mut self.env.encoding
+    }
+
+    pub fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.env.tags
+    }
+
+    pub fn rotated_at_mut(&mut self) -> &mut Option<DateTime<Utc>> {
+        &mut self.env.rotated_at
+    }
+}
+
+impl std::ops::Deref for EnvMut<'_> {
+    type Target = Env;
+
+    fn deref(&self) -> &Env {
+        self.env
     }
 }
 
 /// Allow users to iterate over the `EnvVec` struct
 impl IntoIterator for EnvVec {
     type Item = Env;
-    type IntoIter = std::vec::IntoIter<Env>;
+    type IntoIter = indexmap::map::IntoValues<String, Env>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.envs.into_iter()
+        self.envs.into_values()
     }
 }
 
 impl<'a> IntoIterator for &'a EnvVec {
     type Item = &'a Env;
-    type IntoIter = std::slice::Iter<'a, Env>;
+    type IntoIter = indexmap::map::Values<'a, String, Env>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.envs.iter()
-    }
-}
-
-impl<'a> IntoIterator for &'a mut EnvVec {
-    type Item = &'a mut Env;
-    type IntoIter = std::slice::IterMut<'a, Env>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.envs.iter_mut()
+        self.envs.values()
     }
 }
 
@@ -317,13 +795,13 @@ impl<'a> IntoIterator for &'a mut EnvVec {
 ///
 /// The way that envio loads a profile is it first reads the encrypted contents
 /// of the profile file and then passes that to the
-/// [from_content](Profile::from_content) method of the `Profile` struct along
+/// [from_bytes](Profile::from_bytes) method of the `Profile` struct along
 /// with the name of the profile and the encryption type used to encrypt the
 /// profile.
 ///
 /// The encryption type can be deduced using the
-/// [get_encryption_type](crate::crypto::get_encryption_type) function from the
-/// `crypto` module. It is recommended to first take a look at the documentation
+/// [get_encryption_type_from_bytes](crate::crypto::get_encryption_type_from_bytes)
+/// function from the `crypto` module. It is recommended to first take a look at the documentation
 /// for the [crypto](crate::crypto) module before proceeding. Since depending on
 /// the encryption type used, the user might have to provide a key to decrypt
 /// the profile.
@@ -352,13 +830,15 @@ impl<'a> IntoIterator for &'a mut EnvVec {
 ///   let encrypted_content = envio::utils::get_profile_content("my-profile").unwrap();
 ///
 ///   // Load the profile assuming the encryption type is `age`
-///   let mut profile = Profile::from_content("my-profile", &encrypted_content, envio::crypto::get_encryption_type(&encrypted_content).unwrap()).unwrap();
+///   let mut encryption_type = envio::crypto::get_encryption_type_from_bytes(&encrypted_content).unwrap();
+///   encryption_type.set_key("my-key".to_string());
+///   let mut profile = Profile::from_bytes("my-profile", &encrypted_content, encryption_type).unwrap();
 ///   
 ///   // Or use the load_profile macro
 ///   let mut profile = envio::load_profile!("my-profile").unwrap();
 ///
-///   for (key, value) in profile.envs.iter() {
-///    println!("{}={}", key, value);
+///   for env in profile.envs.iter() {
+///    println!("{}={}", env.name, env.value);
 ///   }
 ///
 ///   // Add a new environment variable to the profile
@@ -371,8 +851,24 @@ impl<'a> IntoIterator for &'a mut EnvVec {
 pub struct Profile {
     pub name: String,
     pub envs: EnvVec,
+    /// Optional schema the environment variables are validated against
+    pub schema: Option<Schema>,
+    /// Policy for expired environment variables, `None` to use the global one
+    pub expiration_policy: Option<ExpirationPolicy>,
+    /// Stored in a separate plain text file, see [ProfileMetadata]
+    #[serde(skip)]
+    pub metadata: ProfileMetadata,
     pub profile_file_path: PathBuf,
     encryption_type: Box<dyn EncryptionType>,
+    /// Where the profile is pushed to, `profile_file_path` when `None`
+    #[serde(skip)]
+    store: Option<StoreLocation>,
+}
+
+/// A profile of a [ProfileStore]
+struct StoreLocation {
+    store: Arc<dyn ProfileStore>,
+    name: String,
 }
 
 impl Profile {
@@ -385,8 +881,12 @@ impl Profile {
         Profile {
             name,
             envs,
+            schema: None,
+            expiration_policy: None,
+            metadata: ProfileMetadata::default(),
             profile_file_path,
             encryption_type,
+            store: None,
         }
     }
 
@@ -395,6 +895,9 @@ impl Profile {
     /// This method is not meant to be used by the end user. It is recommended to
     /// use the [load_profile](crate::load_profile) macro to load a profile.
     ///
+    /// Profiles stored in an older format are migrated in memory without
+    /// asking, use [load](Profile::load) to control this.
+    ///
     /// # Parameters
     /// - `profile_name` - The name of the profile
     /// - `encryption_type` - The encryption type used to encrypt the profile
@@ -418,87 +921,287 @@ impl Profile {
     ///     }
     ///  };
     /// ```
-    pub fn from(
+    pub fn from(profile_name: &str, encryption_type: Box<dyn EncryptionType>) -> Result<Profile> {
+        Profile::load(profile_name, encryption_type, &LoadOptions::default())
+            .map(|(profile, _)| profile)
+    }
+
+    /// Load a profile from the store envio keeps its profiles in, see
+    /// [StoreKind](crate::store::StoreKind), and report the format it was
+    /// stored in
+    ///
+    /// Profiles stored in an older [format](crate::format) are migrated to the
+    /// current one. This never prompts or prints, `options` decides what
+    /// happens with profiles in the plain text format and whether migrated
+    /// profiles are written back. Anything worth telling the user about is
+    /// collected in the [warnings](LoadReport::warnings) of the report.
+    ///
+    /// # Parameters
+    /// - `profile_name` - The name of the profile or the path to the profile
+    ///   file
+    /// - `encryption_type` - The encryption type used to encrypt the profile
+    /// - `options` - How to load the profile
+    ///
+    /// # Returns
+    /// - `Result<(Profile, LoadReport)>`: the profile and how it was loaded
+    ///
+    /// # Examples
+    /// ```
+    /// use envio::format::LoadOptions;
+    /// use envio::Profile;
+    ///
+    /// let encryption_type = envio::crypto::get_encryption_type("my-profile").unwrap();
+    /// let options = LoadOptions::default();
+    ///
+    /// let (profile, report) = Profile::load("my-profile", encryption_type, &options).unwrap();
+    ///
+    /// for warning in &report.warnings {
+    ///     eprintln!("warning: {}", warning);
+    /// }
+    /// ```
+    pub fn load(
         profile_name: &str,
-        mut encryption_type: Box<dyn EncryptionType>,
-    ) -> Result<Profile> {
-        let profile_file_path = utils::get_profile_filepath(profile_name)?;
-        let encrypted_content = std::fs::read(&profile_file_path)?;
+        encryption_type: Box<dyn EncryptionType>,
+        options: &LoadOptions,
+    ) -> Result<(Profile, LoadReport)> {
+        Profile::load_from(
+            store::store_for(profile_name)?,
+            profile_name,
+            encryption_type,
+            options,
+        )
+    }
 
-        let truncated_content = truncate_identity_bytes(&encrypted_content);
+    /// Load a profile from a [ProfileStore] and report the format it was
+    /// stored in
+    ///
+    /// Works like [load](Profile::load), the changes made to the profile are
+    /// pushed back to the same store.
+    ///
+    /// # Parameters
+    /// - `store` - The store the profile is kept in
+    /// - `profile_name` - The name of the profile
+    /// - `encryption_type` - The encryption type used to encrypt the profile
+    /// - `options` - How to load the profile
+    ///
+    /// # Returns
+    /// - `Result<(Profile, LoadReport)>`: the profile and how it was loaded
+    pub fn load_from(
+        store: Arc<dyn ProfileStore>,
+        profile_name: &str,
+        encryption_type: Box<dyn EncryptionType>,
+        options: &LoadOptions,
+    ) -> Result<(Profile, LoadReport)> {
+        let encrypted_content = store.read(profile_name)?;
+
+        let (profile, found_version) = Profile::decode(
+            profile_name,
+            &encrypted_content,
+            store.path(profile_name).unwrap_or_default(),
+            encryption_type,
+            options,
+        )?;
+
+        Profile::finish_load(
+            store,
+            profile_name,
+            profile,
+            found_version,
+            options.write_back,
+        )
+    }
 
-        let content = match encryption_type.decrypt(&truncated_content) {
-            Ok(c) => c,
-            Err(e) => {
-                return Err(e);
-            }
-        };
+    /// Attach a decoded profile to its store, write it back if it was
+    /// migrated and `write_back` is set and report how it was loaded
+    fn finish_load(
+        store: Arc<dyn ProfileStore>,
+        profile_name: &str,
+        mut profile: Profile,
+        found_version: u32,
+        write_back: bool,
+    ) -> Result<(Profile, LoadReport)> {
+        profile.metadata = store.read_metadata(profile_name)?;
+
+        // A profile loaded from a path is pushed back to the same file, see
+        // `store_location`, as stores only write profiles by name
+        if !contains_path_separator(profile_name) {
+            profile.store = Some(StoreLocation {
+                store,
+                name: profile_name.to_string(),
+            });
+        }
 
-        match bincode::deserialize(&content) {
-            Ok(profile) => Ok(profile),
-            Err(_) => {
-                // Profiles created with older versions of envio are not serialized using bincode
-                println!(
-                    "{}",
-                    format!(
-                        "{}: Unable to deserialize the profile content\n\
-                    \n\
-                    This may indicate:\n\
-                     - The file has been tampered with\n\
-                     - It was created with an older version of the tool\n",
-                        "Warning".yellow().bold()
-                    )
-                );
-
-                let prompt =
-                    Confirm::new("Do you want to fallback to the old way of reading the profile?")
-                        .with_default(false)
-                        .with_help_message("If the file has been tampered with, then falling back to the old way of reading the profile will not work")
-                        .prompt();
-
-                let fallback = match prompt {
-                    Ok(f) => f,
-                    Err(e) => return Err(Error::Msg(e.to_string())),
-                };
-
-                if !fallback {
-                    return Err(Error::Deserialization(
-                        "Unable to deserialize the profile content".to_string(),
-                    ));
-                }
-
-                let mut envs = HashMap::new();
-                let string_content = String::from_utf8_lossy(&content);
-                for line in string_content.lines() {
-                    if line.is_empty() {
-                        continue;
-                    }
-
-                    if !line.contains('=') {
-                        encryption_type.set_key(line.to_string());
-                        continue;
-                    }
-
-                    let mut parts = line.splitn(2, '=');
-                    if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
-                        envs.insert(key.to_string(), value.to_string());
-                    }
-                }
-
-                let mut profile = Profile::new(
-                    profile_name.to_owned(),
-                    envs.into(),
-                    profile_file_path,
-                    encryption_type,
-                );
-
-                profile.push_changes()?; // Update the profile file with the new format
-
-                println!("{}", "Fallback successful!".green().bold());
-
-                return Ok(profile);
-            }
+        let migrated = found_version != CURRENT_FORMAT_VERSION;
+        let written_back = migrated && write_back;
+
+        if written_back {
+            profile.push_changes()?;
+        }
+
+        let mut warnings = Vec::new();
+
+        if found_version == 0 {
+            warnings.push(LoadWarning::TextFormat);
         }
+
+        if migrated {
+            warnings.push(LoadWarning::Migrated {
+                from_version: found_version,
+            });
+        }
+
+        let expired: Vec<String> = profile
+            .expired_envs()
+            .iter()
+            .map(|e| e.name.clone())
+            .collect();
+
+        if !expired.is_empty() {
+            warnings.push(LoadWarning::ExpiredEnvs(expired));
+        }
+
+        Ok((
+            profile,
+            LoadReport {
+                found_version,
+                migrated,
+                written_back,
+                warnings,
+            },
+        ))
+    }
+
+    /// Async version of [load](Profile::load)
+    ///
+    /// Reading, decrypting and writing back the profile happens on tokio's
+    /// blocking thread pool, so waiting for GPG's pinentry does not block the
+    /// runtime. [confirm_text_format](LoadOptions::confirm_text_format) is
+    /// called on the current task.
+    ///
+    /// # Parameters
+    /// - `profile_name` - The name of the profile or the path to the profile
+    ///   file
+    /// - `encryption_type` - The encryption type used to encrypt the profile
+    /// - `options` - How to load the profile
+    ///
+    /// # Returns
+    /// - `Result<(Profile, LoadReport)>`: the profile and how it was loaded
+    ///
+    /// # Examples
+    /// ```
+    /// use envio::format::LoadOptions;
+    /// use envio::Profile;
+    ///
+    /// # async fn run() -> envio::error::Result<()> {
+    /// let encryption_type = envio::crypto::get_encryption_type_async("my-profile").await?;
+    ///
+    /// let (profile, _) =
+    ///     Profile::load_async("my-profile", encryption_type, &LoadOptions::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn load_async(
+        profile_name: &str,
+        encryption_type: Box<dyn EncryptionType>,
+        options: &LoadOptions<'_>,
+    ) -> Result<(Profile, LoadReport)> {
+        let name = profile_name.to_string();
+
+        let (store, content, encryption_type) = crate::utils::spawn_blocking(move || {
+            let store = store::store_for(&name)?;
+            let encrypted_content = store.read(&name)?;
+            let content = encryption_type.decrypt(&truncate_identity_bytes(&encrypted_content))?;
+
+            Ok((store, content, encryption_type))
+        })
+        .await?;
+
+        let (profile, found_version) = format::decode(
+            &content,
+            profile_name,
+            store.path(profile_name).unwrap_or_default(),
+            encryption_type,
+            options,
+        )?;
+
+        let name = profile_name.to_string();
+        let write_back = options.write_back;
+
+        crate::utils::spawn_blocking(move || {
+            Profile::finish_load(store, &name, profile, found_version, write_back)
+        })
+        .await
+    }
+
+    /// Create a new profile object from the encrypted content of a profile
+    ///
+    /// Unlike [from](Profile::from) this does not touch the filesystem, the
+    /// content can come from anywhere. Profiles stored in an older
+    /// [format](crate::format) are migrated in memory, use
+    /// [to_bytes](Profile::to_bytes) to get them in the current format.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the profile, used if the content does not store
+    ///   it
+    /// - `bytes` - The encrypted content of the profile
+    /// - `encryption_type` - The encryption type used to encrypt the profile,
+    ///   see [get_encryption_type_from_bytes](crate::crypto::get_encryption_type_from_bytes)
+    ///
+    /// # Returns
+    /// - `Result<Profile>`: the profile object if the operation was successful or an error if it was not
+    ///
+    /// # Examples
+    /// ```
+    /// use envio::crypto::get_encryption_type_from_bytes;
+    /// use envio::Profile;
+    ///
+    /// let bytes = std::fs::read("my-profile.env").unwrap();
+    ///
+    /// let mut encryption_type = get_encryption_type_from_bytes(&bytes).unwrap();
+    /// encryption_type.set_key("my-key".to_string());
+    ///
+    /// let profile = Profile::from_bytes("my-profile", &bytes, encryption_type).unwrap();
+    /// ```
+    pub fn from_bytes(
+        name: &str,
+        bytes: &[u8],
+        encryption_type: Box<dyn EncryptionType>,
+    ) -> Result<Profile> {
+        Profile::decode(
+            name,
+            bytes,
+            PathBuf::new(),
+            encryption_type,
+            &LoadOptions::default(),
+        )
+        .map(|(profile, _)| profile)
+    }
+
+    /// Serialize and encrypt the profile into the content of a profile file
+    ///
+    /// # Returns
+    /// - `Result<Vec<u8>>`: the encrypted content, which can be read back using
+    ///   [from_bytes](Profile::from_bytes)
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let serialized_data = format::encode(self)?;
+
+        self.encryption_type.encrypt(&serialized_data)
+    }
+
+    /// Decrypt the content of a profile and migrate it to the current format
+    fn decode(
+        name: &str,
+        bytes: &[u8],
+        profile_file_path: PathBuf,
+        encryption_type: Box<dyn EncryptionType>,
+        options: &LoadOptions,
+    ) -> Result<(Profile, u32)> {
+        let truncated_content = truncate_identity_bytes(bytes);
+
+        let content = encryption_type.decrypt(&truncated_content)?;
+
+        format::decode(&content, name, profile_file_path, encryption_type, options)
     }
 
     /// Check to see if a profile with the given name exists on the system
@@ -521,19 +1224,58 @@ impl Profile {
     ///   println!("The profile does not exist");
     /// }
     pub fn does_exist(name: &str) -> bool {
-        let configdir = get_configdir();
+        store::store_for(name).is_ok_and(|store| store.exists(name))
+    }
 
-        let profile_path = configdir.join("profiles").join(format!("{}.env", name));
+    /// Push the changes made to the profile to a [ProfileStore] instead of
+    /// the profile file
+    ///
+    /// The profile is stored under its [name](Profile::name).
+    ///
+    /// # Parameters
+    /// - `store` - The store to push the changes to
+    pub fn set_store(&mut self, store: Arc<dyn ProfileStore>) {
+        self.store = Some(StoreLocation {
+            store,
+            name: self.name.clone(),
+        });
+    }
 
-        if profile_path.exists() {
-            return true;
+    /// Get the store the profile is pushed to and the name it is stored under
+    fn store_location(&self) -> Result<(Arc<dyn ProfileStore>, String)> {
+        if let Some(location) = &self.store {
+            return Ok((location.store.clone(), location.name.clone()));
         }
 
-        false
+        // The profile file is kept in a store of its own directory, so that
+        // it is written to exactly where it is
+        let path = &self.profile_file_path;
+
+        let name = match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(ext)) if ext == PROFILE_FILE_EXTENSION => {
+                stem.to_string_lossy().into_owned()
+            }
+            _ => {
+                return Err(Error::Msg(format!(
+                    "Profile file `{}` does not end in .{}",
+                    path.display(),
+                    PROFILE_FILE_EXTENSION
+                )))
+            }
+        };
+
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        Ok((Arc::new(FsStore::new(dir)), name))
     }
 
     /// Add a new environment variable to the profile
     ///
+    /// An existing environment variable with the same name is overwritten.
+    ///
     /// # Parameters
     /// - `env` - The name of the environment variable
     /// - `env_value` - The value of the environment variable
@@ -550,11 +1292,14 @@ impl Profile {
     ///
     /// ```
     pub fn insert_env(&mut self, env: String, env_value: String) {
-        self.envs.push(Env::from_key_value(env, env_value));
+        self.envs.insert(Env::from_key_value(env, env_value));
     }
 
     /// Edit an existing environment variable of the profile
     ///
+    /// The new value is taken as text, to store binary data insert an
+    /// [Env::from_bytes] instead.
+    ///
     /// # Parameters
     /// - `env` - The name of the environment variable
     /// - `new_value` - The new value of the environment variable
@@ -574,16 +1319,14 @@ impl Profile {
     ///
     /// ```
     pub fn edit_env(&mut self, env: String, new_value: String) -> Result<()> {
-        if self.envs.iter().any(|e| e.name == env) {
-            for e in self.envs.iter_mut() {
-                if e.name == env {
-                    e.value = new_value;
-                    return Ok(());
-                }
+        match self.envs.get_mut(&env) {
+            Some(mut e) => {
+                *e.value_mut() = new_value;
+                *e.encoding_mut() = ValueEncoding::Text;
+                Ok(())
             }
+            None => Err(Error::EnvDoesNotExist(env)),
         }
-
-        Err(Error::EnvDoesNotExist(env))
     }
 
     /// Remove an environment variable from the profile
@@ -607,12 +1350,10 @@ impl Profile {
     ///
     /// ```
     pub fn remove_env(&mut self, env: &str) -> Result<()> {
-        if self.envs.iter().any(|e| e.name == env) {
-            self.envs.retain(|e| e.name != env);
-            return Ok(());
+        match self.envs.remove(env) {
+            Some(_) => Ok(()),
+            None => Err(Error::EnvDoesNotExist(env.to_string())),
         }
-
-        Err(Error::EnvDoesNotExist(env.to_string()))
     }
 
     /// Get the value of an environment variable from the profile
@@ -639,13 +1380,114 @@ impl Profile {
     /// }
     /// ```
     pub fn get_env(&self, env: &str) -> Option<&String> {
-        for e in self.envs.iter() {
-            if e.name == env {
-                return Some(&e.value);
-            }
+        self.envs.get(env)
+    }
+
+    /// Validate the environment variables of the profile against its schema
+    ///
+    /// # Returns
+    /// - `Vec<Violation>`: all the violations found, empty if the profile is
+    ///   valid or does not have a schema
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::load_profile;
+    ///
+    /// let profile = load_profile!("my-profile").unwrap();
+    ///
+    /// for violation in profile.validate() {
+    ///     println!("{}", violation);
+    /// }
+    /// ```
+    pub fn validate(&self) -> Vec<Violation> {
+        match &self.schema {
+            Some(schema) => schema.validate(&self.envs),
+            None => Vec::new(),
         }
+    }
+
+    /// Get the environment variables of the profile which have expired
+    ///
+    /// # Returns
+    /// - `Vec<&Env>`: the expired environment variables
+    pub fn expired_envs(&self) -> Vec<&Env> {
+        self.envs.iter().filter(|e| e.is_expired()).collect()
+    }
 
-        None
+    /// Get the expiration dates of the environment variables that have one
+    ///
+    /// # Returns
+    /// - `BTreeMap<String, NaiveDate>`: the expiration dates by name
+    pub fn expiration_dates(&self) -> BTreeMap<String, NaiveDate> {
+        self.envs
+            .iter()
+            .filter_map(|e| Some((e.name.clone(), e.expiration_date?)))
+            .collect()
+    }
+
+    /// Remove the expired environment variables from the profile
+    ///
+    /// The changes are not pushed to the profile file, this is meant to be
+    /// used before loading a profile.
+    ///
+    /// # Returns
+    /// - `Vec<Env>`: the environment variables that were removed
+    pub fn strip_expired(&mut self) -> Vec<Env> {
+        let expired: Vec<Env> = self
+            .envs
+            .iter()
+            .filter(|e| e.is_expired())
+            .cloned()
+            .collect();
+
+        self.envs.retain(|e| !e.is_expired());
+
+        expired
+    }
+
+    /// Deserialize the environment variables of the profile into a type
+    ///
+    /// `DATABASE_URL` is mapped onto a field named `database_url` and
+    /// `DB__HOST` onto the `host` field of a nested `db` struct, see the
+    /// [de](crate::de) module for the details.
+    ///
+    /// # Returns
+    /// - `Result<T>`: the deserialized value or an error naming the
+    ///   environment variable that is missing or invalid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::load_profile;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     database_url: String,
+    ///     port: u16,
+    ///     #[serde(default)]
+    ///     debug: bool,
+    /// }
+    ///
+    /// let profile = load_profile!("my-profile").unwrap();
+    /// let config: Config = profile.deserialize().unwrap();
+    /// ```
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        self.deserialize_with(&DeserializeOptions::default())
+    }
+
+    /// Deserialize the environment variables of the profile into a type, using
+    /// a prefix, a separator or a case conversion other than the defaults
+    ///
+    /// # Parameters
+    /// - `options` - how the names are mapped onto the fields of the type
+    ///
+    /// # Returns
+    /// - `Result<T>`: the deserialized value or an error naming the
+    ///   environment variable that is missing or invalid
+    pub fn deserialize_with<T: DeserializeOwned>(&self, options: &DeserializeOptions) -> Result<T> {
+        de::from_envs(&self.envs, options)
     }
 
     pub fn get_envs_hashmap(&self) -> std::collections::HashMap<String, String> {
@@ -658,7 +1500,18 @@ impl Profile {
         envs
     }
 
-    /// Push the changes made to the profile object to the profile file
+    /// Push the changes made to the profile object to the profile file, or to
+    /// the [ProfileStore] it was loaded from
+    ///
+    /// The modification time of the profile is updated and its
+    /// [metadata](ProfileMetadata) is written next to the profile file.
+    ///
+    /// The profile file is replaced atomically, a crash or a failed encryption
+    /// never leaves a truncated profile behind. To keep concurrent writers from
+    /// overwriting each other's changes, hold a
+    /// [ProfileLock](crate::lock::ProfileLock), or the
+    /// [lock](ProfileStore::lock) of the store, from before the profile is
+    /// loaded until after the changes are pushed.
     ///
     /// # Returns
     /// - `Result<()>`: indicating whether the operation was successful or not
@@ -676,33 +1529,55 @@ impl Profile {
     ///
     /// ```
     pub fn push_changes(&mut self) -> Result<()> {
-        let mut file = std::fs::OpenOptions::new()
-            .write(true)
-            .append(false)
-            .create(true)
-            .open(&self.profile_file_path)?;
-
-        file.set_len(0)?;
-
-        let serialized_data = match bincode::serialize(&self) {
-            Ok(data) => data,
-            Err(e) => {
-                return Err(Error::Serialization(e.to_string()));
-            }
-        };
+        // Encrypt before touching the profile file, so that a failed encryption
+        // leaves the previous version intact
+        let encrypted_data = self.to_bytes()?;
+        let (store, name) = self.store_location()?;
 
-        let encrypted_data = match self.encryption_type.encrypt(&serialized_data) {
-            Ok(data) => data,
-            Err(e) => {
-                return Err(e);
-            }
-        };
+        store.write(&name, &encrypted_data)?;
+
+        self.metadata = self.pushed_metadata();
+        store.write_metadata(&name, &self.metadata)?;
+
+        Ok(())
+    }
+
+    /// Get the metadata of the profile as it is once the changes are pushed
+    fn pushed_metadata(&self) -> ProfileMetadata {
+        let mut metadata = self.metadata.clone();
 
-        file.write_all(&encrypted_data)?;
+        metadata.touch();
+        metadata.encryption_type = Some(self.encryption_type.as_string().to_string());
+        metadata.env_count = Some(self.envs.len());
+        metadata.expiration_dates = Some(self.expiration_dates());
 
-        file.flush()?;
+        metadata
+    }
+
+    /// Async version of [push_changes](Profile::push_changes)
+    ///
+    /// Encrypting and writing the profile happens on tokio's blocking thread
+    /// pool.
+    ///
+    /// # Returns
+    /// - `Result<()>`: indicating whether the operation was successful or not
+    #[cfg(feature = "async")]
+    pub async fn push_changes_async(&mut self) -> Result<()> {
+        let serialized_data = format::encode(self)?;
+        let encryption_type = self.encryption_type.clone_box();
+        let (store, name) = self.store_location()?;
+
+        let metadata = self.pushed_metadata();
 
-        file.sync_all()?;
+        self.metadata = crate::utils::spawn_blocking(move || {
+            let encrypted_data = encryption_type.encrypt(&serialized_data)?;
+
+            store.write(&name, &encrypted_data)?;
+            store.write_metadata(&name, &metadata)?;
+
+            Ok(metadata)
+        })
+        .await?;
 
         Ok(())
     }
@@ -742,8 +1617,8 @@ impl Profile {
 ///    }
 /// };
 ///
-/// for (key, value) in profile.envs.iter() {
-///   println!("{}={}", key, value);
+/// for env in profile.envs.iter() {
+///   println!("{}={}", env.name, env.value);
 /// }
 ///
 /// ```
@@ -765,25 +1640,17 @@ impl Profile {
 ///    }
 /// };
 ///
-/// for (key, value) in profile.envs.iter() {
-///   println!("{}={}", key, value);
+/// for env in profile.envs.iter() {
+///   println!("{}={}", env.name, env.value);
 /// }
 ///
 /// ```
 #[macro_export]
 macro_rules! load_profile {
     ($name:expr $(, $get_key:expr)?) => {
-        (||->envio::error::Result<envio::Profile> {
-            use envio::Profile;
-            use envio::crypto;
-            use envio::utils;
-
-            let mut encryption_type;
-
-            match crypto::get_encryption_type($name) {
-                Ok(t) => encryption_type = t,
-                Err(e) => return Err(e.into()),
-            }
+        (|| -> $crate::error::Result<$crate::Profile> {
+            #[allow(unused_mut)]
+            let mut encryption_type = $crate::crypto::get_encryption_type($name)?;
 
             if encryption_type.as_string() == "age" {
                 $(
@@ -792,10 +1659,7 @@ macro_rules! load_profile {
                 )?
             }
 
-            match Profile::from($name, encryption_type) {
-                Ok(profile) => return Ok(profile),
-                Err(e) => return Err(e.into()),
-            }
-         })()
+            $crate::Profile::from($name, encryption_type)
+        })()
     };
 }
@@ -0,0 +1,392 @@
+//! Validation schemas for the environment variables of a profile
+//!
+//! A [Schema] is optional and stored inside of the profile. It declares for
+//! each key the type of value it holds, whether the key is required and the
+//! default value to use when the key is missing. Keys that are not part of the
+//! schema are not validated.
+//!
+//! Schemas are written in TOML, one table per key:
+//!
+//! ```toml
+//! [PORT]
+//! type = "int"
+//! required = true
+//! default = 8080
+//!
+//! [LOG_LEVEL]
+//! type = "enum"
+//! values = ["debug", "info", "warn", "error"]
+//!
+//! [RELEASE]
+//! type = "regex"
+//! pattern = "v[0-9]+\\.[0-9]+\\.[0-9]+"
+//! ```
+//!
+//! The supported types are `int`, `bool`, `url`, `enum`, `regex` and
+//! `duration`.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::utils::parse_duration;
+use crate::{Env, EnvVec};
+
+/// Type of the value of an environment variable
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ValueType {
+    /// A signed 64 bit integer
+    Int,
+    /// `true`, `false`, `1`, `0`, `yes`, `no`, `on` or `off`
+    Bool,
+    /// An absolute URL
+    Url,
+    /// One of the given values
+    Enum(Vec<String>),
+    /// A value that fully matches the given regular expression
+    Regex(String),
+    /// A duration such as `30s`, `5m` or `1h30m`
+    Duration,
+}
+
+impl ValueType {
+    /// Check to see if `value` is a valid value of this type
+    ///
+    /// # Parameters
+    /// - `value` - the value to check
+    ///
+    /// # Returns
+    /// - `std::result::Result<(), String>`: the reason why the value is
+    ///   invalid if it is not valid
+    pub fn check(&self, value: &str) -> std::result::Result<(), String> {
+        let valid = match self {
+            ValueType::Int => value.parse::<i64>().is_ok(),
+            ValueType::Bool => matches!(
+                value.to_lowercase().as_str(),
+                "true" | "false" | "1" | "0" | "yes" | "no" | "on" | "off"
+            ),
+            ValueType::Url => url::Url::parse(value).is_ok(),
+            ValueType::Enum(values) => {
+                if !values.iter().any(|v| v == value) {
+                    return Err(format!("expected one of: {}", values.join(", ")));
+                }
+
+                true
+            }
+            ValueType::Regex(pattern) => {
+                let regex = Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|e| format!("invalid pattern: {}", e))?;
+
+                if !regex.is_match(value) {
+                    return Err(format!("expected a value matching `{}`", pattern));
+                }
+
+                true
+            }
+            ValueType::Duration => parse_duration(value).is_ok(),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("expected a value of type `{}`", self.as_str()))
+        }
+    }
+
+    /// Get the name of the type as used in schema files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValueType::Int => "int",
+            ValueType::Bool => "bool",
+            ValueType::Url => "url",
+            ValueType::Enum(_) => "enum",
+            ValueType::Regex(_) => "regex",
+            ValueType::Duration => "duration",
+        }
+    }
+}
+
+/// Declaration of a single key of a [Schema]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FieldSpec {
+    pub key: String,
+    pub value_type: ValueType,
+    pub required: bool,
+    pub default: Option<String>,
+}
+
+/// A rule of the schema that an environment variable does not follow
+#[derive(Debug, Clone)]
+pub struct Violation {
+    /// The key of the environment variable
+    pub key: String,
+    /// What is wrong with the environment variable
+    pub kind: ViolationKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ViolationKind {
+    /// The key is required but the profile does not contain it
+    Missing,
+    /// The value does not match the declared type
+    InvalidValue { value: String, reason: String },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ViolationKind::Missing => write!(f, "`{}` is required but missing", self.key),
+            ViolationKind::InvalidValue { value, reason } => {
+                write!(
+                    f,
+                    "`{}` has invalid value `{}`, {}",
+                    self.key, value, reason
+                )
+            }
+        }
+    }
+}
+
+/// Representation of a field in a schema file
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawField {
+    #[serde(rename = "type")]
+    value_type: String,
+    #[serde(default)]
+    required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<toml::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    values: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+}
+
+/// Validation schema of a profile
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Schema {
+    fields: Vec<FieldSpec>,
+}
+
+impl Schema {
+    pub fn new() -> Schema {
+        Schema { fields: Vec::new() }
+    }
+
+    /// Parse a schema from its TOML representation
+    ///
+    /// # Parameters
+    /// - `content` - the content of the schema file
+    ///
+    /// # Returns
+    /// - `Result<Schema>`: the schema or an error if it is not valid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use envio::schema::Schema;
+    ///
+    /// let schema = Schema::from_toml("[PORT]\ntype = \"int\"\nrequired = true").unwrap();
+    /// ```
+    pub fn from_toml(content: &str) -> Result<Schema> {
+        let raw_fields: BTreeMap<String, RawField> =
+            toml::from_str(content).map_err(|e| Error::InvalidSchema(e.to_string()))?;
+
+        let mut schema = Schema::new();
+
+        for (key, raw) in raw_fields {
+            let value_type = match raw.value_type.as_str() {
+                "int" => ValueType::Int,
+                "bool" => ValueType::Bool,
+                "url" => ValueType::Url,
+                "duration" => ValueType::Duration,
+                "enum" => match raw.values {
+                    Some(values) if !values.is_empty() => ValueType::Enum(values),
+                    _ => {
+                        return Err(Error::InvalidSchema(format!(
+                            "`{}` is an enum but does not declare any `values`",
+                            key
+                        )))
+                    }
+                },
+                "regex" => match raw.pattern {
+                    Some(pattern) => {
+                        if let Err(e) = Regex::new(&pattern) {
+                            return Err(Error::InvalidSchema(format!(
+                                "`{}` has an invalid pattern: {}",
+                                key, e
+                            )));
+                        }
+
+                        ValueType::Regex(pattern)
+                    }
+                    None => {
+                        return Err(Error::InvalidSchema(format!(
+                            "`{}` is a regex but does not declare a `pattern`",
+                            key
+                        )))
+                    }
+                },
+                other => {
+                    return Err(Error::InvalidSchema(format!(
+                        "`{}` has unknown type `{}`",
+                        key, other
+                    )))
+                }
+            };
+
+            let default = match raw.default {
+                None => None,
+                Some(toml::Value::String(s)) => Some(s),
+                Some(toml::Value::Integer(i)) => Some(i.to_string()),
+                Some(toml::Value::Float(f)) => Some(f.to_string()),
+                Some(toml::Value::Boolean(b)) => Some(b.to_string()),
+                Some(_) => {
+                    return Err(Error::InvalidSchema(format!(
+                        "`{}` has a default value that is not a string, number or boolean",
+                        key
+                    )))
+                }
+            };
+
+            if let Some(default) = &default {
+                if let Err(reason) = value_type.check(default) {
+                    return Err(Error::InvalidSchema(format!(
+                        "`{}` has an invalid default value, {}",
+                        key, reason
+                    )));
+                }
+            }
+
+            schema.insert(FieldSpec {
+                key,
+                value_type,
+                required: raw.required,
+                default,
+            });
+        }
+
+        Ok(schema)
+    }
+
+    /// Get the TOML representation of the schema
+    ///
+    /// # Returns
+    /// - `Result<String>`: the content of a schema file describing this schema
+    pub fn to_toml(&self) -> Result<String> {
+        let mut raw_fields = BTreeMap::new();
+
+        for field in &self.fields {
+            let (values, pattern) = match &field.value_type {
+                ValueType::Enum(values) => (Some(values.clone()), None),
+                ValueType::Regex(pattern) => (None, Some(pattern.clone())),
+                _ => (None, None),
+            };
+
+            raw_fields.insert(
+                field.key.clone(),
+                RawField {
+                    value_type: field.value_type.as_str().to_string(),
+                    required: field.required,
+                    default: field.default.clone().map(toml::Value::String),
+                    values,
+                    pattern,
+                },
+            );
+        }
+
+        toml::to_string(&raw_fields).map_err(|e| Error::Serialization(e.to_string()))
+    }
+
+    /// Add a field to the schema, replacing any field with the same key
+    pub fn insert(&mut self, field: FieldSpec) {
+        self.fields.retain(|f| f.key != field.key);
+        self.fields.push(field);
+    }
+
+    /// Get the declaration of a key
+    pub fn get(&self, key: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|f| f.key == key)
+    }
+
+    /// Return an iterator over the fields of the schema
+    pub fn fields(&self) -> std::slice::Iter<'_, FieldSpec> {
+        self.fields.iter()
+    }
+
+    /// Check to see if the schema does not declare any keys
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Validate a single environment variable against the schema
+    ///
+    /// # Parameters
+    /// - `env` - the environment variable to validate
+    ///
+    /// # Returns
+    /// - `Option<Violation>`: the violation if the value is not valid
+    pub fn validate_env(&self, env: &Env) -> Option<Violation> {
+        let field = self.get(&env.name)?;
+
+        field
+            .value_type
+            .check(&env.value)
+            .err()
+            .map(|reason| Violation {
+                key: env.name.clone(),
+                kind: ViolationKind::InvalidValue {
+                    value: env.value.clone(),
+                    reason,
+                },
+            })
+    }
+
+    /// Validate environment variables against the schema
+    ///
+    /// # Parameters
+    /// - `envs` - the environment variables to validate
+    ///
+    /// # Returns
+    /// - `Vec<Violation>`: all the violations found, empty if `envs` is valid
+    pub fn validate(&self, envs: &EnvVec) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = self
+            .fields
+            .iter()
+            .filter(|f| f.required && !envs.contains_key(&f.key))
+            .map(|f| Violation {
+                key: f.key.clone(),
+                kind: ViolationKind::Missing,
+            })
+            .collect();
+
+        violations.extend(envs.iter().filter_map(|e| self.validate_env(e)));
+
+        violations
+    }
+
+    /// Add the keys which are missing from `envs` but have a default value
+    ///
+    /// # Parameters
+    /// - `envs` - the environment variables to add the defaults to
+    ///
+    /// # Returns
+    /// - `Vec<String>`: the keys that were added
+    pub fn apply_defaults(&self, envs: &mut EnvVec) -> Vec<String> {
+        let mut added = Vec::new();
+
+        for field in &self.fields {
+            if let Some(default) = &field.default {
+                if !envs.contains_key(&field.key) {
+                    envs.insert(Env::from_key_value(field.key.clone(), default.clone()));
+                    added.push(field.key.clone());
+                }
+            }
+        }
+
+        added
+    }
+}
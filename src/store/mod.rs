@@ -0,0 +1,640 @@
+//! Storage backends for profiles
+//!
+//! A [ProfileStore] keeps the encrypted content of profiles along with their
+//! [metadata](ProfileMetadata), identified by the name of the profile. It
+//! never sees the decrypted content.
+//!
+//! The following stores are provided:
+//! - [FsStore]: one `<name>.env` file per profile in a directory, this is
+//!   where envio keeps profiles by default
+//! - `SqliteStore`: every profile in a single SQLite database, requires the
+//!   `sqlite` feature
+//! - [MemoryStore]: profiles kept in memory, meant for tests
+//!
+//! Which of the first two holds the profiles of envio is recorded in the
+//! [data directory](crate::utils::get_datadir), see [StoreKind].
+//!
+//! ```rust
+//! use std::sync::Arc;
+//!
+//! use envio::crypto::create_encryption_type;
+//! use envio::store::{MemoryStore, ProfileStore};
+//! use envio::{EnvVec, Profile};
+//!
+//! let store = Arc::new(MemoryStore::new());
+//!
+//! let encryption_type = create_encryption_type("my-key".to_string(), "age").unwrap();
+//! let mut profile = Profile::new("my-profile".to_string(), EnvVec::new(), Default::default(), encryption_type);
+//! profile.set_store(store.clone());
+//! profile.push_changes().unwrap();
+//!
+//! assert_eq!(store.list().unwrap(), vec!["my-profile"]);
+//! ```
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::error::{Error, Result};
+use crate::lock::ProfileLock;
+use crate::metadata::ProfileMetadata;
+use crate::utils::{contains_path_separator, get_datadir, write_atomic};
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::{HistoryEntry, SqliteStore, SqliteTransaction, DEFAULT_HISTORY_LIMIT};
+
+/// Extension of the files profiles are stored in by [FsStore]
+pub const PROFILE_FILE_EXTENSION: &str = "env";
+
+/// Name of the file in the data directory which records the [StoreKind]
+/// holding the profiles
+pub const STORE_KIND_FILE_NAME: &str = "store";
+
+/// Name of the database in the data directory used by the
+/// [sqlite](StoreKind::Sqlite) store
+pub const DATABASE_FILE_NAME: &str = "profiles.db";
+
+/// The kinds of stores envio can keep its profiles in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StoreKind {
+    /// An [FsStore] on the `profiles` directory of the data directory
+    #[default]
+    Fs,
+    /// A `SqliteStore` on the `profiles.db` database of the data directory
+    Sqlite,
+}
+
+impl StoreKind {
+    /// Get the kind of store the profiles are currently kept in
+    ///
+    /// # Returns
+    /// - `Result<StoreKind>`: the kind recorded in the data directory,
+    ///   [Fs](StoreKind::Fs) if none was recorded
+    pub fn current() -> Result<StoreKind> {
+        let path = get_datadir()?.join(STORE_KIND_FILE_NAME);
+
+        if !path.exists() {
+            return Ok(StoreKind::default());
+        }
+
+        std::fs::read_to_string(path)?.trim().parse()
+    }
+
+    /// Record that the profiles are kept in this kind of store from now on
+    ///
+    /// The profiles are not moved, this is up to the caller.
+    ///
+    /// # Returns
+    /// - `Result<()>`: whether the operation was successful
+    pub fn make_current(self) -> Result<()> {
+        let datadir = get_datadir()?;
+        std::fs::create_dir_all(&datadir)?;
+
+        write_atomic(
+            &datadir.join(STORE_KIND_FILE_NAME),
+            format!("{}\n", self).as_bytes(),
+        )
+    }
+
+    /// Open the store of this kind in the data directory
+    ///
+    /// # Returns
+    /// - `Result<Arc<dyn ProfileStore>>`: the store, or an error if envio was
+    ///   built without support for it
+    pub fn open(self) -> Result<Arc<dyn ProfileStore>> {
+        match self {
+            StoreKind::Fs => Ok(Arc::new(FsStore::from_datadir()?)),
+            #[cfg(feature = "sqlite")]
+            StoreKind::Sqlite => Ok(Arc::new(SqliteStore::open(
+                get_datadir()?.join(DATABASE_FILE_NAME),
+            )?)),
+            #[cfg(not(feature = "sqlite"))]
+            StoreKind::Sqlite => Err(Error::Msg(
+                "The profiles are kept in a SQLite database, but envio was built without the `sqlite` feature".to_string(),
+            )),
+        }
+    }
+}
+
+impl FromStr for StoreKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "fs" => Ok(StoreKind::Fs),
+            "sqlite" => Ok(StoreKind::Sqlite),
+            _ => Err(Error::InvalidStoreKind(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for StoreKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreKind::Fs => write!(f, "fs"),
+            StoreKind::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// Open the store the profiles of envio are currently kept in
+///
+/// # Returns
+/// - `Result<Arc<dyn ProfileStore>>`: the store, see [StoreKind::current]
+pub fn open_default() -> Result<Arc<dyn ProfileStore>> {
+    StoreKind::current()?.open()
+}
+
+/// Open the store holding a profile that is given by name or by the path to
+/// its file
+///
+/// Paths, which have to contain a path separator, always refer to a profile
+/// file, no matter which store the profiles are currently kept in.
+pub(crate) fn store_for(name: &str) -> Result<Arc<dyn ProfileStore>> {
+    if contains_path_separator(name) {
+        return Ok(Arc::new(FsStore::from_datadir()?));
+    }
+
+    open_default()
+}
+
+/// Check that a name can be used for the files a store keeps next to its
+/// profiles, which must never end up outside of the store's directory
+pub(crate) fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || contains_path_separator(name) {
+        return Err(Error::Msg(format!(
+            "Invalid profile name '{}', profiles can only be changed by name",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Storage backend for the encrypted content and the metadata of profiles
+///
+/// Stores are shared between threads, e.g. by [Profile](crate::Profile)s
+/// loaded from them, and therefore have to be `Send` and `Sync`.
+pub trait ProfileStore: Send + Sync {
+    /// Get the names of all the stored profiles, sorted by name
+    ///
+    /// # Returns
+    /// - `Result<Vec<String>>`: the names of the profiles
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// Check to see if a profile is stored
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `bool`: whether the profile exists
+    fn exists(&self, name: &str) -> bool;
+
+    /// Read the encrypted content of a profile
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Result<Vec<u8>>`: the encrypted content, or
+    ///   [ProfileDoesNotExist](Error::ProfileDoesNotExist) if there is no such
+    ///   profile
+    fn read(&self, name: &str) -> Result<Vec<u8>>;
+
+    /// Replace the encrypted content of a profile, creating it if it does not
+    /// exist yet
+    ///
+    /// A failed write must leave the previous content intact.
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    /// - `content` - the encrypted content
+    ///
+    /// # Returns
+    /// - `Result<()>`: whether the operation was successful
+    fn write(&self, name: &str, content: &[u8]) -> Result<()>;
+
+    /// Delete a profile along with its metadata
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Result<()>`: whether the operation was successful,
+    ///   [ProfileDoesNotExist](Error::ProfileDoesNotExist) if there is no such
+    ///   profile
+    fn delete(&self, name: &str) -> Result<()>;
+
+    /// Read the metadata of a profile
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Result<ProfileMetadata>`: the metadata, empty if none was stored
+    fn read_metadata(&self, name: &str) -> Result<ProfileMetadata>;
+
+    /// Replace the metadata of a profile
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    /// - `metadata` - the new metadata
+    ///
+    /// # Returns
+    /// - `Result<()>`: whether the operation was successful
+    fn write_metadata(&self, name: &str, metadata: &ProfileMetadata) -> Result<()>;
+
+    /// Lock a profile, waiting for whoever holds the lock to release it
+    ///
+    /// The profile does not have to exist yet, so that its creation can be
+    /// locked as well. See the [lock](crate::lock) module for why profiles are
+    /// locked.
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Result<StoreLock>`: the lock, released when dropped
+    fn lock(&self, name: &str) -> Result<StoreLock>;
+
+    /// Lock a profile if nobody else holds the lock
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Result<Option<StoreLock>>`: the lock or `None` if it is held by
+    ///   someone else
+    fn try_lock(&self, name: &str) -> Result<Option<StoreLock>>;
+
+    /// Get the path of the file a profile is stored in, if the store keeps
+    /// profiles in files
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Option<PathBuf>`: the path, which does not have to exist yet
+    fn path(&self, _name: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Add several profiles along with their metadata, either all of them or
+    /// none of them
+    ///
+    /// The default implementation adds the profiles one after the other and
+    /// deletes the ones added so far again if one of them can not be added.
+    /// Stores supporting transactions add them in a single one instead.
+    ///
+    /// # Parameters
+    /// - `profiles` - the profiles to add
+    ///
+    /// # Returns
+    /// - `Result<()>`: whether the operation was successful,
+    ///   [ProfileExists](Error::ProfileExists) if one of the profiles is
+    ///   stored already. If deleting the profiles added so far fails too, the
+    ///   error says which ones are left behind.
+    fn add_all(&self, profiles: &[StoredProfile]) -> Result<()> {
+        let mut added: Vec<&str> = Vec::with_capacity(profiles.len());
+
+        for profile in profiles {
+            let result = if self.exists(&profile.name) {
+                Err(Error::ProfileExists(profile.name.clone()))
+            } else {
+                self.write(&profile.name, &profile.content).and_then(|()| {
+                    added.push(&profile.name);
+
+                    if profile.metadata == ProfileMetadata::default() {
+                        return Ok(());
+                    }
+
+                    self.write_metadata(&profile.name, &profile.metadata)
+                })
+            };
+
+            if let Err(e) = result {
+                let left_behind: Vec<String> = added
+                    .iter()
+                    .filter_map(|name| {
+                        self.delete(name)
+                            .err()
+                            .map(|delete_error| format!("`{}` ({})", name, delete_error))
+                    })
+                    .collect();
+
+                if left_behind.is_empty() {
+                    return Err(e);
+                }
+
+                return Err(Error::Msg(format!(
+                    "{}, the following profiles were added before and could not be deleted again: {}",
+                    e,
+                    left_behind.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A profile as kept by a [ProfileStore], see [add_all](ProfileStore::add_all)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredProfile {
+    pub name: String,
+    /// The encrypted content of the profile
+    pub content: Vec<u8>,
+    pub metadata: ProfileMetadata,
+}
+
+/// Exclusive lock on a profile of a [ProfileStore], released when dropped
+pub struct StoreLock {
+    _guard: Box<dyn Send>,
+}
+
+impl StoreLock {
+    /// Wrap the guard of a store specific lock, which must release the lock
+    /// when it is dropped
+    pub fn new<G: Send + 'static>(guard: G) -> StoreLock {
+        StoreLock {
+            _guard: Box::new(guard),
+        }
+    }
+}
+
+/// Store keeping every profile in a `<name>.env` file of a directory
+///
+/// The metadata of a profile is kept next to it, see the
+/// [metadata](crate::metadata) module, and profiles are locked using a
+/// [ProfileLock].
+///
+/// Names containing a path separator, e.g. `./my-profile.env`, are used as the
+/// path to a profile file instead. Other names always refer to a profile of
+/// the store, even if a file of that name exists in the current directory.
+#[derive(Debug, Clone)]
+pub struct FsStore {
+    dir: PathBuf,
+}
+
+impl FsStore {
+    /// Create a store for the `profiles` directory of the
+    /// [data directory](crate::utils::get_datadir), where envio keeps its
+    /// profiles by default
+    ///
+    /// # Returns
+    /// - `Result<FsStore>`: the store, or an error if the data directory can
+    ///   not be determined
+    pub fn from_datadir() -> Result<FsStore> {
+        Ok(FsStore::new(get_datadir()?.join("profiles")))
+    }
+
+    /// Create a store for the profiles of a directory
+    ///
+    /// The directory is created when the first profile is written.
+    ///
+    /// # Parameters
+    /// - `dir` - the directory the profiles are stored in
+    pub fn new(dir: impl Into<PathBuf>) -> FsStore {
+        FsStore { dir: dir.into() }
+    }
+
+    /// Get the directory the profiles are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the path of the file a profile is read from
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile or the path to the profile file,
+    ///   which has to contain a path separator, e.g. `./my-profile.env`
+    ///
+    /// # Returns
+    /// - `PathBuf`: the path to the profile file, which does not have to exist
+    pub fn profile_path(&self, name: &str) -> PathBuf {
+        if contains_path_separator(name) {
+            return PathBuf::from(name);
+        }
+
+        self.dir
+            .join(format!("{}.{}", name, PROFILE_FILE_EXTENSION))
+    }
+
+    /// Get the path of the file a profile is written to or deleted from, which
+    /// is always inside of the store directory
+    fn stored_path(&self, name: &str) -> Result<PathBuf> {
+        check_name(name)?;
+
+        Ok(self.profile_path(name))
+    }
+}
+
+impl ProfileStore for FsStore {
+    fn list(&self) -> Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut names = Vec::new();
+
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+
+            if path
+                .extension()
+                .map_or(true, |ext| ext != PROFILE_FILE_EXTENSION)
+            {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) if !name.starts_with('.') => name.to_string(),
+                _ => continue,
+            };
+
+            names.push(name);
+        }
+
+        names.sort();
+
+        Ok(names)
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.profile_path(name).exists()
+    }
+
+    fn read(&self, name: &str) -> Result<Vec<u8>> {
+        let path = self.profile_path(name);
+
+        if !path.exists() {
+            return Err(Error::ProfileDoesNotExist(name.to_string()));
+        }
+
+        Ok(std::fs::read(path)?)
+    }
+
+    fn write(&self, name: &str, content: &[u8]) -> Result<()> {
+        let path = self.stored_path(name)?;
+        std::fs::create_dir_all(&self.dir)?;
+
+        write_atomic(&path, content)
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        let path = self.stored_path(name)?;
+
+        if !path.exists() {
+            return Err(Error::ProfileDoesNotExist(name.to_string()));
+        }
+
+        std::fs::remove_file(&path)?;
+
+        let metadata_path = ProfileMetadata::file_path(&path);
+
+        if metadata_path.exists() {
+            std::fs::remove_file(metadata_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_metadata(&self, name: &str) -> Result<ProfileMetadata> {
+        ProfileMetadata::load(&self.profile_path(name))
+    }
+
+    fn write_metadata(&self, name: &str, metadata: &ProfileMetadata) -> Result<()> {
+        metadata.save(&self.stored_path(name)?)
+    }
+
+    fn lock(&self, name: &str) -> Result<StoreLock> {
+        ProfileLock::acquire_path(&self.profile_path(name)).map(StoreLock::new)
+    }
+
+    fn try_lock(&self, name: &str) -> Result<Option<StoreLock>> {
+        Ok(ProfileLock::try_acquire_path(&self.profile_path(name))?.map(StoreLock::new))
+    }
+
+    fn path(&self, name: &str) -> Option<PathBuf> {
+        Some(self.profile_path(name))
+    }
+}
+
+/// Store keeping profiles in memory, meant for tests
+///
+/// Clones of a store share its profiles and locks.
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    state: Arc<MemoryState>,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    profiles: Mutex<BTreeMap<String, Vec<u8>>>,
+    metadata: Mutex<BTreeMap<String, ProfileMetadata>>,
+    locked: Mutex<HashSet<String>>,
+    unlocked: Condvar,
+}
+
+/// Lock on a profile of a [MemoryStore]
+struct MemoryLock {
+    state: Arc<MemoryState>,
+    name: String,
+}
+
+impl Drop for MemoryLock {
+    fn drop(&mut self) {
+        lock_mutex(&self.state.locked).remove(&self.name);
+        self.state.unlocked.notify_all();
+    }
+}
+
+/// Lock a mutex, ignoring poisoning since the data it guards stays consistent
+/// no matter where a panic happened
+fn lock_mutex<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+impl ProfileStore for MemoryStore {
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(lock_mutex(&self.state.profiles).keys().cloned().collect())
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        lock_mutex(&self.state.profiles).contains_key(name)
+    }
+
+    fn read(&self, name: &str) -> Result<Vec<u8>> {
+        lock_mutex(&self.state.profiles)
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::ProfileDoesNotExist(name.to_string()))
+    }
+
+    fn write(&self, name: &str, content: &[u8]) -> Result<()> {
+        lock_mutex(&self.state.profiles).insert(name.to_string(), content.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        if lock_mutex(&self.state.profiles).remove(name).is_none() {
+            return Err(Error::ProfileDoesNotExist(name.to_string()));
+        }
+
+        lock_mutex(&self.state.metadata).remove(name);
+        Ok(())
+    }
+
+    fn read_metadata(&self, name: &str) -> Result<ProfileMetadata> {
+        Ok(lock_mutex(&self.state.metadata)
+            .get(name)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn write_metadata(&self, name: &str, metadata: &ProfileMetadata) -> Result<()> {
+        lock_mutex(&self.state.metadata).insert(name.to_string(), metadata.clone());
+        Ok(())
+    }
+
+    fn lock(&self, name: &str) -> Result<StoreLock> {
+        let mut locked = lock_mutex(&self.state.locked);
+
+        while locked.contains(name) {
+            locked = self
+                .state
+                .unlocked
+                .wait(locked)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+
+        locked.insert(name.to_string());
+
+        Ok(StoreLock::new(MemoryLock {
+            state: self.state.clone(),
+            name: name.to_string(),
+        }))
+    }
+
+    fn try_lock(&self, name: &str) -> Result<Option<StoreLock>> {
+        if !lock_mutex(&self.state.locked).insert(name.to_string()) {
+            return Ok(None);
+        }
+
+        Ok(Some(StoreLock::new(MemoryLock {
+            state: self.state.clone(),
+            name: name.to_string(),
+        })))
+    }
+}
@@ -0,0 +1,461 @@
+//! Store keeping every profile in a single SQLite database
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+
+use crate::error::{Error, Result};
+use crate::lock::ProfileLock;
+use crate::metadata::ProfileMetadata;
+
+use super::{
+    check_name, lock_mutex, ProfileStore, StoreLock, StoredProfile, PROFILE_FILE_EXTENSION,
+};
+
+/// Number of replaced versions kept per profile by default
+pub const DEFAULT_HISTORY_LIMIT: usize = 10;
+
+/// Version of the database schema, stored in the `user_version` pragma
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS profiles (
+        name TEXT PRIMARY KEY NOT NULL,
+        content BLOB NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS metadata (
+        name TEXT PRIMARY KEY NOT NULL,
+        description TEXT,
+        owner TEXT,
+        notes TEXT,
+        encryption_type TEXT,
+        env_count INTEGER,
+        created_at TEXT,
+        modified_at TEXT,
+        expiration_dates TEXT
+    );
+
+    CREATE TABLE IF NOT EXISTS history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        content BLOB NOT NULL,
+        replaced_at TEXT NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS history_name ON history (name);
+";
+
+/// Store keeping the encrypted content, the metadata and the previous
+/// versions of every profile in one SQLite database
+///
+/// The content of every profile is encrypted on its own before it reaches the
+/// store, exactly like it is for the other stores. The database only holds
+/// the encrypted content and the [metadata](ProfileMetadata), which never
+/// contains secrets.
+///
+/// Whenever a profile is written, the content it replaces is kept in the
+/// history of the profile, see [history](SqliteStore::history).
+///
+/// Several changes can be made atomically using a
+/// [transaction](SqliteStore::transaction). Profiles are locked with a
+/// [ProfileLock] on a `.<profile>.lock` file next to the database.
+pub struct SqliteStore {
+    path: Option<PathBuf>,
+    connection: Mutex<Connection>,
+    history_limit: usize,
+}
+
+/// A replaced version of a profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// When this version was replaced
+    pub replaced_at: DateTime<Utc>,
+    /// The encrypted content of this version
+    pub content: Vec<u8>,
+}
+
+impl SqliteStore {
+    /// Open the database at `path`, creating it if it does not exist
+    ///
+    /// # Parameters
+    /// - `path` - the path to the database file
+    ///
+    /// # Returns
+    /// - `Result<SqliteStore>`: the store
+    pub fn open(path: impl Into<PathBuf>) -> Result<SqliteStore> {
+        let path = path.into();
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let connection = Connection::open(&path)?;
+
+        // Other processes may be writing to the database at the same time
+        connection.busy_timeout(Duration::from_secs(10))?;
+
+        SqliteStore::init(Some(path), connection)
+    }
+
+    /// Create a store backed by a database that only lives in memory
+    ///
+    /// # Returns
+    /// - `Result<SqliteStore>`: the store
+    pub fn open_in_memory() -> Result<SqliteStore> {
+        SqliteStore::init(None, Connection::open_in_memory()?)
+    }
+
+    fn init(path: Option<PathBuf>, connection: Connection) -> Result<SqliteStore> {
+        let version: i32 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+        if version > SCHEMA_VERSION {
+            return Err(Error::Msg(format!(
+                "The profile database was created by a newer version of envio (schema version {}), please update envio",
+                version
+            )));
+        }
+
+        connection.execute_batch(SCHEMA)?;
+        connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+        Ok(SqliteStore {
+            path,
+            connection: Mutex::new(connection),
+            history_limit: DEFAULT_HISTORY_LIMIT,
+        })
+    }
+
+    /// Set how many replaced versions are kept per profile,
+    /// [DEFAULT_HISTORY_LIMIT] by default
+    ///
+    /// `0` disables the history.
+    pub fn history_limit(mut self, history_limit: usize) -> SqliteStore {
+        self.history_limit = history_limit;
+        self
+    }
+
+    /// Get the path of the database file, `None` if it only lives in memory
+    pub fn database_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Get the replaced versions of a profile, the most recent first
+    ///
+    /// # Parameters
+    /// - `name` - the name of the profile
+    ///
+    /// # Returns
+    /// - `Result<Vec<HistoryEntry>>`: the replaced versions
+    pub fn history(&self, name: &str) -> Result<Vec<HistoryEntry>> {
+        let connection = lock_mutex(&self.connection);
+
+        let mut statement = connection
+            .prepare("SELECT replaced_at, content FROM history WHERE name = ?1 ORDER BY id DESC")?;
+
+        let entries = statement
+            .query_map([name], |row| {
+                Ok(HistoryEntry {
+                    replaced_at: row.get(0)?,
+                    content: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
+    /// Run `f` in a transaction, all of its changes are committed if it
+    /// returns `Ok` and none of them are if it returns `Err`
+    ///
+    /// The transaction takes the write lock of the database right away, other
+    /// writers wait until it is done.
+    ///
+    /// # Parameters
+    /// - `f` - the changes to make
+    ///
+    /// # Returns
+    /// - `Result<T>`: what `f` returned
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use envio::store::SqliteStore;
+    ///
+    /// let store = SqliteStore::open("profiles.db").unwrap();
+    ///
+    /// store
+    ///     .transaction(|tx| {
+    ///         let content = tx.read("old-name")?;
+    ///         tx.write("new-name", &content)?;
+    ///         tx.write_metadata("new-name", &tx.read_metadata("old-name")?)?;
+    ///         tx.delete("old-name")
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn transaction<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&SqliteTransaction) -> Result<T>,
+    {
+        self.run(TransactionBehavior::Immediate, f)
+    }
+
+    /// Run `f` in a transaction which only takes the write lock of the
+    /// database once `f` writes to it, so that readers do not wait for each
+    /// other
+    fn read_transaction<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&SqliteTransaction) -> Result<T>,
+    {
+        self.run(TransactionBehavior::Deferred, f)
+    }
+
+    fn run<T, F>(&self, behavior: TransactionBehavior, f: F) -> Result<T>
+    where
+        F: FnOnce(&SqliteTransaction) -> Result<T>,
+    {
+        let mut connection = lock_mutex(&self.connection);
+
+        let transaction = SqliteTransaction {
+            transaction: connection.transaction_with_behavior(behavior)?,
+            history_limit: self.history_limit,
+        };
+
+        let value = f(&transaction)?;
+        transaction.transaction.commit()?;
+
+        Ok(value)
+    }
+
+    /// Get the path the lock file of a profile is derived from, which is
+    /// always next to the database
+    fn lock_path(&self, name: &str) -> Result<PathBuf> {
+        check_name(name)?;
+
+        let path = self.path.as_deref().ok_or_else(|| {
+            Error::Msg("Profiles of an in-memory database can not be locked".to_string())
+        })?;
+
+        Ok(path.with_file_name(format!("{}.{}", name, PROFILE_FILE_EXTENSION)))
+    }
+}
+
+/// Changes made to a [SqliteStore] inside of a
+/// [transaction](SqliteStore::transaction)
+///
+/// The methods work like the ones of [ProfileStore].
+pub struct SqliteTransaction<'a> {
+    transaction: rusqlite::Transaction<'a>,
+    history_limit: usize,
+}
+
+impl SqliteTransaction<'_> {
+    /// See [ProfileStore::list]
+    pub fn list(&self) -> Result<Vec<String>> {
+        let mut statement = self
+            .transaction
+            .prepare("SELECT name FROM profiles ORDER BY name")?;
+
+        let names = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(names)
+    }
+
+    /// See [ProfileStore::exists]
+    pub fn exists(&self, name: &str) -> Result<bool> {
+        Ok(self
+            .transaction
+            .query_row("SELECT 1 FROM profiles WHERE name = ?1", [name], |_| Ok(()))
+            .optional()?
+            .is_some())
+    }
+
+    /// See [ProfileStore::read]
+    pub fn read(&self, name: &str) -> Result<Vec<u8>> {
+        self.transaction
+            .query_row(
+                "SELECT content FROM profiles WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| Error::ProfileDoesNotExist(name.to_string()))
+    }
+
+    /// See [ProfileStore::write], the content that is replaced is added to the
+    /// history of the profile
+    pub fn write(&self, name: &str, content: &[u8]) -> Result<()> {
+        if self.history_limit > 0 {
+            self.transaction.execute(
+                "INSERT INTO history (name, content, replaced_at)
+                 SELECT name, content, ?2 FROM profiles WHERE name = ?1",
+                params![name, Utc::now()],
+            )?;
+
+            self.transaction.execute(
+                "DELETE FROM history WHERE name = ?1 AND id NOT IN (
+                    SELECT id FROM history WHERE name = ?1 ORDER BY id DESC LIMIT ?2
+                 )",
+                params![name, self.history_limit as i64],
+            )?;
+        }
+
+        self.transaction.execute(
+            "INSERT INTO profiles (name, content) VALUES (?1, ?2)
+             ON CONFLICT (name) DO UPDATE SET content = excluded.content",
+            params![name, content],
+        )?;
+
+        Ok(())
+    }
+
+    /// See [ProfileStore::delete], the history of the profile is deleted as
+    /// well
+    pub fn delete(&self, name: &str) -> Result<()> {
+        if self
+            .transaction
+            .execute("DELETE FROM profiles WHERE name = ?1", [name])?
+            == 0
+        {
+            return Err(Error::ProfileDoesNotExist(name.to_string()));
+        }
+
+        self.transaction
+            .execute("DELETE FROM metadata WHERE name = ?1", [name])?;
+        self.transaction
+            .execute("DELETE FROM history WHERE name = ?1", [name])?;
+
+        Ok(())
+    }
+
+    /// See [ProfileStore::read_metadata]
+    pub fn read_metadata(&self, name: &str) -> Result<ProfileMetadata> {
+        let metadata = self
+            .transaction
+            .query_row(
+                "SELECT description, owner, notes, encryption_type, env_count, created_at, modified_at,
+                        expiration_dates
+                 FROM metadata WHERE name = ?1",
+                [name],
+                |row| {
+                    Ok((
+                        ProfileMetadata {
+                            description: row.get(0)?,
+                            owner: row.get(1)?,
+                            notes: row.get(2)?,
+                            encryption_type: row.get(3)?,
+                            env_count: row.get(4)?,
+                            created_at: row.get(5)?,
+                            modified_at: row.get(6)?,
+                            expiration_dates: None,
+                        },
+                        row.get::<_, Option<String>>(7)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        let Some((mut metadata, expiration_dates)) = metadata else {
+            return Ok(ProfileMetadata::default());
+        };
+
+        // The expiration dates are kept as a TOML table, like in the metadata
+        // files of the other stores
+        metadata.expiration_dates = expiration_dates
+            .map(|dates| toml::from_str(&dates))
+            .transpose()
+            .map_err(|e| Error::Deserialization(e.to_string()))?;
+
+        Ok(metadata)
+    }
+
+    /// See [ProfileStore::write_metadata]
+    pub fn write_metadata(&self, name: &str, metadata: &ProfileMetadata) -> Result<()> {
+        let expiration_dates = metadata
+            .expiration_dates
+            .as_ref()
+            .map(toml::to_string)
+            .transpose()
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+
+        self.transaction.execute(
+            "INSERT OR REPLACE INTO metadata
+                (name, description, owner, notes, encryption_type, env_count, created_at, modified_at,
+                 expiration_dates)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                name,
+                metadata.description,
+                metadata.owner,
+                metadata.notes,
+                metadata.encryption_type,
+                metadata.env_count,
+                metadata.created_at,
+                metadata.modified_at,
+                expiration_dates,
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ProfileStore for SqliteStore {
+    fn list(&self) -> Result<Vec<String>> {
+        self.read_transaction(|tx| tx.list())
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.read_transaction(|tx| tx.exists(name)).unwrap_or(false)
+    }
+
+    fn read(&self, name: &str) -> Result<Vec<u8>> {
+        self.read_transaction(|tx| tx.read(name))
+    }
+
+    fn write(&self, name: &str, content: &[u8]) -> Result<()> {
+        self.transaction(|tx| tx.write(name, content))
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        self.transaction(|tx| tx.delete(name))
+    }
+
+    fn read_metadata(&self, name: &str) -> Result<ProfileMetadata> {
+        self.read_transaction(|tx| tx.read_metadata(name))
+    }
+
+    fn write_metadata(&self, name: &str, metadata: &ProfileMetadata) -> Result<()> {
+        self.transaction(|tx| tx.write_metadata(name, metadata))
+    }
+
+    fn lock(&self, name: &str) -> Result<StoreLock> {
+        ProfileLock::acquire_path(&self.lock_path(name)?).map(StoreLock::new)
+    }
+
+    fn try_lock(&self, name: &str) -> Result<Option<StoreLock>> {
+        Ok(ProfileLock::try_acquire_path(&self.lock_path(name)?)?.map(StoreLock::new))
+    }
+
+    fn add_all(&self, profiles: &[StoredProfile]) -> Result<()> {
+        self.transaction(|tx| {
+            for profile in profiles {
+                if tx.exists(&profile.name)? {
+                    return Err(Error::ProfileExists(profile.name.clone()));
+                }
+
+                tx.write(&profile.name, &profile.content)?;
+
+                if profile.metadata != ProfileMetadata::default() {
+                    tx.write_metadata(&profile.name, &profile.metadata)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -4,13 +4,14 @@
 /// The CLI also has its own utility functions, but they are located in the `bin/envio` directory inside the `utils.rs` file.
 /// There might be a few functions that are used in both the CLI and the library, but they are kept separate since the library does not expose these utility functions to the end user. They are only used internally.
 use std::{
-    io::Read,
+    io::Write,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use crate::{
     error::{Error, Result},
-    Profile,
+    store::{self, FsStore, ProfileStore},
 };
 
 pub fn contains_path_separator(s: &str) -> bool {
@@ -18,18 +19,13 @@ pub fn contains_path_separator(s: &str) -> bool {
 }
 
 pub fn get_profile_filepath(name: &str) -> Result<PathBuf> {
-    match Path::new(name).exists() {
-        true => Ok(PathBuf::from(name)),
-        false => {
-            if !Profile::does_exist(name) {
-                return Err(Error::ProfileDoesNotExist(name.to_string()));
-            }
-
-            Ok(get_configdir()
-                .join("profiles")
-                .join(format!("{}.env", name)))
-        }
+    let store = FsStore::from_datadir()?;
+
+    if !store.exists(name) {
+        return Err(Error::ProfileDoesNotExist(name.to_string()));
     }
+
+    Ok(store.profile_path(name))
 }
 
 /// Reads the encrypted content of a profile and returns it
@@ -37,26 +33,225 @@ pub fn get_profile_filepath(name: &str) -> Result<PathBuf> {
 /// # Parameters
 /// - `name`: &str - the name of the profile
 ///
-/// `name` can either be the name of the profile or the absolute path to the profile file.
+/// `name` can either be the name of the profile or the path to the profile file, which has to contain a path separator.
 ///
 /// # Returns
 /// - `Result<Vec<u8>>`: the encrypted content of the profile
 pub fn get_profile_content(name: &str) -> Result<Vec<u8>> {
-    let profile_file_path = get_profile_filepath(name)?;
+    store::store_for(name)?.read(name)
+}
 
-    let mut file = std::fs::OpenOptions::new()
-        .read(true)
-        .open(profile_file_path)?;
+/// Environment variable overriding the directory envio keeps its
+/// configuration and profiles in
+pub const ENVIO_HOME_ENV: &str = "ENVIO_HOME";
 
-    let mut encrypted_contents = Vec::new();
-    file.read_to_end(&mut encrypted_contents).unwrap();
+/// Get the home directory of the user
+///
+/// # Returns
+/// - `Result<PathBuf>`: the home directory, or an error if it can not be
+///   determined, as is the case in some containers
+pub fn get_homedir() -> Result<PathBuf> {
+    dirs::home_dir().ok_or_else(|| {
+        Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Could not find home directory",
+        ))
+    })
+}
+
+/// Get the directory envio keeps its configuration in
+///
+/// The first of these is used:
+/// - `$ENVIO_HOME`
+/// - `~/.envio`, if it exists, where envio used to keep everything
+/// - `$XDG_CONFIG_HOME/envio`, or the config directory of the platform if
+///   `XDG_CONFIG_HOME` is not set, e.g. `~/.config/envio`
+///
+/// # Returns
+/// - `Result<PathBuf>`: the config directory, which does not have to exist yet
+pub fn get_configdir() -> Result<PathBuf> {
+    get_envio_dir("XDG_CONFIG_HOME", dirs::config_dir)
+}
 
-    Ok(encrypted_contents)
+/// Get the directory envio keeps its profiles in
+///
+/// Same as [get_configdir] but falls back to `$XDG_DATA_HOME/envio`, or the
+/// data directory of the platform, e.g. `~/.local/share/envio`.
+///
+/// # Returns
+/// - `Result<PathBuf>`: the data directory, which does not have to exist yet
+pub fn get_datadir() -> Result<PathBuf> {
+    get_envio_dir("XDG_DATA_HOME", dirs::data_dir)
 }
 
-pub fn get_configdir() -> PathBuf {
-    let homedir = dirs::home_dir().unwrap();
-    homedir.join(".envio")
+fn get_envio_dir(xdg_var: &str, platform_dir: fn() -> Option<PathBuf>) -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(ENVIO_HOME_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if let Some(legacy_dir) = dirs::home_dir()
+        .map(|home| home.join(".envio"))
+        .filter(|dir| dir.is_dir())
+    {
+        return Ok(legacy_dir);
+    }
+
+    // Relative paths are invalid according to the XDG base directory
+    // specification and have to be ignored
+    let xdg_dir = std::env::var_os(xdg_var)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute());
+
+    match xdg_dir.or_else(platform_dir) {
+        Some(dir) => Ok(dir.join("envio")),
+        None => Err(Error::Msg(format!(
+            "Could not find the home directory, set `{}` to the directory envio should use",
+            ENVIO_HOME_ENV
+        ))),
+    }
+}
+
+/// Write `data` to `path` without ever leaving a partially written file behind
+///
+/// The data is first written to a temporary file in the same directory which
+/// is synced to disk and then renamed over `path`. The directory is synced as
+/// well so that the rename itself survives a crash. If `path` already exists,
+/// its permissions are kept.
+///
+/// # Parameters
+/// - `path`: &Path - the file to write
+/// - `data`: &[u8] - the new content of the file
+///
+/// # Returns
+/// - `Result<()>`: whether the operation was successful, `path` is left
+///   untouched if it was not
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let file_name = match path.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => {
+            return Err(Error::Msg(format!(
+                "`{}` is not a file path",
+                path.display()
+            )))
+        }
+    };
+
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let tmp_path = dir.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let write_tmp = || -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+
+        if let Ok(metadata) = std::fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+
+        file.write_all(data)?;
+        file.sync_all()?;
+
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    };
+
+    if let Err(e) = write_tmp() {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    // Directories can not be opened as files on Windows, where the rename is
+    // durable on its own
+    #[cfg(target_family = "unix")]
+    std::fs::File::open(&dir)?.sync_all()?;
+
+    Ok(())
+}
+
+/// Run blocking work, such as file I/O or a GPG decryption that waits for
+/// pinentry, on tokio's blocking thread pool
+///
+/// # Parameters
+/// - `f` - the blocking work
+///
+/// # Returns
+/// - `Result<T>`: the result of the work, or an error if it panicked
+#[cfg(feature = "async")]
+pub(crate) async fn spawn_blocking<F, T>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) => Err(Error::Msg(format!("Blocking task failed: {}", e))),
+    }
+}
+
+/// Parse a duration such as `90s`, `15m`, `12h`, `14d`, `2w` or `1h30m`
+///
+/// The supported units are `ms`, `s`, `m`, `h`, `d` and `w`. A number without
+/// a unit is interpreted as a number of seconds.
+///
+/// # Parameters
+/// - `s`: &str - the duration to parse
+///
+/// # Returns
+/// - `Result<Duration>`: the parsed duration
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let invalid = || Error::InvalidDuration(s.to_string());
+
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(invalid());
+    }
+
+    if let Ok(secs) = s.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        if digits == 0 {
+            return Err(invalid());
+        }
+
+        let amount: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+
+        let unit = match &rest[..unit_len] {
+            "ms" => Duration::from_millis(1),
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(60 * 60),
+            "d" => Duration::from_secs(60 * 60 * 24),
+            "w" => Duration::from_secs(60 * 60 * 24 * 7),
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+
+        total += unit
+            .checked_mul(u32::try_from(amount).map_err(|_| invalid())?)
+            .ok_or_else(invalid)?;
+    }
+
+    Ok(total)
 }
 
 pub fn truncate_identity_bytes(encrypted_contents: &[u8]) -> Vec<u8> {
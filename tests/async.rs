@@ -0,0 +1,80 @@
+//! Tests for the async versions of the blocking library functions
+#![cfg(feature = "async")]
+
+use envio::crypto::{create_encryption_type, decrypt_async, encrypt_async};
+use envio::format::{LoadOptions, CURRENT_FORMAT_VERSION};
+use envio::{Env, EnvVec, Profile};
+
+const KEY: &str = "correct horse battery staple";
+
+#[tokio::test]
+async fn profile_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("async.env");
+
+    let envs: EnvVec = [Env::from_key_value(
+        "HOST".to_string(),
+        "localhost".to_string(),
+    )]
+    .into_iter()
+    .collect();
+
+    let mut profile = Profile::new(
+        "async".to_string(),
+        envs,
+        path.clone(),
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+    );
+    profile.push_changes_async().await.unwrap();
+
+    let (mut profile, report) = Profile::load_async(
+        path.to_str().unwrap(),
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+        &LoadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.found_version, CURRENT_FORMAT_VERSION);
+    assert_eq!(profile.get_env("HOST").unwrap(), "localhost");
+
+    profile.insert_env("PORT".to_string(), "8080".to_string());
+    profile.push_changes_async().await.unwrap();
+
+    let (profile, _) = Profile::load_async(
+        path.to_str().unwrap(),
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+        &LoadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(profile.get_env("PORT").unwrap(), "8080");
+}
+
+#[tokio::test]
+async fn encryption_round_trip() {
+    let encryption_type = create_encryption_type(KEY.to_string(), "age").unwrap();
+    let data = b"KEY=value".to_vec();
+
+    let encrypted = encrypt_async(encryption_type.as_ref(), data.clone())
+        .await
+        .unwrap();
+    assert_ne!(encrypted, data);
+
+    let decrypted = decrypt_async(encryption_type.as_ref(), encrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted, data);
+}
+
+#[test]
+fn load_async_can_be_spawned() {
+    fn assert_send<T: Send>(_: T) {}
+
+    assert_send(Profile::load_async(
+        "profile",
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+        &LoadOptions::default(),
+    ));
+}
@@ -0,0 +1,47 @@
+//! Fixtures shared by the tests that go through the default profiles
+//! directory
+//!
+//! The profiles are kept in a temporary directory that `ENVIO_HOME` points to.
+
+// Every test crate compiles this module, but not every one uses all of it
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use envio::crypto::create_encryption_type;
+use envio::{Env, EnvVec, Profile};
+
+pub const KEY: &str = "correct horse battery staple";
+
+/// Get the profiles directory, inside of a temporary envio home directory
+/// shared by all the tests of a test crate
+pub fn profiles_dir() -> PathBuf {
+    static HOME: OnceLock<tempfile::TempDir> = OnceLock::new();
+
+    let home = HOME.get_or_init(|| {
+        let home = tempfile::tempdir().unwrap();
+        std::env::set_var("ENVIO_HOME", home.path());
+        std::fs::create_dir_all(home.path().join("profiles")).unwrap();
+        home
+    });
+
+    home.path().join("profiles")
+}
+
+/// Build a profile in the profiles directory, encrypted with [KEY]
+pub fn profile(name: &str, envs: Vec<Env>) -> Profile {
+    let envs: EnvVec = envs.into_iter().collect();
+
+    Profile::new(
+        name.to_string(),
+        envs,
+        profiles_dir().join(format!("{}.env", name)),
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+    )
+}
+
+/// Build a profile and write it to the profiles directory
+pub fn save_profile(name: &str, envs: Vec<Env>) {
+    profile(name, envs).push_changes().unwrap();
+}
@@ -0,0 +1,193 @@
+//! Tests for deserializing environment variables into user defined types
+
+use serde::Deserialize;
+
+use envio::de::{from_envs, DeserializeOptions, KeyCase};
+use envio::error::Error;
+use envio::{Env, EnvVec};
+
+fn envs(envs: &[(&str, &str)]) -> EnvVec {
+    envs.iter()
+        .map(|(k, v)| Env::from_key_value(k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Database {
+    host: String,
+    port: u16,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Config {
+    debug: bool,
+    workers: Option<usize>,
+    allowed_hosts: Vec<String>,
+    database: Database,
+}
+
+#[test]
+fn nested_structs() {
+    let config: Config = from_envs(
+        &envs(&[
+            ("DEBUG", "true"),
+            ("WORKERS", "4"),
+            ("ALLOWED_HOSTS", "localhost,example.com"),
+            ("DATABASE__HOST", "db"),
+            ("DATABASE__PORT", "5432"),
+        ]),
+        &DeserializeOptions::new(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            debug: true,
+            workers: Some(4),
+            allowed_hosts: vec!["localhost".to_string(), "example.com".to_string()],
+            database: Database {
+                host: "db".to_string(),
+                port: 5432,
+            },
+        }
+    );
+}
+
+#[test]
+fn missing_options_are_none() {
+    #[derive(Deserialize)]
+    struct Cfg {
+        workers: Option<usize>,
+        database: Option<Database>,
+    }
+
+    let cfg: Cfg = from_envs(&envs(&[]), &DeserializeOptions::new()).unwrap();
+
+    assert_eq!(cfg.workers, None);
+    assert_eq!(cfg.database, None);
+}
+
+#[test]
+fn defaults_are_used_for_missing_fields() {
+    fn default_port() -> u16 {
+        8080
+    }
+
+    #[derive(Deserialize)]
+    struct Cfg {
+        #[serde(default = "default_port")]
+        port: u16,
+        #[serde(default)]
+        verbose: bool,
+    }
+
+    let cfg: Cfg = from_envs(&envs(&[]), &DeserializeOptions::new()).unwrap();
+
+    assert_eq!(cfg.port, 8080);
+    assert!(!cfg.verbose);
+}
+
+#[test]
+fn prefix_filters_and_is_stripped() {
+    #[derive(Deserialize)]
+    struct Cfg {
+        port: u16,
+    }
+
+    let cfg: Cfg = from_envs(
+        &envs(&[("APP_PORT", "80"), ("PORT", "1"), ("OTHER_PORT", "2")]),
+        &DeserializeOptions::new().prefix("APP_"),
+    )
+    .unwrap();
+
+    assert_eq!(cfg.port, 80);
+}
+
+#[test]
+fn case_conversion() {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Camel {
+        database_url: String,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(non_snake_case)]
+    struct Preserved {
+        DATABASE_URL: String,
+    }
+
+    let envs = envs(&[("DATABASE_URL", "postgres://")]);
+
+    let camel: Camel =
+        from_envs(&envs, &DeserializeOptions::new().case(KeyCase::CamelCase)).unwrap();
+    let preserved: Preserved =
+        from_envs(&envs, &DeserializeOptions::new().case(KeyCase::Preserve)).unwrap();
+
+    assert_eq!(camel.database_url, "postgres://");
+    assert_eq!(preserved.DATABASE_URL, "postgres://");
+}
+
+#[test]
+fn unknown_keys_are_ignored() {
+    #[derive(Deserialize)]
+    struct Cfg {
+        port: u16,
+        db: Db,
+    }
+
+    #[derive(Deserialize)]
+    struct Db {
+        host: String,
+    }
+
+    // `AWS` only exists as a group of nested keys
+    let cfg: Cfg = from_envs(
+        &envs(&[
+            ("PORT", "80"),
+            ("DB__HOST", "h"),
+            ("DB__UNUSED", "x"),
+            ("AWS__REGION", "eu"),
+            ("UNUSED", "y"),
+        ]),
+        &DeserializeOptions::new(),
+    )
+    .unwrap();
+
+    assert_eq!(cfg.port, 80);
+    assert_eq!(cfg.db.host, "h");
+}
+
+#[test]
+fn errors_name_the_key_but_not_the_value() {
+    let result: Result<Database, Error> = from_envs(
+        &envs(&[("DATABASE__HOST", "db"), ("DATABASE__PORT", "secret-port")]),
+        &DeserializeOptions::new().prefix("DATABASE__"),
+    );
+
+    match result {
+        Err(Error::InvalidEnv { key, message }) => {
+            assert_eq!(key, "DATABASE__PORT");
+            assert!(!message.contains("secret-port"), "{}", message);
+        }
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn missing_fields_name_the_nested_key() {
+    let result: Result<Config, Error> = from_envs(
+        &envs(&[
+            ("DEBUG", "false"),
+            ("ALLOWED_HOSTS", ""),
+            ("DATABASE__HOST", "db"),
+        ]),
+        &DeserializeOptions::new(),
+    );
+
+    match result {
+        Err(Error::EnvDoesNotExist(key)) => assert_eq!(key, "DATABASE__PORT"),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
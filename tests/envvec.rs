@@ -0,0 +1,139 @@
+//! Tests for the key handling of `EnvVec`
+
+use serde::Serialize;
+
+use envio::error::Error;
+use envio::{Env, EnvVec};
+
+fn env(key: &str, value: &str) -> Env {
+    Env::from_key_value(key.to_string(), value.to_string())
+}
+
+fn names<'a>(envs: impl Iterator<Item = &'a Env>) -> Vec<&'a str> {
+    envs.map(|e| e.name.as_str()).collect()
+}
+
+/// The layout `EnvVec` was serialized with when it was a plain list
+#[derive(Serialize)]
+struct OldEnvVec {
+    envs: Vec<Env>,
+}
+
+#[test]
+fn insert_overwrites_in_place() {
+    let mut envs: EnvVec = [env("A", "1"), env("B", "2")].into_iter().collect();
+
+    let old = envs.insert(env("A", "3"));
+
+    assert_eq!(old.unwrap().value, "1");
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs.get("A").unwrap(), "3");
+    assert_eq!(names(envs.iter()), ["A", "B"]);
+}
+
+#[test]
+fn try_insert_refuses_duplicates() {
+    let mut envs: EnvVec = [env("A", "1")].into_iter().collect();
+
+    assert!(matches!(
+        envs.try_insert(env("A", "2")),
+        Err(Error::EnvExists(key)) if key == "A"
+    ));
+    assert_eq!(envs.get("A").unwrap(), "1");
+
+    envs.try_insert(env("B", "2")).unwrap();
+    assert_eq!(envs.len(), 2);
+}
+
+#[test]
+fn collecting_keeps_the_last_duplicate() {
+    let envs: EnvVec = vec![env("A", "1"), env("B", "2"), env("A", "3")].into();
+
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs.get("A").unwrap(), "3");
+    assert_eq!(names(envs.iter()), ["A", "B"]);
+}
+
+#[test]
+fn entry() {
+    let mut envs = EnvVec::new();
+
+    let entry = envs.entry("A".to_string());
+    assert!(!entry.is_occupied());
+    entry
+        .and_modify(|mut e| e.value_mut().push('!'))
+        .or_insert("1".to_string());
+    assert_eq!(envs.get("A").unwrap(), "1");
+
+    let entry = envs.entry("A".to_string());
+    assert!(entry.is_occupied());
+    entry
+        .and_modify(|mut e| e.value_mut().push('!'))
+        .or_insert_with(|| unreachable!());
+    assert_eq!(envs.get("A").unwrap(), "1!");
+
+    let mut inserted = envs.entry("B".to_string()).or_insert("2".to_string());
+    *inserted.sensitive_mut() = true;
+    assert_eq!(inserted.name, "B");
+    assert!(envs.get_env("B").unwrap().sensitive);
+}
+
+#[test]
+fn mutation_keeps_the_key() {
+    let mut envs: EnvVec = [env("A", "1"), env("B", "2")].into_iter().collect();
+
+    for mut e in envs.iter_mut() {
+        e.value_mut().push('0');
+        e.tags_mut().push("t".to_string());
+    }
+
+    let mut b = envs.get_mut("B").unwrap();
+    *b.comment_mut() = Some("comment".to_string());
+
+    assert_eq!(envs.get("A").unwrap(), "10");
+    assert_eq!(envs.get("B").unwrap(), "20");
+    assert_eq!(
+        envs.get_env("B").unwrap().comment.as_deref(),
+        Some("comment")
+    );
+    assert!(envs.iter().all(|e| e.has_tag("t")));
+    assert!(envs.get_mut("C").is_none());
+}
+
+#[test]
+fn sorted_iteration_leaves_the_order_alone() {
+    let mut envs: EnvVec = [env("C", "3"), env("A", "1"), env("B", "2")]
+        .into_iter()
+        .collect();
+
+    assert_eq!(names(envs.iter_sorted()), ["A", "B", "C"]);
+    assert_eq!(names(envs.iter()), ["C", "A", "B"]);
+
+    envs.remove("A");
+    assert_eq!(names(envs.iter()), ["C", "B"]);
+
+    envs.sort_keys();
+    assert_eq!(names(envs.iter()), ["B", "C"]);
+}
+
+#[test]
+fn old_list_layout_is_deserialized() {
+    let old = bincode::serialize(&OldEnvVec {
+        envs: vec![env("A", "1"), env("B", "2"), env("A", "3")],
+    })
+    .unwrap();
+
+    let envs: EnvVec = bincode::deserialize(&old).unwrap();
+
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs.get("A").unwrap(), "3");
+    assert_eq!(names(envs.iter()), ["A", "B"]);
+
+    // The current layout is still the plain list, without duplicates
+    let current = bincode::serialize(&envs).unwrap();
+    let expected = bincode::serialize(&OldEnvVec {
+        envs: vec![env("A", "3"), env("B", "2")],
+    })
+    .unwrap();
+    assert_eq!(current, expected);
+}
@@ -0,0 +1,355 @@
+//! Round-trip tests for the escaping of exported values
+//!
+//! Every value is escaped, written the way `envio export` writes it and read
+//! back, which has to reproduce it byte for byte.
+
+use envio::{dotenv, escape};
+
+/// Values that are easy to corrupt, plus one with every printable and control
+/// ASCII character except NUL, which environment variables can not hold
+fn values() -> Vec<String> {
+    let mut values: Vec<String> = [
+        "",
+        "plain",
+        "with spaces",
+        "  leading and trailing  ",
+        "it's",
+        "say \"hi\"",
+        "'single' and \"double\"",
+        "# not a comment",
+        "value # not a comment either",
+        "$HOME ${HOME} $(whoami) `whoami`",
+        "back\\slash \\n \\' \\\" \\$",
+        "trailing backslash\\",
+        "line\nbreak",
+        "windows\r\nline break",
+        "\n\nsurrounded\n\n",
+        "tab\there",
+        "export KEY=value",
+        "KEY=value",
+        "!history !! !$",
+        "'\\''",
+        "unicode: héllo wörld ✓ 🔑 ‘quotes’",
+    ]
+    .iter()
+    .map(|v| v.to_string())
+    .collect();
+
+    values.push((1u8..=127).map(char::from).collect());
+    values
+}
+
+fn parse_single(buffer: &str) -> String {
+    let entries = dotenv::parse(buffer).unwrap();
+    assert_eq!(entries.len(), 1, "{:?}", buffer);
+    assert_eq!(entries[0].key, "KEY");
+
+    entries[0].value.clone()
+}
+
+#[test]
+fn dotenv_round_trip() {
+    for value in values() {
+        let buffer = format!("KEY={}\n", escape::dotenv(&value));
+
+        assert_eq!(parse_single(&buffer), value, "{:?}", buffer);
+    }
+}
+
+#[test]
+fn dotenv_round_trip_of_many_keys() {
+    let values = values();
+    let buffer: String = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| format!("KEY_{}={}\n", i, escape::dotenv(value)))
+        .collect();
+
+    let entries = dotenv::parse(&buffer).unwrap();
+    let parsed: Vec<String> = entries.into_iter().map(|e| e.value).collect();
+
+    assert_eq!(parsed, values);
+}
+
+#[test]
+fn sh_round_trip_through_parser() {
+    for value in values() {
+        let buffer = format!("export KEY={}\n", escape::sh(&value));
+
+        assert_eq!(parse_single(&buffer), value, "{:?}", buffer);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn sh_round_trip_through_shell() {
+    for value in values() {
+        let script = format!("export KEY={}\nprintf '%s' \"$KEY\"", escape::sh(&value));
+
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&script)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{:?}", script);
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            value,
+            "{:?}",
+            script
+        );
+    }
+}
+
+#[test]
+fn sh_leaves_safe_values_bare() {
+    assert_eq!(
+        escape::sh("postgres://db:5432/app"),
+        "postgres://db:5432/app"
+    );
+    assert_eq!(escape::sh(""), "''");
+    assert_eq!(escape::sh("it's"), "'it'\\''s'");
+}
+
+#[test]
+fn fish_round_trip() {
+    // Inside of single quotes fish only interprets `\\` and `\'`
+    fn unquote(quoted: &str) -> String {
+        let inner = &quoted[1..quoted.len() - 1];
+        let mut value = String::new();
+        let mut chars = inner.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next().unwrap() {
+                    c @ ('\\' | '\'') => value.push(c),
+                    c => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                },
+                '\'' => panic!("unescaped quote in {:?}", quoted),
+                c => value.push(c),
+            }
+        }
+
+        value
+    }
+
+    for value in values() {
+        let quoted = escape::fish(&value);
+
+        assert!(quoted.starts_with('\'') && quoted.ends_with('\''));
+        assert_eq!(unquote(&quoted), value, "{:?}", quoted);
+    }
+}
+
+/// Read back a single-quoted string in which quotes are written by doubling
+/// them, which is how PowerShell and elvish quote
+fn unquote_doubled(quoted: &str, quotes: &[char]) -> String {
+    let inner = quoted
+        .strip_prefix('\'')
+        .and_then(|q| q.strip_suffix('\''))
+        .unwrap();
+    let mut value = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if quotes.contains(&c) {
+            assert_eq!(chars.next(), Some(c), "undoubled quote in {:?}", quoted);
+        }
+        value.push(c);
+    }
+
+    value
+}
+
+/// Read back a double-quoted string with backslash escapes, `code` is the
+/// escape of a character code with `digits` hex digits
+fn unescape_double_quoted(quoted: &str, code: char, digits: usize) -> String {
+    let inner = quoted
+        .strip_prefix('"')
+        .and_then(|q| q.strip_suffix('"'))
+        .unwrap();
+    let mut value = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next().unwrap() {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                c @ ('\\' | '"') => value.push(c),
+                c if c == code => {
+                    let hex: String = chars.by_ref().take(digits).collect();
+                    let c = u32::from_str_radix(&hex, 16).unwrap();
+                    value.push(char::from_u32(c).unwrap());
+                }
+                c => panic!("unknown escape \\{} in {:?}", c, quoted),
+            },
+            '"' => panic!("unescaped quote in {:?}", quoted),
+            c => {
+                assert!(!c.is_ascii_control(), "control character in {:?}", quoted);
+                value.push(c);
+            }
+        }
+    }
+
+    value
+}
+
+#[test]
+fn pwsh_round_trip() {
+    let quotes = ['\'', '\u{2018}', '\u{2019}', '\u{201a}', '\u{201b}'];
+
+    for value in values() {
+        let quoted = escape::pwsh(&value);
+
+        assert_eq!(unquote_doubled(&quoted, &quotes), value, "{:?}", quoted);
+    }
+}
+
+#[test]
+fn elvish_round_trip() {
+    for value in values() {
+        let quoted = escape::elvish(&value);
+
+        assert_eq!(unquote_doubled(&quoted, &['\'']), value, "{:?}", quoted);
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    for value in values() {
+        let quoted = escape::json(&value);
+
+        assert_eq!(
+            unescape_double_quoted(&quoted, 'u', 4),
+            value,
+            "{:?}",
+            quoted
+        );
+    }
+}
+
+#[test]
+fn python_round_trip() {
+    for value in values() {
+        let quoted = escape::python(&value);
+
+        assert_eq!(
+            unescape_double_quoted(&quoted, 'x', 2),
+            value,
+            "{:?}",
+            quoted
+        );
+    }
+}
+
+/// tcsh quotes the way POSIX shells do, except that `!` is escaped outside of
+/// the quotes, which sh reads back the same way
+#[cfg(unix)]
+#[test]
+fn tcsh_round_trip_through_shell() {
+    for value in values() {
+        let quoted = match escape::tcsh(&value) {
+            Ok(quoted) => quoted,
+            Err(_) => {
+                assert!(value.contains(|c: char| c.is_control()), "{:?}", value);
+                continue;
+            }
+        };
+        let script = format!("printf '%s' {}", quoted);
+
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&script)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{:?}", script);
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            value,
+            "{:?}",
+            script
+        );
+    }
+}
+
+#[test]
+fn tcsh_escapes_history_substitution() {
+    assert_eq!(escape::tcsh("a!b").unwrap(), "'a'\\!'b'");
+    assert!(escape::tcsh("line\nbreak").is_err());
+}
+
+#[test]
+fn docker_env_file_round_trip() {
+    for value in values() {
+        let line = match escape::docker_env_file(&value) {
+            Ok(escaped) => format!("KEY={}", escaped),
+            Err(_) => {
+                assert!(value.contains(['\n', '\r']), "{:?} was rejected", value);
+                continue;
+            }
+        };
+
+        // Docker splits each line at the first `=` and keeps the rest as is
+        let (key, read) = line.split_once('=').unwrap();
+
+        assert_eq!(key, "KEY");
+        assert_eq!(read, value);
+    }
+}
+
+#[test]
+fn parse_joins_quoted_parts() {
+    assert_eq!(parse_single("KEY='it'\\''s'"), "it's");
+    assert_eq!(parse_single("KEY=\"a\"'b'\\c"), "abc");
+    assert_eq!(parse_single("KEY='a' # comment"), "a");
+}
+
+#[test]
+fn parse_strips_comments_from_unquoted_values() {
+    assert_eq!(parse_single("KEY=value # comment"), "value");
+    assert_eq!(parse_single("KEY=value\t# comment"), "value");
+    assert_eq!(parse_single("KEY=value #"), "value");
+
+    // A `#` that is not preceded by whitespace is part of the value
+    assert_eq!(parse_single("KEY=a#b"), "a#b");
+    assert_eq!(parse_single("KEY=#value"), "#value");
+}
+
+#[test]
+fn parse_rejects_content_after_quoted_values() {
+    assert_eq!(parse_single("KEY=\"a\"   "), "a");
+    assert_eq!(parse_single("KEY=\"a\"\t# comment"), "a");
+
+    assert!(dotenv::parse("KEY=\"a\" b").is_err());
+    assert!(dotenv::parse("KEY='a'b").is_err());
+    assert!(dotenv::parse("KEY='a' \"b\"").is_err());
+}
+
+#[test]
+fn parse_keeps_annotations() {
+    let entries =
+        dotenv::parse("# @comment the database\n# plain comment\nexport DB=\"x\"\nOTHER=y\n")
+            .unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        entries[0].annotations,
+        vec![("comment".to_string(), "the database".to_string())]
+    );
+    assert_eq!(entries[0].key, "DB");
+    assert!(entries[1].annotations.is_empty());
+}
+
+#[test]
+fn parse_rejects_unterminated_values() {
+    assert!(dotenv::parse("KEY=\"open").is_err());
+    assert!(dotenv::parse("KEY='open").is_err());
+    assert!(dotenv::parse("KEY='a'\\").is_err());
+}
@@ -0,0 +1,227 @@
+//! Tests for reading profiles written in the older formats and migrating them
+//!
+//! The fixtures mirror the layouts of the older formats, see the
+//! [format](envio::format) module, and are serialized the way the versions of
+//! envio that used them did.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use envio::crypto::{create_encryption_type, EncryptionType};
+use envio::error::Error;
+use envio::format::{LoadOptions, LoadReport, LoadWarning, CURRENT_FORMAT_VERSION};
+use envio::store::{MemoryStore, ProfileStore};
+use envio::{Env, EnvVec, Profile, ValueEncoding};
+
+const KEY: &str = "correct horse battery staple";
+const NAME: &str = "fixture";
+
+fn encryption_type() -> Box<dyn EncryptionType> {
+    create_encryption_type(KEY.to_string(), "age").unwrap()
+}
+
+/// Prefix a serialized profile with the header of a format version
+fn with_header(version: u32, body: Vec<u8>) -> Vec<u8> {
+    let mut payload = b"ENVIOPRF".to_vec();
+    payload.extend_from_slice(&version.to_le_bytes());
+    payload.extend(body);
+    payload
+}
+
+/// Encrypt a payload and store it as the profile [NAME]
+fn store_payload(payload: &[u8]) -> Arc<MemoryStore> {
+    let store = Arc::new(MemoryStore::new());
+    store
+        .write(NAME, &encryption_type().encrypt(payload).unwrap())
+        .unwrap();
+
+    store
+}
+
+fn load(
+    store: &Arc<MemoryStore>,
+    options: &LoadOptions,
+) -> envio::error::Result<(Profile, LoadReport)> {
+    Profile::load_from(store.clone(), NAME, encryption_type(), options)
+}
+
+#[derive(Serialize)]
+struct EnvV1 {
+    name: String,
+    value: String,
+    comment: Option<String>,
+    expiration_date: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+struct EnvVecV1 {
+    envs: Vec<EnvV1>,
+}
+
+#[derive(Serialize)]
+struct ProfileV1 {
+    name: String,
+    envs: EnvVecV1,
+    profile_file_path: PathBuf,
+    encryption_type: Box<dyn EncryptionType>,
+}
+
+fn v1_payload() -> Vec<u8> {
+    bincode::serialize(&ProfileV1 {
+        name: NAME.to_string(),
+        envs: EnvVecV1 {
+            envs: vec![
+                EnvV1 {
+                    name: "HOST".to_string(),
+                    value: "localhost".to_string(),
+                    comment: Some("the host".to_string()),
+                    expiration_date: None,
+                },
+                EnvV1 {
+                    name: "API_TOKEN".to_string(),
+                    value: "secret".to_string(),
+                    comment: None,
+                    expiration_date: NaiveDate::from_ymd_opt(2999, 1, 1),
+                },
+            ],
+        },
+        profile_file_path: PathBuf::new(),
+        encryption_type: encryption_type(),
+    })
+    .unwrap()
+}
+
+fn assert_migrated(report: &LoadReport, from_version: u32) {
+    assert_eq!(report.found_version, from_version);
+    assert!(report.migrated);
+    assert!(!report.written_back);
+    assert!(report
+        .warnings
+        .contains(&LoadWarning::Migrated { from_version }));
+}
+
+#[test]
+fn text_format_is_migrated() {
+    let store = store_payload(b"HOST=localhost\nURL=a=b\n");
+
+    let (profile, report) = load(&store, &LoadOptions::default()).unwrap();
+
+    assert_migrated(&report, 0);
+    assert!(report.warnings.contains(&LoadWarning::TextFormat));
+    assert_eq!(profile.get_env("HOST").unwrap(), "localhost");
+    assert_eq!(profile.get_env("URL").unwrap(), "a=b");
+}
+
+#[test]
+fn text_format_can_be_refused() {
+    let store = store_payload(b"HOST=localhost\n");
+    let refuse = || false;
+    let options = LoadOptions {
+        confirm_text_format: Some(&refuse),
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        load(&store, &options),
+        Err(Error::Deserialization(_))
+    ));
+}
+
+#[test]
+fn corrupted_text_is_not_taken_for_the_text_format() {
+    for payload in [
+        "several lines\nwithout any\nseparator",
+        "KEY WITH SPACES=value",
+        "=value",
+    ] {
+        let store = store_payload(payload.as_bytes());
+
+        assert!(
+            matches!(
+                load(&store, &LoadOptions::default()),
+                Err(Error::Deserialization(_))
+            ),
+            "{:?}",
+            payload
+        );
+    }
+}
+
+#[test]
+fn version_1_is_migrated() {
+    let store = store_payload(&v1_payload());
+
+    let (profile, report) = load(&store, &LoadOptions::default()).unwrap();
+
+    assert_migrated(&report, 1);
+
+    let host = profile.envs.get_env("HOST").unwrap();
+    assert_eq!(host.value, "localhost");
+    assert_eq!(host.comment.as_deref(), Some("the host"));
+    assert!(!host.sensitive);
+
+    // The sensitivity is derived from the name
+    let token = profile.envs.get_env("API_TOKEN").unwrap();
+    assert!(token.sensitive);
+    assert_eq!(token.expiration_date, NaiveDate::from_ymd_opt(2999, 1, 1));
+    assert_eq!(token.encoding, ValueEncoding::Text);
+    assert!(token.tags.is_empty());
+    assert_eq!(token.rotated_at, None);
+    assert!(profile.expiration_policy.is_none());
+}
+
+#[test]
+fn migrated_profiles_are_only_written_back_when_asked() {
+    let store = store_payload(&v1_payload());
+    let stored = store.read(NAME).unwrap();
+
+    load(&store, &LoadOptions::default()).unwrap();
+    assert_eq!(store.read(NAME).unwrap(), stored);
+
+    let options = LoadOptions {
+        write_back: true,
+        ..Default::default()
+    };
+    let (_, report) = load(&store, &options).unwrap();
+    assert!(report.written_back);
+
+    let (profile, report) = load(&store, &LoadOptions::default()).unwrap();
+    assert_eq!(report.found_version, CURRENT_FORMAT_VERSION);
+    assert!(!report.migrated);
+    assert_eq!(profile.get_env("HOST").unwrap(), "localhost");
+}
+
+#[test]
+fn current_format_round_trip() {
+    let store = Arc::new(MemoryStore::new());
+    let envs: EnvVec = [Env::from_key_value(
+        "HOST".to_string(),
+        "localhost".to_string(),
+    )]
+    .into_iter()
+    .collect();
+
+    let mut profile = Profile::new(NAME.to_string(), envs, PathBuf::new(), encryption_type());
+    profile.set_store(store.clone());
+    profile.push_changes().unwrap();
+
+    let (profile, report) = load(&store, &LoadOptions::default()).unwrap();
+
+    assert_eq!(report.found_version, CURRENT_FORMAT_VERSION);
+    assert!(!report.migrated);
+    assert!(report.warnings.is_empty());
+    assert_eq!(profile.get_env("HOST").unwrap(), "localhost");
+}
+
+#[test]
+fn newer_format_versions_are_refused() {
+    let store = store_payload(&with_header(CURRENT_FORMAT_VERSION + 1, Vec::new()));
+
+    assert!(matches!(
+        load(&store, &LoadOptions::default()),
+        Err(Error::UnsupportedFormatVersion(v)) if v == CURRENT_FORMAT_VERSION + 1
+    ));
+}
@@ -0,0 +1,120 @@
+//! Tests for the `Loader` builder
+//!
+//! The profiles are kept in a temporary directory that `ENVIO_HOME` points to,
+//! see [common].
+
+mod common;
+
+use std::process::Command;
+
+use envio::{Env, Loader};
+
+use common::{save_profile, KEY};
+
+fn env(name: &str, value: &str) -> Env {
+    Env::from_key_value(name.to_string(), value.to_string())
+}
+
+#[test]
+fn load_filters_and_strips_the_prefix() {
+    save_profile(
+        "prefix",
+        vec![
+            env("APP_HOST", "localhost"),
+            env("APP_PORT", "8080"),
+            env("APP_", "nameless"),
+            env("OTHER", "ignored"),
+        ],
+    );
+
+    let envs = Loader::new("prefix")
+        .key_provider(|| KEY.to_string())
+        .prefix("APP_")
+        .strip_prefix(true)
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        envs.iter().collect::<Vec<_>>(),
+        vec![("HOST", "localhost"), ("PORT", "8080")]
+    );
+}
+
+#[test]
+fn load_filters_by_tags() {
+    let mut tagged = env("DB_PASSWORD", "hunter2");
+    tagged.tags = vec!["db".to_string()];
+
+    save_profile("tags", vec![tagged, env("LOG_LEVEL", "debug")]);
+
+    let envs = Loader::new("tags")
+        .key_provider(|| KEY.to_string())
+        .tags(["db", "cache"])
+        .load()
+        .unwrap();
+
+    assert_eq!(envs.len(), 1);
+    assert_eq!(envs.get("DB_PASSWORD"), Some("hunter2"));
+}
+
+#[test]
+fn apply_to_process_respects_existing_variables() {
+    save_profile(
+        "process",
+        vec![
+            env("ENVIO_TEST_LOADER_SET", "from-profile"),
+            env("ENVIO_TEST_LOADER_NEW", "from-profile"),
+        ],
+    );
+    std::env::set_var("ENVIO_TEST_LOADER_SET", "existing");
+
+    let dry_run = Loader::new("process")
+        .key_provider(|| KEY.to_string())
+        .override_existing(true)
+        .dry_run(true)
+        .load()
+        .unwrap();
+
+    assert_eq!(dry_run.apply_to_process().len(), 2);
+    assert!(std::env::var("ENVIO_TEST_LOADER_NEW").is_err());
+
+    let applied = Loader::new("process")
+        .key_provider(|| KEY.to_string())
+        .load()
+        .unwrap()
+        .apply_to_process();
+
+    assert_eq!(applied, vec!["ENVIO_TEST_LOADER_NEW"]);
+    assert_eq!(std::env::var("ENVIO_TEST_LOADER_SET").unwrap(), "existing");
+    assert_eq!(
+        std::env::var("ENVIO_TEST_LOADER_NEW").unwrap(),
+        "from-profile"
+    );
+}
+
+#[test]
+fn apply_to_command_leaves_the_process_untouched() {
+    save_profile(
+        "command",
+        vec![
+            env("ENVIO_TEST_COMMAND_A", "a"),
+            env("ENVIO_TEST_COMMAND_B", "b"),
+        ],
+    );
+
+    let mut command = Command::new("true");
+    command.env("ENVIO_TEST_COMMAND_A", "explicit");
+
+    let applied = Loader::new("command")
+        .key_provider(|| KEY.to_string())
+        .load()
+        .unwrap()
+        .apply_to_command(&mut command);
+
+    assert_eq!(applied, vec!["ENVIO_TEST_COMMAND_B"]);
+    assert!(std::env::var("ENVIO_TEST_COMMAND_B").is_err());
+
+    let envs: Vec<_> = command.get_envs().collect();
+    assert!(envs.contains(&("ENVIO_TEST_COMMAND_A".as_ref(), Some("explicit".as_ref()))));
+    assert!(envs.contains(&("ENVIO_TEST_COMMAND_B".as_ref(), Some("b".as_ref()))));
+}
@@ -0,0 +1,99 @@
+//! Tests for the public macros and the byte oriented `Profile` API
+//!
+//! The profiles are kept in a temporary directory that `ENVIO_HOME` points to,
+//! see [common].
+
+mod common;
+
+use envio::crypto::{create_encryption_type, get_encryption_type_from_bytes};
+use envio::error::Error;
+use envio::{Env, Profile};
+
+use common::{profiles_dir, KEY};
+
+fn sample_profile(name: &str, envs: &[(&str, &str)]) -> Profile {
+    common::profile(
+        name,
+        envs.iter()
+            .map(|(k, v)| Env::from_key_value(k.to_string(), v.to_string()))
+            .collect(),
+    )
+}
+
+#[test]
+fn bytes_round_trip() {
+    let profile = sample_profile("bytes", &[("HOST", "localhost"), ("PORT", "8080")]);
+
+    let bytes = profile.to_bytes().unwrap();
+
+    let mut encryption_type = get_encryption_type_from_bytes(&bytes).unwrap();
+    assert_eq!(encryption_type.as_string(), "age");
+    encryption_type.set_key(KEY.to_string());
+
+    let loaded = Profile::from_bytes("bytes", &bytes, encryption_type).unwrap();
+
+    assert_eq!(loaded.name, "bytes");
+    assert_eq!(loaded.envs.keys(), vec!["HOST", "PORT"]);
+    assert_eq!(loaded.get_env("PORT").unwrap(), "8080");
+}
+
+#[test]
+fn from_bytes_with_wrong_key_fails() {
+    let bytes = sample_profile("wrong-key", &[("A", "1")])
+        .to_bytes()
+        .unwrap();
+
+    let encryption_type = create_encryption_type("not the key".to_string(), "age").unwrap();
+
+    assert!(Profile::from_bytes("wrong-key", &bytes, encryption_type).is_err());
+}
+
+#[test]
+fn from_bytes_rejects_garbage() {
+    let bytes = b"definitely not an encrypted profile";
+
+    let encryption_type = get_encryption_type_from_bytes(bytes).unwrap();
+
+    assert!(Profile::from_bytes("garbage", bytes, encryption_type).is_err());
+}
+
+#[test]
+fn load_profile_macro_uses_the_key_closure() {
+    sample_profile("load-profile", &[("DATABASE_URL", "postgres://localhost")])
+        .push_changes()
+        .unwrap();
+
+    let profile = envio::load_profile!("load-profile", || KEY.to_string()).unwrap();
+
+    assert_eq!(
+        profile.get_env("DATABASE_URL").unwrap(),
+        "postgres://localhost"
+    );
+}
+
+#[test]
+fn load_macro_sets_the_environment_variables() {
+    sample_profile(
+        "load",
+        &[
+            ("ENVIO_TEST_LOAD_A", "first"),
+            ("ENVIO_TEST_LOAD_B", "second"),
+        ],
+    )
+    .push_changes()
+    .unwrap();
+
+    envio::load!("load", || KEY.to_string()).unwrap();
+
+    assert_eq!(std::env::var("ENVIO_TEST_LOAD_A").unwrap(), "first");
+    assert_eq!(std::env::var("ENVIO_TEST_LOAD_B").unwrap(), "second");
+}
+
+#[test]
+fn load_macro_reports_missing_profiles() {
+    profiles_dir();
+
+    let result = envio::load!("does-not-exist", || KEY.to_string());
+
+    assert!(matches!(result, Err(Error::ProfileDoesNotExist(_))));
+}
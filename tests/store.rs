@@ -0,0 +1,312 @@
+//! Tests for the profile stores and for loading profiles through them
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+use envio::crypto::{create_encryption_type, get_encryption_type_from_bytes};
+use envio::error::Error;
+use envio::format::LoadOptions;
+use envio::store::{FsStore, MemoryStore, ProfileStore, StoredProfile};
+use envio::{Env, EnvVec, Loader, Profile, ProfileMetadata};
+
+const KEY: &str = "correct horse battery staple";
+
+fn push_profile(store: Arc<dyn ProfileStore>, name: &str, envs: &[(&str, &str)]) {
+    let envs: EnvVec = envs
+        .iter()
+        .map(|(k, v)| Env::from_key_value(k.to_string(), v.to_string()))
+        .collect();
+
+    let mut profile = Profile::new(
+        name.to_string(),
+        envs,
+        store.path(name).unwrap_or_default(),
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+    );
+    profile.set_store(store);
+    profile.push_changes().unwrap();
+}
+
+fn load(store: Arc<dyn ProfileStore>, name: &str) -> Profile {
+    let mut encryption_type = get_encryption_type_from_bytes(&store.read(name).unwrap()).unwrap();
+    encryption_type.set_key(KEY.to_string());
+
+    Profile::load_from(store, name, encryption_type, &LoadOptions::default())
+        .unwrap()
+        .0
+}
+
+/// Run the same checks against every store
+fn check_store(store: Arc<dyn ProfileStore>) {
+    assert!(store.list().unwrap().is_empty());
+    assert!(matches!(
+        store.read("missing"),
+        Err(Error::ProfileDoesNotExist(_))
+    ));
+
+    push_profile(store.clone(), "beta", &[("B", "2")]);
+    push_profile(store.clone(), "alpha", &[("A", "1")]);
+
+    assert_eq!(store.list().unwrap(), vec!["alpha", "beta"]);
+    assert!(store.exists("alpha"));
+
+    let mut profile = load(store.clone(), "alpha");
+    assert!(profile.metadata.created_at.is_some());
+
+    profile.insert_env("C".to_string(), "3".to_string());
+    let expires = NaiveDate::from_ymd_opt(2999, 1, 1).unwrap();
+    *profile.envs.get_mut("C").unwrap().expiration_date_mut() = Some(expires);
+    profile.push_changes().unwrap();
+
+    assert_eq!(load(store.clone(), "alpha").get_env("C").unwrap(), "3");
+
+    // The expiration dates can be read without decrypting the profile
+    let expiration_dates = BTreeMap::from([("C".to_string(), expires)]);
+    assert_eq!(
+        store.read_metadata("alpha").unwrap().expiration_dates,
+        Some(expiration_dates.clone())
+    );
+
+    let metadata = ProfileMetadata {
+        description: Some("first".to_string()),
+        expiration_dates: Some(expiration_dates),
+        ..Default::default()
+    };
+    store.write_metadata("alpha", &metadata).unwrap();
+    assert_eq!(store.read_metadata("alpha").unwrap(), metadata);
+
+    store.delete("alpha").unwrap();
+
+    assert!(!store.exists("alpha"));
+    assert_eq!(store.read_metadata("alpha").unwrap(), Default::default());
+    assert!(matches!(
+        store.delete("alpha"),
+        Err(Error::ProfileDoesNotExist(_))
+    ));
+}
+
+/// Adding several profiles at once adds either all of them or none, like
+/// `envio store migrate` relies on
+fn check_add_all(store: Arc<dyn ProfileStore>) {
+    store.write("existing", b"original").unwrap();
+
+    let profiles: Vec<StoredProfile> = ["added", "existing"]
+        .iter()
+        .map(|name| StoredProfile {
+            name: name.to_string(),
+            content: b"copied".to_vec(),
+            metadata: ProfileMetadata {
+                description: Some("copied".to_string()),
+                ..Default::default()
+            },
+        })
+        .collect();
+
+    assert!(matches!(
+        store.add_all(&profiles),
+        Err(Error::ProfileExists(name)) if name == "existing"
+    ));
+
+    assert_eq!(store.list().unwrap(), vec!["existing"]);
+    assert_eq!(store.read("existing").unwrap(), b"original");
+    assert_eq!(store.read_metadata("added").unwrap(), Default::default());
+
+    store.add_all(&profiles[..1]).unwrap();
+
+    assert_eq!(store.read("added").unwrap(), b"copied");
+    assert_eq!(store.read_metadata("added").unwrap(), profiles[0].metadata);
+}
+
+#[test]
+fn memory_store() {
+    check_store(Arc::new(MemoryStore::new()));
+    check_add_all(Arc::new(MemoryStore::new()));
+}
+
+#[test]
+fn fs_store() {
+    let dir = tempfile::tempdir().unwrap();
+
+    check_store(Arc::new(FsStore::new(dir.path().join("profiles"))));
+    check_add_all(Arc::new(FsStore::new(dir.path().join("other"))));
+}
+
+#[test]
+fn fs_store_never_touches_files_outside_of_its_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FsStore::new(dir.path().join("profiles"));
+
+    // A file named like the profile in the current directory is not the
+    // profile
+    let cwd = tempfile::tempdir().unwrap();
+    std::fs::write(cwd.path().join("victim"), "unrelated").unwrap();
+    std::env::set_current_dir(cwd.path()).unwrap();
+
+    assert!(!store.exists("victim"));
+    assert!(matches!(
+        store.delete("victim"),
+        Err(Error::ProfileDoesNotExist(_))
+    ));
+    drop(store.lock("victim").unwrap());
+
+    assert_eq!(
+        std::fs::read_to_string(cwd.path().join("victim")).unwrap(),
+        "unrelated"
+    );
+    assert!(!cwd.path().join(".victim.lock").exists());
+
+    // Paths can be read from but not written to or deleted
+    assert!(store.write("./victim", b"content").is_err());
+    assert!(store.delete("./victim").is_err());
+    assert_eq!(store.read("./victim").unwrap(), b"unrelated");
+}
+
+#[test]
+fn profiles_loaded_from_a_path_are_pushed_back_to_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.env");
+    let path_name = path.to_str().unwrap();
+
+    let mut profile = Profile::new(
+        "file".to_string(),
+        EnvVec::new(),
+        path.clone(),
+        create_encryption_type(KEY.to_string(), "age").unwrap(),
+    );
+    profile.push_changes().unwrap();
+
+    let load = || {
+        Profile::load(
+            path_name,
+            create_encryption_type(KEY.to_string(), "age").unwrap(),
+            &LoadOptions::default(),
+        )
+        .unwrap()
+        .0
+    };
+
+    let mut profile = load();
+    profile.insert_env("A".to_string(), "1".to_string());
+    profile.push_changes().unwrap();
+
+    assert_eq!(load().get_env("A").unwrap(), "1");
+
+    let mut files: Vec<String> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+
+    assert_eq!(files, vec!["file.env", "file.meta"]);
+}
+
+#[test]
+fn locks_are_exclusive() {
+    let store = MemoryStore::new();
+
+    let lock = store.lock("profile").unwrap();
+    assert!(store.try_lock("profile").unwrap().is_none());
+    assert!(store.try_lock("other").unwrap().is_some());
+
+    drop(lock);
+    assert!(store.try_lock("profile").unwrap().is_some());
+}
+
+#[test]
+fn loader_reads_from_the_store() {
+    let store = Arc::new(MemoryStore::new());
+    push_profile(store.clone(), "loader", &[("HOST", "localhost")]);
+
+    let envs = Loader::new("loader")
+        .store(store)
+        .key_provider(|| KEY.to_string())
+        .load()
+        .unwrap();
+
+    assert_eq!(envs.get("HOST"), Some("localhost"));
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+
+    use envio::store::SqliteStore;
+
+    #[test]
+    fn sqlite_store() {
+        let dir = tempfile::tempdir().unwrap();
+
+        check_store(Arc::new(
+            SqliteStore::open(dir.path().join("profiles.db")).unwrap(),
+        ));
+        check_add_all(Arc::new(
+            SqliteStore::open(dir.path().join("other.db")).unwrap(),
+        ));
+    }
+
+    #[test]
+    fn replaced_versions_are_kept() {
+        let store = SqliteStore::open_in_memory().unwrap().history_limit(2);
+
+        for content in ["v1", "v2", "v3", "v4"] {
+            store.write("profile", content.as_bytes()).unwrap();
+        }
+
+        let history: Vec<Vec<u8>> = store
+            .history("profile")
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.content)
+            .collect();
+
+        assert_eq!(history, vec![b"v3".to_vec(), b"v2".to_vec()]);
+
+        store.delete("profile").unwrap();
+        assert!(store.history("profile").unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_transactions_are_rolled_back() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store.write("kept", b"content").unwrap();
+
+        let result: envio::error::Result<()> = store.transaction(|tx| {
+            tx.write("added", b"content")?;
+            tx.delete("kept")?;
+            tx.delete("missing")
+        });
+
+        assert!(matches!(result, Err(Error::ProfileDoesNotExist(_))));
+        assert_eq!(store.list().unwrap(), vec!["kept"]);
+    }
+
+    #[test]
+    fn lock_files_stay_next_to_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteStore::open(dir.path().join("db").join("profiles.db")).unwrap();
+
+        for name in ["", "../escaped", "/tmp/escaped", "nested/profile"] {
+            assert!(store.lock(name).is_err(), "{:?}", name);
+            assert!(store.try_lock(name).is_err(), "{:?}", name);
+        }
+
+        drop(store.lock("profile").unwrap());
+
+        assert!(dir.path().join("db").join(".profile.lock").exists());
+        assert!(!dir.path().join(".escaped.lock").exists());
+    }
+
+    #[test]
+    fn metadata_is_recorded_on_push() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(SqliteStore::open(dir.path().join("profiles.db")).unwrap());
+
+        push_profile(store.clone(), "profile", &[("A", "1"), ("B", "2")]);
+
+        let metadata = store.read_metadata("profile").unwrap();
+        assert_eq!(metadata.encryption_type.as_deref(), Some("age"));
+        assert_eq!(metadata.env_count, Some(2));
+    }
+}